@@ -1,6 +1,7 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::wrong_self_convention)]
 
+use meilisearch_types::batches::BatchId;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::keys::Key;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
@@ -57,6 +58,8 @@ pub enum Version {
 #[serde(rename_all = "camelCase")]
 pub struct TaskDump {
     pub uid: TaskId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub batch_uid: Option<BatchId>,
     #[serde(default)]
     pub index_uid: Option<String>,
     pub status: Status,
@@ -100,6 +103,9 @@ pub enum KindDump {
     DocumentDeletion {
         documents_ids: Vec<String>,
     },
+    DocumentDeletionByFilter {
+        filter: serde_json::Value,
+    },
     DocumentClear,
     Settings {
         settings: Box<meilisearch_types::settings::Settings<Unchecked>>,
@@ -135,6 +141,7 @@ impl From<Task> for TaskDump {
     fn from(task: Task) -> Self {
         TaskDump {
             uid: task.uid,
+            batch_uid: task.batch_uid,
             index_uid: task.index_uid().map(|uid| uid.to_string()),
             status: task.status,
             kind: task.kind.into(),
@@ -166,6 +173,9 @@ impl From<KindWithContent> for KindDump {
             KindWithContent::DocumentDeletion { documents_ids, .. } => {
                 KindDump::DocumentDeletion { documents_ids }
             }
+            KindWithContent::DocumentDeletionByFilter { filter_expr, .. } => {
+                KindDump::DocumentDeletionByFilter { filter: filter_expr }
+            }
             KindWithContent::DocumentClear { .. } => KindDump::DocumentClear,
             KindWithContent::SettingsUpdate {
                 new_settings,
@@ -203,6 +213,7 @@ pub(crate) mod test {
 
     use big_s::S;
     use maplit::btreeset;
+    use meilisearch_types::batches::{Batch, BatchTimings};
     use meilisearch_types::index_uid::IndexUid;
     use meilisearch_types::keys::{Action, Key};
     use meilisearch_types::milli::update::Setting;
@@ -210,6 +221,7 @@ pub(crate) mod test {
     use meilisearch_types::settings::{Checked, Settings};
     use meilisearch_types::star_or::StarOr;
     use meilisearch_types::tasks::{Details, Status};
+    use roaring::RoaringBitmap;
     use serde_json::{json, Map, Value};
     use time::macros::datetime;
     use uuid::Uuid;
@@ -255,11 +267,18 @@ pub(crate) mod test {
             sortable_attributes: Setting::Set(btreeset! { S("age") }),
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            non_separator_tokens: Setting::NotSet,
+            separator_tokens: Setting::NotSet,
+            dictionary: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
+            proximity_precision: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            search_cutoff_ms: Setting::NotSet,
+            prefix_search: Setting::NotSet,
             _kind: std::marker::PhantomData,
         };
         settings.check()
@@ -270,6 +289,7 @@ pub(crate) mod test {
             (
                 TaskDump {
                     uid: 0,
+                    batch_uid: Some(0),
                     index_uid: Some(S("doggo")),
                     status: Status::Succeeded,
                     kind: KindDump::DocumentImport {
@@ -293,6 +313,7 @@ pub(crate) mod test {
             (
                 TaskDump {
                     uid: 1,
+                    batch_uid: None,
                     index_uid: Some(S("doggo")),
                     status: Status::Enqueued,
                     kind: KindDump::DocumentImport {
@@ -319,6 +340,7 @@ pub(crate) mod test {
             (
                 TaskDump {
                     uid: 5,
+                    batch_uid: None,
                     index_uid: Some(S("catto")),
                     status: Status::Enqueued,
                     kind: KindDump::IndexDeletion,
@@ -334,6 +356,20 @@ pub(crate) mod test {
         ]
     }
 
+    pub fn create_test_batches() -> Vec<Batch> {
+        vec![Batch {
+            uid: 0,
+            task_uids: RoaringBitmap::from_iter([0]),
+            details: json!({ "receivedDocuments": 12, "indexedDocuments": 10 })
+                .as_object()
+                .unwrap()
+                .clone(),
+            timings: BatchTimings::default(),
+            started_at: datetime!(2022-11-20 0:00 UTC),
+            finished_at: datetime!(2022-11-21 0:00 UTC),
+        }]
+    }
+
     pub fn create_test_api_keys() -> Vec<Key> {
         vec![
             Key {
@@ -398,6 +434,15 @@ pub(crate) mod test {
         }
         task_queue.flush().unwrap();
 
+        // ========== pushing the batch queue
+        let batches = create_test_batches();
+
+        let mut batch_queue = dump.create_batches_queue().unwrap();
+        for batch in &batches {
+            batch_queue.push_batch(batch).unwrap();
+        }
+        batch_queue.flush().unwrap();
+
         // ========== pushing the api keys
         let api_keys = create_test_api_keys();
 
@@ -455,6 +500,10 @@ pub(crate) mod test {
             }
         }
 
+        // ==== checking the batch queue
+        let batches = dump.batches().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(batches, create_test_batches());
+
         // ==== checking the keys
         for (key, expected) in dump.keys().unwrap().zip(create_test_api_keys()) {
             assert_eq!(key.unwrap(), expected);
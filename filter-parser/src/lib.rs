@@ -6,7 +6,7 @@
 //! or             = and ("OR" WS+ and)*
 //! and            = not ("AND" WS+ not)*
 //! not            = ("NOT" WS+ not) | primary
-//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | to
+//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | geoBoundingBox | geoPolygon | in | condition | exists | not_exists | to
 //! in             = value "IN" WS* "[" value_list "]"
 //! condition      = value ("=" | "!=" | ">" | ">=" | "<" | "<=") value
 //! exists         = value "EXISTS"
@@ -19,6 +19,7 @@
 //! word           = (alphanumeric | _ | - | .)+
 //! geoRadius      = "_geoRadius(" WS* float WS* "," WS* float WS* "," float WS* ")"
 //! geoBoundingBox = "_geoBoundingBox([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
+//! geoPolygon     = "_geoPolygon([" WS* "[" WS* float WS* "," WS* float WS* "]" (WS* "," WS* "[" WS* float WS* "," WS* float WS* "]")+ WS* "])"
 //! ```
 //!
 //! Other BNF grammar used to handle some specific errors:
@@ -142,6 +143,7 @@ pub enum FilterCondition<'a> {
     And(Vec<Self>),
     GeoLowerThan { point: [Token<'a>; 2], radius: Token<'a> },
     GeoBoundingBox { top_left_point: [Token<'a>; 2], bottom_right_point: [Token<'a>; 2] },
+    GeoPolygon { points: Vec<[Token<'a>; 2]> },
 }
 
 impl<'a> FilterCondition<'a> {
@@ -368,6 +370,36 @@ fn parse_geo_bounding_box(input: Span) -> IResult<FilterCondition> {
     Ok((input, res))
 }
 
+/// geoPolygon      = WS* "_geoPolygon([[float WS* "," WS* float WS* "], [float WS* "," WS* float WS* "], ...])"
+/// If we parse `_geoPolygon` we MUST parse the rest of the expression.
+fn parse_geo_polygon(input: Span) -> IResult<FilterCondition> {
+    // we want to allow space BEFORE the _geoPolygon but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact("_geoPolygon"))),
+        // if we were able to parse `_geoPolygon` and can't parse the rest of the input we return a failure
+        cut(delimited(
+            tuple((char('('), multispace0, char('['))),
+            separated_list1(
+                tag(","),
+                ws(delimited(char('['), separated_list1(tag(","), ws(recognize_float)), char(']'))),
+            ),
+            tuple((char(']'), multispace0, char(')'))),
+        )),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::GeoPolygon)));
+
+    let (remaining, args) = parsed?;
+
+    if args.len() < 3 || args.iter().any(|point| point.len() != 2) {
+        return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoPolygon)));
+    }
+
+    let res = FilterCondition::GeoPolygon {
+        points: args.into_iter().map(|point| [point[0].into(), point[1].into()]).collect(),
+    };
+    Ok((remaining, res))
+}
+
 /// geoPoint      = WS* "_geoPoint(float WS* "," WS* float WS* "," WS* float)
 fn parse_geo_point(input: Span) -> IResult<FilterCondition> {
     // we want to forbid space BEFORE the _geoPoint but not after
@@ -395,7 +427,7 @@ fn parse_error_reserved_keyword(input: Span) -> IResult<FilterCondition> {
     }
 }
 
-/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | condition | exists | not_exists | to
+/// primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | geoBoundingBox | geoPolygon | condition | exists | not_exists | to
 fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
     if depth > MAX_FILTER_DEPTH {
         return Err(nom::Err::Error(Error::new_from_kind(input, ErrorKind::DepthLimitReached)));
@@ -411,6 +443,7 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
         ),
         parse_geo_radius,
         parse_geo_bounding_box,
+        parse_geo_polygon,
         parse_in,
         parse_not_in,
         parse_condition,
@@ -519,6 +552,11 @@ pub mod tests {
         insta::assert_display_snapshot!(p("NOT _geoBoundingBox([12, 13], [14, 15])"), @"NOT (_geoBoundingBox([{12}, {13}], [{14}, {15}]))");
         insta::assert_display_snapshot!(p("_geoBoundingBox([12,13],[14,15])"), @"_geoBoundingBox([{12}, {13}], [{14}, {15}])");
 
+        // Test geo polygon
+        insta::assert_display_snapshot!(p("_geoPolygon([[12, 13], [14, 15], [16, 17]])"), @"_geoPolygon([[{12}, {13}], [{14}, {15}], [{16}, {17}], ])");
+        insta::assert_display_snapshot!(p("NOT _geoPolygon([[12, 13], [14, 15], [16, 17]])"), @"NOT (_geoPolygon([[{12}, {13}], [{14}, {15}], [{16}, {17}], ]))");
+        insta::assert_display_snapshot!(p("_geoPolygon([[12,13],[14,15],[16,17],[18,19]])"), @"_geoPolygon([[{12}, {13}], [{14}, {15}], [{16}, {17}], [{18}, {19}], ])");
+
         // Test OR + AND
         insta::assert_display_snapshot!(p("channel = ponce AND 'dog race' != 'bernese mountain'"), @"AND[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
         insta::assert_display_snapshot!(p("channel = ponce OR 'dog race' != 'bernese mountain'"), @"OR[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
@@ -576,7 +614,7 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("'OR'"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, or `_geoBoundingBox` at `\'OR\'`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `\'OR\'`.
         1:5 'OR'
         "###);
 
@@ -586,12 +624,12 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("channel Ponce"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, or `_geoBoundingBox` at `channel Ponce`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `channel Ponce`.
         1:14 channel Ponce
         "###);
 
         insta::assert_display_snapshot!(p("channel = Ponce OR"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, or `_geoBoundingBox` but instead got nothing.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` but instead got nothing.
         19:19 channel = Ponce OR
         "###);
 
@@ -620,13 +658,28 @@ pub mod tests {
         1:26 _geoBoundingBox(1.0, 1.0)
         "###);
 
+        insta::assert_display_snapshot!(p("_geoPolygon"), @r###"
+        The `_geoPolygon` filter expects a list of at least three pairs of arguments: `_geoPolygon([[latitude, longitude], [latitude, longitude], [latitude, longitude], ...])`.
+        1:12 _geoPolygon
+        "###);
+
+        insta::assert_display_snapshot!(p("_geoPolygon([[1.0, 1.0], [2.0, 2.0]])"), @r###"
+        The `_geoPolygon` filter expects a list of at least three pairs of arguments: `_geoPolygon([[latitude, longitude], [latitude, longitude], [latitude, longitude], ...])`.
+        1:38 _geoPolygon([[1.0, 1.0], [2.0, 2.0]])
+        "###);
+
+        insta::assert_display_snapshot!(p("_geoPolygon([1.0, 1.0], [2.0, 2.0], [3.0, 3.0])"), @r###"
+        The `_geoPolygon` filter expects a list of at least three pairs of arguments: `_geoPolygon([[latitude, longitude], [latitude, longitude], [latitude, longitude], ...])`.
+        1:48 _geoPolygon([1.0, 1.0], [2.0, 2.0], [3.0, 3.0])
+        "###);
+
         insta::assert_display_snapshot!(p("_geoPoint(12, 13, 14)"), @r###"
-        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance), or _geoBoundingBox([latitude, longitude], [latitude, longitude]) built-in rules to filter on `_geo` coordinates.
+        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])`, or `_geoPolygon([[latitude, longitude], ...])` built-in rules to filter on `_geo` coordinates.
         1:22 _geoPoint(12, 13, 14)
         "###);
 
         insta::assert_display_snapshot!(p("position <= _geoPoint(12, 13, 14)"), @r###"
-        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance), or _geoBoundingBox([latitude, longitude], [latitude, longitude]) built-in rules to filter on `_geo` coordinates.
+        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])`, or `_geoPolygon([[latitude, longitude], ...])` built-in rules to filter on `_geo` coordinates.
         13:34 position <= _geoPoint(12, 13, 14)
         "###);
 
@@ -635,6 +688,11 @@ pub mod tests {
         13:35 position <= _geoRadius(12, 13, 14)
         "###);
 
+        insta::assert_display_snapshot!(p("position <= _geoPolygon([[12, 13], [14, 15], [16, 17]])"), @r###"
+        The `_geoPolygon` filter is an operation and can't be used as a value.
+        13:56 position <= _geoPolygon([[12, 13], [14, 15], [16, 17]])
+        "###);
+
         insta::assert_display_snapshot!(p("channel = 'ponce"), @r###"
         Expression `\'ponce` is missing the following closing delimiter: `'`.
         11:17 channel = 'ponce
@@ -656,12 +714,12 @@ pub mod tests {
         "###);
 
         insta::assert_display_snapshot!(p("colour NOT EXIST"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, or `_geoBoundingBox` at `colour NOT EXIST`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `colour NOT EXIST`.
         1:17 colour NOT EXIST
         "###);
 
         insta::assert_display_snapshot!(p("subscribers 100 TO1000"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, or `_geoBoundingBox` at `subscribers 100 TO1000`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `subscribers 100 TO1000`.
         1:23 subscribers 100 TO1000
         "###);
 
@@ -790,6 +848,13 @@ impl<'a> std::fmt::Display for FilterCondition<'a> {
                     bottom_right_point[1]
                 )
             }
+            FilterCondition::GeoPolygon { points } => {
+                write!(f, "_geoPolygon([")?;
+                for [lat, lng] in points {
+                    write!(f, "[{lat}, {lng}], ")?;
+                }
+                write!(f, "])")
+            }
         }
     }
 }
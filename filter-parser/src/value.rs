@@ -7,8 +7,8 @@ use nom::{InputIter, InputLength, InputTake, Slice};
 
 use crate::error::{ExpectedValueKind, NomErrorExt};
 use crate::{
-    parse_geo_bounding_box, parse_geo_point, parse_geo_radius, Error, ErrorKind, IResult, Span,
-    Token,
+    parse_geo_bounding_box, parse_geo_point, parse_geo_polygon, parse_geo_radius, Error, ErrorKind,
+    IResult, Span, Token,
 };
 
 /// This function goes through all characters in the [Span] if it finds any escaped character (`\`).
@@ -123,6 +123,24 @@ pub fn parse_value(input: Span) -> IResult<Token> {
         _ => (),
     }
 
+    match parse_geo_polygon(input) {
+        Ok(_) => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoPolygon,
+            )))
+        }
+        // if we encountered a failure it means the user badly wrote a _geoPolygon filter.
+        // But instead of showing them how to fix his syntax we are going to tell them they should not use this filter as a value.
+        Err(e) if e.is_failure() => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoPolygon,
+            )))
+        }
+        _ => (),
+    }
+
     // this parser is only used when an error is encountered and it parse the
     // largest string possible that do not contain any “language” syntax.
     // If we try to parse `name = 🦀 AND language = rust` we want to return an
@@ -178,7 +196,18 @@ fn is_syntax_component(c: char) -> bool {
 }
 
 fn is_keyword(s: &str) -> bool {
-    matches!(s, "AND" | "OR" | "IN" | "NOT" | "TO" | "EXISTS" | "_geoRadius" | "_geoBoundingBox")
+    matches!(
+        s,
+        "AND"
+            | "OR"
+            | "IN"
+            | "NOT"
+            | "TO"
+            | "EXISTS"
+            | "_geoRadius"
+            | "_geoBoundingBox"
+            | "_geoPolygon"
+    )
 }
 
 #[cfg(test)]
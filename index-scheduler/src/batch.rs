@@ -23,15 +23,16 @@ use std::fs::{self, File};
 use std::io::BufWriter;
 
 use dump::IndexMetadata;
-use log::{debug, error, info};
+use log::{error, info};
+use meilisearch_types::error::Code;
 use meilisearch_types::heed::{RoTxn, RwTxn};
 use meilisearch_types::milli::documents::{obkv_to_object, DocumentsBatchReader};
 use meilisearch_types::milli::heed::CompactionOption;
 use meilisearch_types::milli::update::{
-    DocumentAdditionResult, DocumentDeletionResult, IndexDocumentsConfig, IndexDocumentsMethod,
-    Settings as MilliSettings,
+    DeleteDocuments, DocumentAdditionResult, DocumentDeletionResult, IndexDocumentsConfig,
+    IndexDocumentsMethod, Settings as MilliSettings,
 };
-use meilisearch_types::milli::{self, BEU32};
+use meilisearch_types::milli::{self, Filter, BEU32};
 use meilisearch_types::settings::{apply_settings_to_builder, Settings, Unchecked};
 use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status, Task};
 use meilisearch_types::{compression, Index, VERSION_FILE_NAME};
@@ -103,6 +104,10 @@ pub(crate) enum IndexOperation {
         documents: Vec<Vec<String>>,
         tasks: Vec<Task>,
     },
+    DocumentDeletionByFilter {
+        index_uid: String,
+        task: Task,
+    },
     DocumentClear {
         index_uid: String,
         tasks: Vec<Task>,
@@ -137,6 +142,21 @@ pub(crate) enum IndexOperation {
 }
 
 impl Batch {
+    /// Return the name of the index the batch writes into, if any.
+    pub fn index_uid(&self) -> Option<&str> {
+        match self {
+            Batch::IndexOperation { op, .. } => Some(op.index_uid()),
+            Batch::IndexCreation { index_uid, .. }
+            | Batch::IndexUpdate { index_uid, .. }
+            | Batch::IndexDeletion { index_uid, .. } => Some(index_uid),
+            Batch::TaskCancelation { .. }
+            | Batch::TaskDeletion(_)
+            | Batch::SnapshotCreation(_)
+            | Batch::Dump(_)
+            | Batch::IndexSwap { .. } => None,
+        }
+    }
+
     /// Return the task ids associated with this batch.
     pub fn ids(&self) -> Vec<TaskId> {
         match self {
@@ -149,6 +169,7 @@ impl Batch {
                 tasks.iter().map(|task| task.uid).collect()
             }
             Batch::IndexOperation { op, .. } => match op {
+                IndexOperation::DocumentDeletionByFilter { task, .. } => vec![task.uid],
                 IndexOperation::DocumentImport { tasks, .. }
                 | IndexOperation::DocumentDeletion { tasks, .. }
                 | IndexOperation::Settings { tasks, .. }
@@ -176,6 +197,7 @@ impl IndexOperation {
         match self {
             IndexOperation::DocumentImport { index_uid, .. }
             | IndexOperation::DocumentDeletion { index_uid, .. }
+            | IndexOperation::DocumentDeletionByFilter { index_uid, .. }
             | IndexOperation::DocumentClear { index_uid, .. }
             | IndexOperation::Settings { index_uid, .. }
             | IndexOperation::DocumentClearAndSetting { index_uid, .. }
@@ -262,6 +284,21 @@ impl IndexScheduler {
                     must_create_index,
                 }))
             }
+            BatchKind::DocumentDeletionByFilter { id } => {
+                let task = self.get_task(rtxn, id)?.ok_or(Error::CorruptedTaskQueue)?;
+                match &task.kind {
+                    KindWithContent::DocumentDeletionByFilter { index_uid, .. } => {
+                        Ok(Some(Batch::IndexOperation {
+                            op: IndexOperation::DocumentDeletionByFilter {
+                                index_uid: index_uid.clone(),
+                                task,
+                            },
+                            must_create_index: false,
+                        }))
+                    }
+                    _ => unreachable!(),
+                }
+            }
             BatchKind::Settings { settings_ids, .. } => {
                 let tasks = self.get_existing_tasks(rtxn, settings_ids)?;
 
@@ -428,7 +465,7 @@ impl IndexScheduler {
         if let Some(task_id) = to_cancel.max() {
             // We retrieve the tasks that were processing before this tasks cancelation started.
             // We must *not* reset the processing tasks before calling this method.
-            let ProcessingTasks { started_at, processing } =
+            let ProcessingTasks { started_at, processing, .. } =
                 &*self.processing_tasks.read().unwrap();
             return Ok(Some(Batch::TaskCancelation {
                 task: self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?,
@@ -523,6 +560,7 @@ impl IndexScheduler {
         {
             self.maybe_fail(crate::tests::FailureLocation::InsideProcessBatch)?;
             self.maybe_fail(crate::tests::FailureLocation::PanicInsideProcessBatch)?;
+            self.maybe_fail(crate::tests::FailureLocation::IndexUnavailableInsideProcessBatch)?;
             self.breakpoint(crate::Breakpoint::InsideProcessBatch);
         }
         match batch {
@@ -560,7 +598,7 @@ impl IndexScheduler {
                 // and if errors occurs when we are deleting files we must do our best to delete
                 // everything. We do not return the encountered errors when deleting the content
                 // files as it is not a breaking operation and we can safely continue our job.
-                match wtxn.commit() {
+                match self.commit_batch_txn(wtxn) {
                     Ok(()) => {
                         for content_uuid in canceled_tasks_content_uuids {
                             if let Err(error) = self.delete_update_file(content_uuid) {
@@ -599,7 +637,7 @@ impl IndexScheduler {
                     }
                     _ => unreachable!(),
                 }
-                wtxn.commit()?;
+                self.commit_batch_txn(wtxn)?;
                 Ok(vec![task])
             }
             Batch::SnapshotCreation(mut tasks) => {
@@ -645,9 +683,6 @@ impl IndexScheduler {
                 }
 
                 // 3. Snapshot every indexes
-                // TODO we are opening all of the indexes it can be too much we should unload all
-                //      of the indexes we are trying to open. It would be even better to only unload
-                //      the ones that were opened by us. Or maybe use a LRU in the index mapper.
                 for result in self.index_mapper.index_mapping.iter(&rtxn)? {
                     let (name, uuid) = result?;
                     let index = self.index_mapper.index(&rtxn, name)?;
@@ -757,31 +792,40 @@ impl IndexScheduler {
                 }
                 dump_tasks.flush()?;
 
-                // 3. Dump the indexes
-                for (uid, index) in self.index_mapper.indexes(&rtxn)? {
+                // 3. dump the batches, the batch of the dump itself isn't written yet
+                let mut dump_batches = dump.create_batches_queue()?;
+                for ret in self.batches.iter(&rtxn)? {
+                    let (_, batch) = ret?;
+                    dump_batches.push_batch(&batch)?;
+                }
+                dump_batches.flush()?;
+
+                // 4. Dump the indexes
+                self.index_mapper.try_for_each_index::<(), ()>(&rtxn, |uid, index| {
                     let rtxn = index.read_txn()?;
                     let metadata = IndexMetadata {
-                        uid: uid.clone(),
+                        uid: uid.to_owned(),
                         primary_key: index.primary_key(&rtxn)?.map(String::from),
                         created_at: index.created_at(&rtxn)?,
                         updated_at: index.updated_at(&rtxn)?,
                     };
-                    let mut index_dumper = dump.create_index(&uid, &metadata)?;
+                    let mut index_dumper = dump.create_index(uid, &metadata)?;
 
                     let fields_ids_map = index.fields_ids_map(&rtxn)?;
                     let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
 
-                    // 3.1. Dump the documents
+                    // 4.1. Dump the documents
                     for ret in index.all_documents(&rtxn)? {
                         let (_id, doc) = ret?;
                         let document = milli::obkv_to_json(&all_fields, &fields_ids_map, doc)?;
                         index_dumper.push_document(&document)?;
                     }
 
-                    // 3.2. Dump the settings
-                    let settings = meilisearch_types::settings::settings(&index, &rtxn)?;
+                    // 4.2. Dump the settings
+                    let settings = meilisearch_types::settings::settings(index, &rtxn)?;
                     index_dumper.settings(&settings)?;
-                }
+                    Ok(())
+                })?;
 
                 let dump_uid = started_at.format(format_description!(
                     "[year repr:full][month repr:numerical][day padding:zero]-[hour padding:zero][minute padding:zero][second padding:zero][subsecond digits:3]"
@@ -806,10 +850,12 @@ impl IndexScheduler {
                     let rtxn = self.env.read_txn()?;
                     self.index_mapper.index(&rtxn, index_uid)?
                 };
+                self.index_mapper
+                    .set_currently_updating_index(Some((index_uid.to_owned(), index.clone())));
 
                 let mut index_wtxn = index.write_txn()?;
                 let tasks = self.apply_index_operation(&mut index_wtxn, &index, op)?;
-                index_wtxn.commit()?;
+                self.commit_batch_txn(index_wtxn)?;
 
                 Ok(tasks)
             }
@@ -836,10 +882,10 @@ impl IndexScheduler {
                     builder.set_primary_key(primary_key);
                     let must_stop_processing = self.must_stop_processing.clone();
                     builder.execute(
-                        |indexing_step| debug!("update: {:?}", indexing_step),
+                        |indexing_step| self.record_indexing_progress(indexing_step),
                         || must_stop_processing.get(),
                     )?;
-                    index_wtxn.commit()?;
+                    self.commit_batch_txn(index_wtxn)?;
                 }
                 task.status = Status::Succeeded;
                 task.details = Some(Details::IndexInfo { primary_key });
@@ -907,7 +953,7 @@ impl IndexScheduler {
                 for swap in swaps {
                     self.apply_index_swap(&mut wtxn, task.uid, &swap.indexes.0, &swap.indexes.1)?;
                 }
-                wtxn.commit()?;
+                self.commit_batch_txn(wtxn)?;
                 task.status = Status::Succeeded;
                 Ok(vec![task])
             }
@@ -1018,7 +1064,7 @@ impl IndexScheduler {
                                 milli::update::Settings::new(index_wtxn, index, indexer_config);
                             builder.set_primary_key(primary_key);
                             builder.execute(
-                                |indexing_step| debug!("update: {:?}", indexing_step),
+                                |indexing_step| self.record_indexing_progress(indexing_step),
                                 || must_stop_processing.clone().get(),
                             )?;
                             primary_key_has_been_set = true;
@@ -1033,7 +1079,7 @@ impl IndexScheduler {
                     index,
                     indexer_config,
                     config,
-                    |indexing_step| debug!("update: {:?}", indexing_step),
+                    |indexing_step| self.record_indexing_progress(indexing_step),
                     || must_stop_processing.get(),
                 )?;
 
@@ -1066,7 +1112,7 @@ impl IndexScheduler {
                         milli::update::Settings::new(index_wtxn, index, indexer_config);
                     builder.reset_primary_key();
                     builder.execute(
-                        |indexing_step| debug!("update: {:?}", indexing_step),
+                        |indexing_step| self.record_indexing_progress(indexing_step),
                         || must_stop_processing.clone().get(),
                     )?;
                 }
@@ -1114,6 +1160,47 @@ impl IndexScheduler {
 
                 Ok(tasks)
             }
+            IndexOperation::DocumentDeletionByFilter { mut task, index_uid: _ } => {
+                let filter =
+                    if let KindWithContent::DocumentDeletionByFilter { filter_expr, .. } =
+                        &task.kind
+                    {
+                        filter_expr
+                    } else {
+                        unreachable!()
+                    };
+                let deleted_documents = delete_document_by_filter(index_wtxn, filter, index);
+                let original_filter = if let Some(Details::DocumentDeletionByFilter {
+                    original_filter,
+                    deleted_documents: _,
+                }) = task.details
+                {
+                    original_filter
+                } else {
+                    // In the case of a `documentDeleteByFilter` the details MUST be set
+                    unreachable!();
+                };
+
+                match deleted_documents {
+                    Ok(deleted_documents) => {
+                        task.status = Status::Succeeded;
+                        task.details = Some(Details::DocumentDeletionByFilter {
+                            original_filter,
+                            deleted_documents: Some(deleted_documents),
+                        });
+                    }
+                    Err(e) => {
+                        task.status = Status::Failed;
+                        task.details = Some(Details::DocumentDeletionByFilter {
+                            original_filter,
+                            deleted_documents: Some(0),
+                        });
+                        task.error = Some(e.into());
+                    }
+                }
+
+                Ok(vec![task])
+            }
             IndexOperation::Settings { index_uid: _, settings, mut tasks } => {
                 let indexer_config = self.index_mapper.indexer_config();
                 let mut builder = milli::update::Settings::new(index_wtxn, index, indexer_config);
@@ -1130,7 +1217,7 @@ impl IndexScheduler {
 
                 let must_stop_processing = self.must_stop_processing.clone();
                 builder.execute(
-                    |indexing_step| debug!("update: {:?}", indexing_step),
+                    |indexing_step| self.record_indexing_progress(indexing_step),
                     || must_stop_processing.get(),
                 )?;
 
@@ -1220,6 +1307,7 @@ impl IndexScheduler {
         let mut affected_statuses = HashSet::new();
         let mut affected_kinds = HashSet::new();
         let mut affected_canceled_by = RoaringBitmap::new();
+        let mut affected_batches = RoaringBitmap::new();
 
         for task_id in to_delete_tasks.iter() {
             let task = self.get_task(wtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
@@ -1241,6 +1329,9 @@ impl IndexScheduler {
             if let Some(canceled_by) = task.canceled_by {
                 affected_canceled_by.insert(canceled_by);
             }
+            if let Some(batch_uid) = task.batch_uid {
+                affected_batches.insert(batch_uid);
+            }
         }
 
         for index in affected_indexes {
@@ -1269,6 +1360,18 @@ impl IndexScheduler {
                 }
             }
         }
+        // A batch is deleted along with its last task. Its uid is not reused since the
+        // batch of the task deletion being processed comes after it.
+        for batch_uid in affected_batches {
+            if let Some(mut batch) = self.get_batch(wtxn, batch_uid)? {
+                batch.task_uids -= &to_delete_tasks;
+                if batch.task_uids.is_empty() {
+                    self.batches.delete(wtxn, &BEU32::new(batch_uid))?;
+                } else {
+                    self.batches.put(wtxn, &BEU32::new(batch_uid), &batch)?;
+                }
+            }
+        }
 
         Ok(to_delete_tasks.len())
     }
@@ -1312,3 +1415,30 @@ impl IndexScheduler {
         Ok(content_files_to_delete)
     }
 }
+
+fn delete_document_by_filter(
+    wtxn: &mut RwTxn,
+    filter: &serde_json::Value,
+    index: &Index,
+) -> Result<u64> {
+    let filter = Filter::from_json(filter).map_err(|err| match err {
+        milli::Error::UserError(milli::UserError::InvalidFilter(_))
+        | milli::Error::UserError(milli::UserError::InvalidFilterExpression(..)) => {
+            Error::from(err).with_custom_error_code(Code::InvalidDocumentFilter)
+        }
+        e => e.into(),
+    })?;
+    Ok(if let Some(filter) = filter {
+        let candidates = filter.evaluate(wtxn, index).map_err(|err| match err {
+            milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+                Error::from(err).with_custom_error_code(Code::InvalidDocumentFilter)
+            }
+            e => e.into(),
+        })?;
+        let mut delete_operation = DeleteDocuments::new(wtxn, index)?;
+        delete_operation.delete_documents(&candidates);
+        delete_operation.execute().map(|result| result.deleted_documents)?
+    } else {
+        0
+    })
+}
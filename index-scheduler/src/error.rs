@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+use meilisearch_types::batches::BatchId;
 use meilisearch_types::error::{Code, ErrorCode};
 use meilisearch_types::tasks::{Kind, Status};
 use meilisearch_types::{heed, milli};
@@ -50,6 +51,8 @@ pub enum Error {
     IndexNotFound(String),
     #[error("Index `{0}` already exists.")]
     IndexAlreadyExists(String),
+    #[error("Index `{0}` is temporarily unavailable, it is being reopened. Try again later.")]
+    IndexUnavailable(String),
     #[error(
         "Indexes must be declared only once during a swap. `{0}` was specified several times."
     )]
@@ -74,6 +77,8 @@ pub enum Error {
     InvalidTaskDate { field: DateField, date: String },
     #[error("Task uid `{task_uid}` is invalid. It should only contain numeric characters.")]
     InvalidTaskUids { task_uid: String },
+    #[error("Batch uid `{batch_uid}` is invalid. It should only contain numeric characters.")]
+    InvalidBatchUids { batch_uid: String },
     #[error(
         "Task status `{status}` is invalid. Available task statuses are {}.",
             enum_iterator::all::<Status>()
@@ -100,6 +105,8 @@ pub enum Error {
     InvalidIndexUid { index_uid: String },
     #[error("Task `{0}` not found.")]
     TaskNotFound(TaskId),
+    #[error("Batch `{0}` not found.")]
+    BatchNotFound(BatchId),
     #[error("Query parameters to filter the tasks to delete are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
     TaskDeletionWithEmptyQuery,
     #[error("Query parameters to filter the tasks to cancel are missing. Available query parameters are: `uids`, `indexUids`, `statuses`, `types`, `canceledBy`, `beforeEnqueuedAt`, `afterEnqueuedAt`, `beforeStartedAt`, `afterStartedAt`, `beforeFinishedAt`, `afterFinishedAt`.")]
@@ -123,6 +130,9 @@ pub enum Error {
     #[error(transparent)]
     Anyhow(#[from] anyhow::Error),
 
+    #[error("{1}")]
+    WithCustomErrorCode(Code, Box<Self>),
+
     // Irrecoverable errors:
     #[error(transparent)]
     CreateBatch(Box<Self>),
@@ -134,22 +144,52 @@ pub enum Error {
     HeedTransaction(heed::Error),
 }
 
+impl Error {
+    /// Wraps the error so that it is reported with the given `code`
+    /// instead of the one it would have returned on its own.
+    pub fn with_custom_error_code(self, code: Code) -> Self {
+        Self::WithCustomErrorCode(code, Box::new(self))
+    }
+
+    /// Whether the error was raised because an index reached its map size.
+    pub fn is_map_full(&self) -> bool {
+        match self {
+            Error::Milli(milli::Error::UserError(milli::UserError::MaxDatabaseSizeReached))
+            | Error::Heed(heed::Error::Mdb(heed::MdbError::MapFull)) => true,
+            Error::WithCustomErrorCode(_, error) => error.is_map_full(),
+            _ => false,
+        }
+    }
+
+    /// Whether the error was raised because the index could not be reopened in time.
+    pub fn is_index_unavailable(&self) -> bool {
+        match self {
+            Error::IndexUnavailable(_) => true,
+            Error::WithCustomErrorCode(_, error) => error.is_index_unavailable(),
+            _ => false,
+        }
+    }
+}
+
 impl ErrorCode for Error {
     fn error_code(&self) -> Code {
         match self {
             Error::IndexNotFound(_) => Code::IndexNotFound,
             Error::IndexAlreadyExists(_) => Code::IndexAlreadyExists,
+            Error::IndexUnavailable(_) => Code::IndexUnavailable,
             Error::SwapDuplicateIndexesFound(_) => Code::InvalidSwapDuplicateIndexFound,
             Error::SwapDuplicateIndexFound(_) => Code::InvalidSwapDuplicateIndexFound,
             Error::SwapIndexNotFound(_) => Code::IndexNotFound,
             Error::SwapIndexesNotFound(_) => Code::IndexNotFound,
             Error::InvalidTaskDate { field, .. } => (*field).into(),
             Error::InvalidTaskUids { .. } => Code::InvalidTaskUids,
+            Error::InvalidBatchUids { .. } => Code::InvalidBatchUids,
             Error::InvalidTaskStatuses { .. } => Code::InvalidTaskStatuses,
             Error::InvalidTaskTypes { .. } => Code::InvalidTaskTypes,
             Error::InvalidTaskCanceledBy { .. } => Code::InvalidTaskCanceledBy,
             Error::InvalidIndexUid { .. } => Code::InvalidIndexUid,
             Error::TaskNotFound(_) => Code::TaskNotFound,
+            Error::BatchNotFound(_) => Code::BatchNotFound,
             Error::TaskDeletionWithEmptyQuery => Code::MissingTaskFilters,
             Error::TaskCancelationWithEmptyQuery => Code::MissingTaskFilters,
             Error::Dump(e) => e.error_code(),
@@ -160,6 +200,7 @@ impl ErrorCode for Error {
             Error::FileStore(e) => e.error_code(),
             Error::IoError(e) => e.error_code(),
             Error::Persist(e) => e.error_code(),
+            Error::WithCustomErrorCode(code, _) => *code,
 
             // Irrecoverable errors
             Error::Anyhow(_) => Code::Internal,
@@ -1,23 +1,28 @@
-use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering::Relaxed;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 use std::{fs, thread};
 
 use log::error;
 use meilisearch_types::heed::types::Str;
-use meilisearch_types::heed::{Database, Env, EnvOpenOptions, RoTxn, RwTxn};
+use meilisearch_types::heed::{Database, Env, EnvClosingEvent, EnvOpenOptions, RoTxn, RwTxn};
 use meilisearch_types::milli::update::IndexerConfig;
 use meilisearch_types::milli::Index;
 use time::OffsetDateTime;
 use uuid::Uuid;
 
-use self::IndexStatus::{Available, BeingDeleted};
+use self::IndexStatus::{Available, BeingDeleted, Closing};
 use crate::uuid_codec::UuidCodec;
 use crate::{clamp_to_page_size, Error, Result};
 
 const INDEX_MAPPING: &str = "index-mapping";
 
+/// How long accessing an index waits for its environment to be closed before being reopened.
+const INDEX_CLOSING_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// Structure managing meilisearch's indexes.
 ///
 /// It is responsible for:
@@ -25,17 +30,25 @@ const INDEX_MAPPING: &str = "index-mapping";
 /// 2. Opening indexes and storing references to these opened indexes
 /// 3. Accessing indexes through their uuid
 /// 4. Mapping a user-defined name to each index uuid.
+/// 5. Closing the least recently used indexes and growing the map size of the full ones.
 #[derive(Clone)]
 pub struct IndexMapper {
     /// Keep track of the opened indexes. Used mainly by the index resolver.
-    index_map: Arc<RwLock<HashMap<Uuid, IndexStatus>>>,
+    index_map: Arc<RwLock<IndexMap>>,
+
+    /// The index currently being updated by the scheduler, always accessible
+    /// even if it was closed by the index map in the meantime.
+    currently_updating_index: Arc<RwLock<Option<(String, Index)>>>,
 
     /// Map an index name with an index uuid currently available on disk.
     pub(crate) index_mapping: Database<Str, UuidCodec>,
 
     /// Path to the folder where the LMDB environments of each index are.
     base_path: PathBuf,
+    /// The map size an index is opened with the first time.
     index_size: usize,
+    /// The amount the map size of an index grows when it is full.
+    index_growth_amount: usize,
     pub indexer_config: Arc<IndexerConfig>,
 }
 
@@ -45,22 +58,98 @@ pub struct IndexMapper {
 pub enum IndexStatus {
     /// Do not insert it back in the index map as it is currently being deleted.
     BeingDeleted,
+    /// The index is being closed and must be reopened once its environment is effectively closed.
+    Closing(Arc<ClosingIndex>),
     /// You can use the index without worrying about anything.
     Available(Index),
 }
 
+/// An index that was closed, either to free some space in the index map or to grow its map size.
+pub struct ClosingIndex {
+    closing_event: EnvClosingEvent,
+    /// The map size the index must be reopened with.
+    map_size: usize,
+}
+
+/// The indexes known by the index mapper, where at most `capacity` of them are opened.
+struct IndexMap {
+    statuses: HashMap<Uuid, (IndexStatus, AtomicU64)>,
+    /// A counter incremented on every access, used to find the least recently used index.
+    clock: AtomicU64,
+    /// The maximum number of indexes opened at the same time.
+    capacity: usize,
+}
+
+impl IndexMap {
+    fn new(capacity: usize) -> Self {
+        Self { statuses: HashMap::new(), clock: AtomicU64::new(0), capacity: capacity.max(1) }
+    }
+
+    /// Return the status of the index and mark it as the most recently used one.
+    fn get(&self, uuid: &Uuid) -> Option<IndexStatus> {
+        self.statuses.get(uuid).map(|(status, last_used)| {
+            last_used.store(self.clock.fetch_add(1, Relaxed), Relaxed);
+            status.clone()
+        })
+    }
+
+    fn insert(&mut self, uuid: Uuid, status: IndexStatus) -> Option<IndexStatus> {
+        let last_used = AtomicU64::new(self.clock.fetch_add(1, Relaxed));
+        self.statuses.insert(uuid, (status, last_used)).map(|(status, _)| status)
+    }
+
+    fn remove(&mut self, uuid: &Uuid) -> Option<IndexStatus> {
+        self.statuses.remove(uuid).map(|(status, _)| status)
+    }
+
+    /// Close the least recently used indexes until at most `capacity` indexes are opened.
+    ///
+    /// The environment of a closed index is effectively closed once all the copies of the index
+    /// are dropped, it is then reopened with the same map size the next time it is accessed.
+    fn close_least_recently_used(&mut self) {
+        loop {
+            let available =
+                self.statuses.iter().filter_map(|(uuid, (status, last_used))| match status {
+                    Available(index) => Some((last_used.load(Relaxed), uuid, index)),
+                    _ => None,
+                });
+            if available.clone().count() <= self.capacity {
+                break;
+            }
+
+            let (_, &uuid, index) = available.min_by_key(|(last_used, ..)| *last_used).unwrap();
+            let index = index.clone();
+            let map_size = match index.map_size() {
+                Ok(map_size) => map_size,
+                Err(e) => {
+                    error!("Could not retrieve the map size of the index {}: {}", uuid, e);
+                    // the index is reopened with the default map size, LMDB
+                    // never maps less than the size of the environment file.
+                    0
+                }
+            };
+            let closing_event = index.prepare_for_closing();
+            self.insert(uuid, Closing(Arc::new(ClosingIndex { closing_event, map_size })));
+        }
+    }
+}
+
 impl IndexMapper {
     pub fn new(
         env: &Env,
         base_path: PathBuf,
         index_size: usize,
+        index_growth_amount: usize,
+        index_count: usize,
         indexer_config: IndexerConfig,
     ) -> Result<Self> {
         Ok(Self {
-            index_map: Arc::default(),
+            index_map: Arc::new(RwLock::new(IndexMap::new(index_count))),
+            currently_updating_index: Arc::default(),
             index_mapping: env.create_database(Some(INDEX_MAPPING))?,
             base_path,
             index_size,
+            index_growth_amount,
             indexer_config: Arc::new(indexer_config),
         })
     }
@@ -71,9 +160,10 @@ impl IndexMapper {
         &self,
         path: &Path,
         date: Option<(OffsetDateTime, OffsetDateTime)>,
+        map_size: usize,
     ) -> Result<Index> {
         let mut options = EnvOpenOptions::new();
-        options.map_size(clamp_to_page_size(self.index_size));
+        options.map_size(clamp_to_page_size(map_size.max(self.index_size)));
         options.max_readers(1024);
 
         if let Some((created, updated)) = date {
@@ -102,15 +192,15 @@ impl IndexMapper {
                 let index_path = self.base_path.join(uuid.to_string());
                 fs::create_dir_all(&index_path)?;
 
-                let index = self.create_or_open_index(&index_path, date)?;
+                let index = self.create_or_open_index(&index_path, date, self.index_size)?;
 
                 wtxn.commit()?;
                 // TODO: it would be better to lazily create the index. But we need an Index::open function for milli.
-                if let Some(BeingDeleted) =
-                    self.index_map.write().unwrap().insert(uuid, Available(index.clone()))
-                {
+                let mut index_map = self.index_map.write().unwrap();
+                if let Some(BeingDeleted) = index_map.insert(uuid, Available(index.clone())) {
                     panic!("Uuid v4 conflict.");
                 }
+                index_map.close_least_recently_used();
 
                 Ok(index)
             }
@@ -134,6 +224,7 @@ impl IndexMapper {
         let mut lock = self.index_map.write().unwrap();
         let closing_event = match lock.insert(uuid, BeingDeleted) {
             Some(Available(index)) => Some(index.prepare_for_closing()),
+            Some(Closing(closing)) => Some(closing.closing_event.clone()),
             _ => None,
         };
 
@@ -173,56 +264,120 @@ impl IndexMapper {
 
     /// Return an index, may open it if it wasn't already opened.
     pub fn index(&self, rtxn: &RoTxn, name: &str) -> Result<Index> {
+        if let Some((current_name, current_index)) =
+            self.currently_updating_index.read().unwrap().as_ref()
+        {
+            if current_name == name {
+                return Ok(current_index.clone());
+            }
+        }
+
         let uuid = self
             .index_mapping
             .get(rtxn, name)?
             .ok_or_else(|| Error::IndexNotFound(name.to_string()))?;
 
-        // we clone here to drop the lock before entering the match
-        let index = self.index_map.read().unwrap().get(&uuid).cloned();
-        let index = match index {
-            Some(Available(index)) => index,
-            Some(BeingDeleted) => return Err(Error::IndexNotFound(name.to_string())),
-            // since we're lazy, it's possible that the index has not been opened yet.
-            None => {
-                let mut index_map = self.index_map.write().unwrap();
-                // between the read lock and the write lock it's not impossible
-                // that someone already opened the index (eg if two search happens
-                // at the same time), thus before opening it we check a second time
-                // if it's not already there.
-                // Since there is a good chance it's not already there we can use
-                // the entry method.
-                match index_map.entry(uuid) {
-                    Entry::Vacant(entry) => {
-                        let index_path = self.base_path.join(uuid.to_string());
-
-                        let index = self.create_or_open_index(&index_path, None)?;
-                        entry.insert(Available(index.clone()));
-                        index
+        loop {
+            // we clone here to drop the lock before entering the match
+            let index = self.index_map.read().unwrap().get(&uuid);
+            match index {
+                Some(Available(index)) => return Ok(index),
+                Some(BeingDeleted) => return Err(Error::IndexNotFound(name.to_string())),
+                // The environment can only be reopened once it is effectively closed,
+                // which happens when all the copies of the index are dropped.
+                Some(Closing(closing)) => {
+                    if !closing.closing_event.wait_timeout(INDEX_CLOSING_TIMEOUT) {
+                        return Err(Error::IndexUnavailable(name.to_string()));
+                    }
+                    let mut index_map = self.index_map.write().unwrap();
+                    // between the wait and the write lock someone may already have
+                    // reopened the index, in which case we start over.
+                    match index_map.get(&uuid) {
+                        Some(Closing(current)) if Arc::ptr_eq(&closing, &current) => {
+                            let index_path = self.base_path.join(uuid.to_string());
+                            let index =
+                                self.create_or_open_index(&index_path, None, closing.map_size)?;
+                            index_map.insert(uuid, Available(index.clone()));
+                            index_map.close_least_recently_used();
+                            return Ok(index);
+                        }
+                        _ => continue,
                     }
-                    Entry::Occupied(entry) => match entry.get() {
-                        Available(index) => index.clone(),
-                        BeingDeleted => return Err(Error::IndexNotFound(name.to_string())),
-                    },
                 }
-            }
-        };
+                // since we're lazy, it's possible that the index has not been opened yet.
+                None => {
+                    let mut index_map = self.index_map.write().unwrap();
+                    // between the read lock and the write lock it's not impossible
+                    // that someone already opened the index (eg if two search happens
+                    // at the same time), thus before opening it we check a second time
+                    // if it's not already there.
+                    if index_map.get(&uuid).is_some() {
+                        continue;
+                    }
 
-        Ok(index)
+                    let index_path = self.base_path.join(uuid.to_string());
+                    let index = self.create_or_open_index(&index_path, None, self.index_size)?;
+                    index_map.insert(uuid, Available(index.clone()));
+                    index_map.close_least_recently_used();
+                    return Ok(index);
+                }
+            }
+        }
     }
 
-    /// Return all indexes, may open them if they weren't already opened.
-    pub fn indexes(&self, rtxn: &RoTxn) -> Result<Vec<(String, Index)>> {
+    /// Call the function on every index, opening them one after the other.
+    ///
+    /// The indexes are not kept opened between two calls so that they can be closed
+    /// by the index map if there are too many of them.
+    pub fn try_for_each_index<U, V>(
+        &self,
+        rtxn: &RoTxn,
+        mut f: impl FnMut(&str, &Index) -> Result<U>,
+    ) -> Result<V>
+    where
+        V: FromIterator<U>,
+    {
         self.index_mapping
             .iter(rtxn)?
             .map(|ret| {
                 ret.map_err(Error::from).and_then(|(name, _)| {
-                    self.index(rtxn, name).map(|index| (name.to_string(), index))
+                    let index = self.index(rtxn, name)?;
+                    f(name, &index)
                 })
             })
             .collect()
     }
 
+    /// Close the index so that it is reopened with a bigger map size the next time it is accessed.
+    pub fn resize_index(&self, rtxn: &RoTxn, name: &str) -> Result<()> {
+        let uuid = self
+            .index_mapping
+            .get(rtxn, name)?
+            .ok_or_else(|| Error::IndexNotFound(name.to_string()))?;
+
+        let mut index_map = self.index_map.write().unwrap();
+        let closing = match index_map.get(&uuid) {
+            Some(Available(index)) => {
+                let map_size = index.map_size()? + self.index_growth_amount;
+                ClosingIndex { closing_event: index.prepare_for_closing(), map_size }
+            }
+            Some(Closing(closing)) => ClosingIndex {
+                closing_event: closing.closing_event.clone(),
+                map_size: closing.map_size.max(self.index_size) + self.index_growth_amount,
+            },
+            Some(BeingDeleted) | None => return Err(Error::IndexNotFound(name.to_string())),
+        };
+        log::info!("Resizing the index `{}` to {} bytes.", name, closing.map_size);
+        index_map.insert(uuid, Closing(Arc::new(closing)));
+
+        Ok(())
+    }
+
+    /// Make the index accessible even if it is closed by the index map while being updated.
+    pub fn set_currently_updating_index(&self, index: Option<(String, Index)>) {
+        *self.currently_updating_index.write().unwrap() = index;
+    }
+
     /// Swap two index names.
     pub fn swap(&self, wtxn: &mut RwTxn, lhs: &str, rhs: &str) -> Result<()> {
         let lhs_uuid = self
@@ -248,3 +403,75 @@ impl IndexMapper {
         &self.indexer_config
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use meilisearch_types::heed::EnvOpenOptions;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn index_mapper(dir: &TempDir, index_count: usize) -> (Env, IndexMapper) {
+        let tasks_path = dir.path().join("tasks");
+        fs::create_dir_all(&tasks_path).unwrap();
+        let env = EnvOpenOptions::new().max_dbs(1).map_size(1000 * 1000).open(tasks_path).unwrap();
+        let mapper = IndexMapper::new(
+            &env,
+            dir.path().join("indexes"),
+            1000 * 1000, // 1 MB, we don't use MiB on purpose.
+            1000 * 1000,
+            index_count,
+            IndexerConfig::default(),
+        )
+        .unwrap();
+        (env, mapper)
+    }
+
+    fn status(env: &Env, mapper: &IndexMapper, name: &str) -> Option<IndexStatus> {
+        let rtxn = env.read_txn().unwrap();
+        let uuid = mapper.index_mapping.get(&rtxn, name).unwrap().unwrap();
+        let index_map = mapper.index_map.read().unwrap();
+        index_map.statuses.get(&uuid).map(|(status, _)| status.clone())
+    }
+
+    #[test]
+    fn close_the_least_recently_used_indexes() {
+        let dir = TempDir::new().unwrap();
+        let (env, mapper) = index_mapper(&dir, 2);
+
+        for name in ["a", "b", "c"] {
+            mapper.create_index(env.write_txn().unwrap(), name, None).unwrap();
+        }
+        assert!(matches!(status(&env, &mapper, "a"), Some(Closing(_))));
+        assert!(matches!(status(&env, &mapper, "b"), Some(Available(_))));
+        assert!(matches!(status(&env, &mapper, "c"), Some(Available(_))));
+
+        // accessing a closed index reopens it and closes the least recently used one.
+        let rtxn = env.read_txn().unwrap();
+        mapper.index(&rtxn, "a").unwrap();
+        assert!(matches!(status(&env, &mapper, "a"), Some(Available(_))));
+        assert!(matches!(status(&env, &mapper, "b"), Some(Closing(_))));
+        assert!(matches!(status(&env, &mapper, "c"), Some(Available(_))));
+
+        let names: Vec<_> =
+            mapper.try_for_each_index(&rtxn, |name, _index| Ok(name.to_string())).unwrap();
+        assert_eq!(names, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn resize_an_index() {
+        let dir = TempDir::new().unwrap();
+        let (env, mapper) = index_mapper(&dir, 2);
+
+        let index = mapper.create_index(env.write_txn().unwrap(), "a", None).unwrap();
+        let map_size = index.map_size().unwrap();
+        drop(index);
+
+        let rtxn = env.read_txn().unwrap();
+        mapper.resize_index(&rtxn, "a").unwrap();
+        assert!(matches!(status(&env, &mapper, "a"), Some(Closing(_))));
+
+        let index = mapper.index(&rtxn, "a").unwrap();
+        assert!(index.map_size().unwrap() > map_size);
+    }
+}
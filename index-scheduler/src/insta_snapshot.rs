@@ -24,11 +24,14 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
         kind,
         index_tasks,
         canceled_by,
+        batches: _,
         enqueued_at,
         started_at,
         finished_at,
         index_mapper,
         wake_up: _,
+        webhook: _,
+        task_updates: _,
         dumps_path: _,
         snapshots_path: _,
         auth_path: _,
@@ -138,6 +141,7 @@ pub fn snapshot_task(task: &Task) -> String {
     let mut snap = String::new();
     let Task {
         uid,
+        batch_uid: _,
         enqueued_at: _,
         started_at: _,
         finished_at: _,
@@ -183,6 +187,9 @@ fn snapshot_details(d: &Details) -> String {
             provided_ids: received_document_ids,
             deleted_documents,
         } => format!("{{ received_document_ids: {received_document_ids}, deleted_documents: {deleted_documents:?} }}"),
+        Details::DocumentDeletionByFilter { original_filter, deleted_documents } => {
+            format!("{{ original_filter: {original_filter}, deleted_documents: {deleted_documents:?} }}")
+        }
         Details::ClearAll { deleted_documents } => {
             format!("{{ deleted_documents: {deleted_documents:?} }}")
         },
@@ -254,6 +261,11 @@ pub fn snapshot_canceled_by(
     snap
 }
 pub fn snapshot_index_mapper(rtxn: &RoTxn, mapper: &IndexMapper) -> String {
-    let names = mapper.indexes(rtxn).unwrap().into_iter().map(|(n, _)| n).collect::<Vec<_>>();
+    let names = mapper
+        .index_mapping
+        .iter(rtxn)
+        .unwrap()
+        .map(|ret| ret.unwrap().0.to_string())
+        .collect::<Vec<_>>();
     format!("{names:?}")
 }
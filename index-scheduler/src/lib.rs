@@ -26,40 +26,55 @@ mod index_mapper;
 mod insta_snapshot;
 mod utils;
 mod uuid_codec;
+mod webhook;
 
 pub type Result<T> = std::result::Result<T, Error>;
 pub type TaskId = u32;
 
 use std::ops::{Bound, RangeBounds};
 use std::path::PathBuf;
-use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::Relaxed;
+use std::sync::atomic::{AtomicBool, AtomicUsize};
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use dump::{KindDump, TaskDump, UpdateFile};
 pub use error::Error;
 use file_store::FileStore;
+use meilisearch_types::batches::{Batch, BatchId, BatchTimings};
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::heed::types::{OwnedType, SerdeBincode, SerdeJson, Str};
-use meilisearch_types::heed::{self, Database, Env, RoTxn};
+use meilisearch_types::heed::{self, Database, Env, RoTxn, RwTxn};
 use meilisearch_types::milli;
 use meilisearch_types::milli::documents::DocumentsBatchBuilder;
-use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::update::{IndexerConfig, UpdateIndexingStep};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, Index, RoaringBitmapCodec, BEU32};
-use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
+use meilisearch_types::task_view::TaskView;
+use meilisearch_types::tasks::{IndexingProgress, Kind, KindWithContent, Status, Task};
 use roaring::RoaringBitmap;
 use synchronoise::SignalEvent;
 use time::OffsetDateTime;
-use utils::{filter_out_references_to_newer_tasks, keep_tasks_within_datetimes, map_bound};
+use tokio::sync::broadcast;
+use utils::{
+    filter_out_references_to_newer_tasks, is_within_datetimes, keep_tasks_within_datetimes,
+    map_bound,
+};
 use uuid::Uuid;
 
 use crate::index_mapper::IndexMapper;
 use crate::utils::{check_index_swap_validity, clamp_to_page_size};
+use crate::webhook::Webhook;
 
 pub(crate) type BEI128 =
     meilisearch_types::heed::zerocopy::I128<meilisearch_types::heed::byteorder::BE>;
 
+/// The number of task updates a subscriber can lag behind before missing some of them.
+const TASK_UPDATES_CAPACITY: usize = 1024;
+
+/// The maximum number of times in a row a batch is processed again because its index
+/// was full or could not be reopened in time, the batch fails with the error afterward.
+const MAX_BATCH_RETRIES: usize = 20;
+
 /// Defines a subset of tasks to be retrieved from the [`IndexScheduler`].
 ///
 /// An empty/default query (where each field is set to `None`) matches all tasks.
@@ -141,23 +156,51 @@ struct ProcessingTasks {
     started_at: OffsetDateTime,
     /// The list of tasks ids that are currently running.
     processing: RoaringBitmap,
+    /// The progress of the indexation of the running tasks, if they index anything.
+    progress: Option<IndexingProgress>,
+    /// The uid of the batch the running tasks are part of.
+    batch_uid: BatchId,
+    /// Measures the time spent in each phase of the running batch.
+    timer: BatchTimer,
 }
 
 impl ProcessingTasks {
     /// Creates an empty `ProcessingAt` struct.
     fn new() -> ProcessingTasks {
-        ProcessingTasks { started_at: OffsetDateTime::now_utc(), processing: RoaringBitmap::new() }
+        ProcessingTasks {
+            started_at: OffsetDateTime::now_utc(),
+            processing: RoaringBitmap::new(),
+            progress: None,
+            batch_uid: 0,
+            timer: BatchTimer::default(),
+        }
     }
 
-    /// Stores the currently processing tasks, and the date time at which it started.
-    fn start_processing_at(&mut self, started_at: OffsetDateTime, processing: RoaringBitmap) {
+    /// Stores the currently processing tasks, the batch they are part of,
+    /// and the date time at which it started.
+    fn start_processing_at(
+        &mut self,
+        started_at: OffsetDateTime,
+        batch_uid: BatchId,
+        processing: RoaringBitmap,
+    ) {
         self.started_at = started_at;
         self.processing = processing;
+        self.progress = None;
+        self.batch_uid = batch_uid;
+        self.timer = BatchTimer::default();
     }
 
     /// Set the processing tasks to an empty list
     fn stop_processing(&mut self) {
         self.processing = RoaringBitmap::new();
+        self.progress = None;
+    }
+
+    /// End the current phase of the running batch and return the time spent in each phase.
+    fn finish_timings(&mut self) -> BatchTimings {
+        self.timer.enter(None);
+        self.timer.timings
     }
 
     /// Returns `true` if there, at least, is one task that is currently processing that we must stop.
@@ -166,6 +209,54 @@ impl ProcessingTasks {
     }
 }
 
+/// The phases of a batch whose duration is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BatchPhase {
+    Transform,
+    Extraction,
+    PrefixDatabases,
+    Commit,
+}
+
+impl From<UpdateIndexingStep> for BatchPhase {
+    fn from(step: UpdateIndexingStep) -> Self {
+        match step {
+            UpdateIndexingStep::RemapDocumentAddition { .. }
+            | UpdateIndexingStep::ComputeIdsAndMergeDocuments { .. } => BatchPhase::Transform,
+            step if step.is_computing_prefix_databases() => BatchPhase::PrefixDatabases,
+            UpdateIndexingStep::IndexDocuments { .. }
+            | UpdateIndexingStep::MergeDataIntoFinalDatabase { .. } => BatchPhase::Extraction,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct BatchTimer {
+    /// The phase the batch is in and the instant it entered it.
+    current: Option<(BatchPhase, Instant)>,
+    timings: BatchTimings,
+}
+
+impl BatchTimer {
+    /// End the current phase, adding its duration to the timings, and start the given one.
+    fn enter(&mut self, phase: Option<BatchPhase>) {
+        if self.current.map(|(current, _)| current) == phase {
+            return;
+        }
+
+        if let Some((current, entered_at)) = self.current.take() {
+            let timing = match current {
+                BatchPhase::Transform => &mut self.timings.transform,
+                BatchPhase::Extraction => &mut self.timings.extraction,
+                BatchPhase::PrefixDatabases => &mut self.timings.prefix_databases,
+                BatchPhase::Commit => &mut self.timings.commit,
+            };
+            *timing = Some(timing.unwrap_or_default() + entered_at.elapsed());
+        }
+        self.current = phase.map(|phase| (phase, Instant::now()));
+    }
+}
+
 #[derive(Default, Clone, Debug)]
 struct MustStopProcessing(Arc<AtomicBool>);
 
@@ -193,6 +284,7 @@ mod db_name {
     pub const ENQUEUED_AT: &str = "enqueued-at";
     pub const STARTED_AT: &str = "started-at";
     pub const FINISHED_AT: &str = "finished-at";
+    pub const BATCHES: &str = "batches";
 }
 
 #[cfg(test)]
@@ -206,6 +298,7 @@ pub enum Breakpoint {
     BeforeProcessing,
     AfterProcessing,
     AbortedIndexation,
+    IndexResized,
     ProcessBatchSucceeded,
     ProcessBatchFailed,
     InsideProcessBatch,
@@ -229,13 +322,21 @@ pub struct IndexSchedulerOptions {
     pub dumps_path: PathBuf,
     /// The maximum size, in bytes, of the task index.
     pub task_db_size: usize,
-    /// The maximum size, in bytes, of each meilisearch index.
+    /// The size, in bytes, with which a meilisearch index is opened the first time.
     pub index_size: usize,
+    /// The amount, in bytes, by which the map size of an index grows when it is full.
+    pub index_growth_amount: usize,
+    /// The maximum number of indexes opened at the same time.
+    pub index_count: usize,
     /// Configuration used during indexing for each meilisearch index.
     pub indexer_config: IndexerConfig,
     /// Set to `true` iff the index scheduler is allowed to automatically
     /// batch tasks together, to process multiple tasks at once.
     pub autobatching_enabled: bool,
+    /// The URL notified of the finished tasks after each batch.
+    pub webhook_url: Option<String>,
+    /// The value of the `Authorization` header sent to the webhook.
+    pub webhook_authorization_header: Option<String>,
 }
 
 /// Structure which holds meilisearch's indexes and schedules the tasks
@@ -267,6 +368,9 @@ pub struct IndexScheduler {
     /// Store the tasks that were canceled by a task uid
     pub(crate) canceled_by: Database<OwnedType<BEU32>, RoaringBitmapCodec>,
 
+    /// Store the processed batches.
+    pub(crate) batches: Database<OwnedType<BEU32>, SerdeJson<Batch>>,
+
     /// Store the task ids of tasks which were enqueued at a specific date
     pub(crate) enqueued_at: Database<OwnedType<BEI128>, CboRoaringBitmapCodec>,
 
@@ -282,9 +386,18 @@ pub struct IndexScheduler {
     /// Get a signal when a batch needs to be processed.
     pub(crate) wake_up: Arc<SignalEvent>,
 
+    /// The number of times in a row the current batch was processed again.
+    pub(crate) batch_retries: Arc<AtomicUsize>,
+
     /// Whether auto-batching is enabled or not.
     pub(crate) autobatching_enabled: bool,
 
+    /// The webhook notified of the finished tasks, if any.
+    pub(crate) webhook: Option<Webhook>,
+
+    /// Broadcast the tasks every time their status changes.
+    pub(crate) task_updates: broadcast::Sender<Task>,
+
     /// The path used to create the dumps.
     pub(crate) dumps_path: PathBuf,
 
@@ -328,12 +441,16 @@ impl IndexScheduler {
             kind: self.kind,
             index_tasks: self.index_tasks,
             canceled_by: self.canceled_by,
+            batches: self.batches,
             enqueued_at: self.enqueued_at,
             started_at: self.started_at,
             finished_at: self.finished_at,
             index_mapper: self.index_mapper.clone(),
             wake_up: self.wake_up.clone(),
+            batch_retries: self.batch_retries.clone(),
             autobatching_enabled: self.autobatching_enabled,
+            webhook: self.webhook.clone(),
+            task_updates: self.task_updates.clone(),
             snapshots_path: self.snapshots_path.clone(),
             dumps_path: self.dumps_path.clone(),
             auth_path: self.auth_path.clone(),
@@ -376,6 +493,7 @@ impl IndexScheduler {
             kind: env.create_database(Some(db_name::KIND))?,
             index_tasks: env.create_database(Some(db_name::INDEX_TASKS))?,
             canceled_by: env.create_database(Some(db_name::CANCELED_BY))?,
+            batches: env.create_database(Some(db_name::BATCHES))?,
             enqueued_at: env.create_database(Some(db_name::ENQUEUED_AT))?,
             started_at: env.create_database(Some(db_name::STARTED_AT))?,
             finished_at: env.create_database(Some(db_name::FINISHED_AT))?,
@@ -383,12 +501,19 @@ impl IndexScheduler {
                 &env,
                 options.indexes_path,
                 options.index_size,
+                options.index_growth_amount,
+                options.index_count,
                 options.indexer_config,
             )?,
             env,
             // we want to start the loop right away in case meilisearch was ctrl+Ced while processing things
             wake_up: Arc::new(SignalEvent::auto(true)),
+            batch_retries: Arc::default(),
             autobatching_enabled: options.autobatching_enabled,
+            webhook: options
+                .webhook_url
+                .map(|url| Webhook::new(url, options.webhook_authorization_header)),
+            task_updates: broadcast::channel(TASK_UPDATES_CAPACITY).0,
             dumps_path: options.dumps_path,
             snapshots_path: options.snapshots_path,
             auth_path: options.auth_path,
@@ -465,10 +590,13 @@ impl IndexScheduler {
         self.index_mapper.index(&rtxn, name)
     }
 
-    /// Return and open all the indexes.
-    pub fn indexes(&self) -> Result<Vec<(String, Index)>> {
+    /// Call the function on every index, opening them one after the other.
+    pub fn try_for_each_index<U, V>(&self, f: impl FnMut(&str, &Index) -> Result<U>) -> Result<V>
+    where
+        V: FromIterator<U>,
+    {
         let rtxn = self.env.read_txn()?;
-        self.index_mapper.indexes(&rtxn)
+        self.index_mapper.try_for_each_index(&rtxn, f)
     }
 
     /// Return the task ids matched by the given query from the index scheduler's point of view.
@@ -657,6 +785,64 @@ impl IndexScheduler {
         Ok(tasks)
     }
 
+    /// Return the batches containing at least one task matching the query from the user's point of view.
+    ///
+    /// The `uids`, `from` and `limit` parameters of the query apply to the batches themselves,
+    /// and the `startedAt` and `finishedAt` dates are the ones of the batches.
+    pub fn get_batches_from_authorized_indexes(
+        &self,
+        query: Query,
+        authorized_indexes: Option<Vec<String>>,
+    ) -> Result<Vec<Batch>> {
+        let rtxn = self.env.read_txn()?;
+
+        let tasks_query = Query {
+            statuses: query.statuses.clone(),
+            types: query.types.clone(),
+            index_uids: query.index_uids.clone(),
+            ..Query::default()
+        };
+        let tasks =
+            self.get_task_ids_from_authorized_indexes(&rtxn, &tasks_query, &authorized_indexes)?;
+
+        // Only the batches between the smallest and the biggest requested uids can match.
+        let uids = query.uids.as_deref().unwrap_or_default();
+        let start = uids.iter().min().map_or(Bound::Unbounded, |uid| Bound::Included(*uid));
+        let end = match (query.from, uids.iter().max()) {
+            (Some(from), Some(uid)) => Bound::Included(from.min(*uid)),
+            (Some(bound), None) | (None, Some(&bound)) => Bound::Included(bound),
+            (None, None) => Bound::Unbounded,
+        };
+        let range = (map_bound(start, BEU32::new), map_bound(end, BEU32::new));
+        let limit = query.limit.unwrap_or(u32::MAX) as usize;
+        let mut batches = Vec::new();
+        for result in self.batches.rev_range(&rtxn, &range)? {
+            if batches.len() >= limit {
+                break;
+            }
+
+            let (_, batch) = result?;
+            if query.uids.as_ref().map_or(false, |uids| !uids.contains(&batch.uid))
+                || batch.task_uids.is_disjoint(&tasks)
+                || !is_within_datetimes(
+                    batch.started_at,
+                    query.after_started_at,
+                    query.before_started_at,
+                )
+                || !is_within_datetimes(
+                    batch.finished_at,
+                    query.after_finished_at,
+                    query.before_finished_at,
+                )
+            {
+                continue;
+            }
+            batches.push(batch);
+        }
+
+        Ok(batches)
+    }
+
     /// Return the tasks matching the query from the user's point of view.
     ///
     /// There are two differences between an internal query and a query executed by
@@ -680,7 +866,7 @@ impl IndexScheduler {
             tasks.into_iter().rev().take(query.limit.unwrap_or(u32::MAX) as usize),
         )?;
 
-        let ProcessingTasks { started_at, processing, .. } =
+        let ProcessingTasks { started_at, processing, batch_uid, .. } =
             self.processing_tasks.read().map_err(|_| Error::CorruptedTaskQueue)?.clone();
 
         let ret = tasks.into_iter();
@@ -689,15 +875,30 @@ impl IndexScheduler {
         } else {
             Ok(ret
                 .map(|task| match processing.contains(task.uid) {
-                    true => {
-                        Task { status: Status::Processing, started_at: Some(started_at), ..task }
-                    }
+                    true => Task {
+                        status: Status::Processing,
+                        started_at: Some(started_at),
+                        batch_uid: Some(batch_uid),
+                        ..task
+                    },
                     false => task,
                 })
                 .collect())
         }
     }
 
+    /// Return the progress of the indexation of the processing tasks, if any.
+    pub fn indexing_progress(&self) -> Result<Option<IndexingProgress>> {
+        Ok(self.processing_tasks.read().map_err(|_| Error::CorruptedTaskQueue)?.progress)
+    }
+
+    /// Return the progress of the indexation of the given task, if it is processing.
+    pub fn task_indexing_progress(&self, task_uid: TaskId) -> Result<Option<IndexingProgress>> {
+        let processing_tasks =
+            self.processing_tasks.read().map_err(|_| Error::CorruptedTaskQueue)?;
+        Ok(processing_tasks.progress.filter(|_| processing_tasks.processing.contains(task_uid)))
+    }
+
     /// Register a new task in the scheduler.
     ///
     /// If it fails and data was associated with the task, it tries to delete the associated data.
@@ -706,6 +907,7 @@ impl IndexScheduler {
 
         let mut task = Task {
             uid: self.next_task_id(&wtxn)?,
+            batch_uid: None,
             enqueued_at: OffsetDateTime::now_utc(),
             started_at: None,
             finished_at: None,
@@ -758,12 +960,21 @@ impl IndexScheduler {
             }
         }
 
+        let _ = self.task_updates.send(task.clone());
+
         // notify the scheduler loop to execute a new tick
         self.wake_up.signal();
 
         Ok(task)
     }
 
+    /// Subscribe to the updates of the tasks.
+    ///
+    /// The receiver gets a task every time it is enqueued, starts processing, or finishes.
+    pub fn subscribe_to_task_updates(&self) -> broadcast::Receiver<Task> {
+        self.task_updates.subscribe()
+    }
+
     /// Register a new task comming from a dump in the scheduler.
     /// By takinig a mutable ref we're pretty sure no one will ever import a dump while actix is running.
     pub fn register_dumped_task(
@@ -794,6 +1005,7 @@ impl IndexScheduler {
 
         let task = Task {
             uid: task.uid,
+            batch_uid: task.batch_uid,
             enqueued_at: task.enqueued_at,
             started_at: task.started_at,
             finished_at: task.finished_at,
@@ -819,6 +1031,12 @@ impl IndexScheduler {
                     documents_ids,
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                 },
+                KindDump::DocumentDeletionByFilter { filter } => {
+                    KindWithContent::DocumentDeletionByFilter {
+                        filter_expr: filter,
+                        index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                    }
+                }
                 KindDump::DocumentClear => KindWithContent::DocumentClear {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                 },
@@ -877,6 +1095,16 @@ impl IndexScheduler {
         Ok(task)
     }
 
+    /// Register a batch comming from a dump in the scheduler.
+    /// The next batches are given the uids that follow the last dumped batch.
+    pub fn register_dumped_batch(&mut self, batch: Batch) -> Result<()> {
+        let mut wtxn = self.env.write_txn()?;
+        self.batches.put(&mut wtxn, &BEU32::new(batch.uid), &batch)?;
+        wtxn.commit()?;
+
+        Ok(())
+    }
+
     /// Create a new index without any associated task.
     pub fn create_raw_index(
         &self,
@@ -939,6 +1167,7 @@ impl IndexScheduler {
                 Some(batch) => batch,
                 None => return Ok(0),
             };
+        let batch_uid = self.next_batch_id(&rtxn)?;
         drop(rtxn);
 
         // 1. store the starting date with the bitmap of processing tasks.
@@ -946,11 +1175,28 @@ impl IndexScheduler {
         ids.sort_unstable();
         let processed_tasks = ids.len();
         let processing_tasks = RoaringBitmap::from_sorted_iter(ids.iter().copied()).unwrap();
-        let started_at = OffsetDateTime::now_utc();
+        let index_uid = batch.index_uid().map(ToOwned::to_owned);
 
         // We reset the must_stop flag to be sure that we don't stop processing tasks
         self.must_stop_processing.reset();
-        self.processing_tasks.write().unwrap().start_processing_at(started_at, processing_tasks);
+        let (started_at, newly_processing_tasks) = {
+            let mut currently_processing = self.processing_tasks.write().unwrap();
+            // The tasks of an aborted or retried batch were already processing,
+            // the batch keeps the date at which it first started.
+            let started_at = if currently_processing.processing.is_disjoint(&processing_tasks) {
+                OffsetDateTime::now_utc()
+            } else {
+                currently_processing.started_at
+            };
+            let newly_processing_tasks = &processing_tasks - &currently_processing.processing;
+            currently_processing.start_processing_at(started_at, batch_uid, processing_tasks);
+            (started_at, newly_processing_tasks)
+        };
+        if let Err(e) =
+            self.broadcast_processing_tasks(newly_processing_tasks, started_at, batch_uid)
+        {
+            log::error!("Could not broadcast the processing tasks: {}", e);
+        }
 
         #[cfg(test)]
         self.breakpoint(Breakpoint::BatchCreated);
@@ -964,6 +1210,38 @@ impl IndexScheduler {
                 .unwrap();
             handle.join().unwrap_or(Err(Error::ProcessBatchPanicked))
         };
+        self.index_mapper.set_currently_updating_index(None);
+
+        // If the index reached its map size we grow it and process the same batch again,
+        // the same goes if the index could not be reopened in time.
+        if let (Err(err), Some(index_uid)) = (&res, &index_uid) {
+            let retryable = err.is_map_full() || err.is_index_unavailable();
+            if retryable && self.batch_retries.fetch_add(1, Relaxed) >= MAX_BATCH_RETRIES {
+                log::error!(
+                    "Giving up on the batch of the index `{}` after {} retries.",
+                    index_uid,
+                    MAX_BATCH_RETRIES
+                );
+            } else if err.is_map_full() {
+                let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
+                match self.index_mapper.resize_index(&rtxn, index_uid) {
+                    Ok(()) => {
+                        #[cfg(test)]
+                        self.breakpoint(Breakpoint::IndexResized);
+
+                        // Like for an aborted indexation, the tasks are kept processing
+                        // and will be part of the batch created by the next tick.
+                        self.wake_up.signal();
+                        return Ok(0);
+                    }
+                    Err(e) => log::error!("Could not resize the index `{}`: {}", index_uid, e),
+                }
+            } else if err.is_index_unavailable() {
+                self.wake_up.signal();
+                return Ok(0);
+            }
+        }
+        self.batch_retries.store(0, Relaxed);
 
         #[cfg(test)]
         self.maybe_fail(tests::FailureLocation::AcquiringWtxn)?;
@@ -971,6 +1249,7 @@ impl IndexScheduler {
         let mut wtxn = self.env.write_txn().map_err(Error::HeedTransaction)?;
 
         let finished_at = OffsetDateTime::now_utc();
+        let mut batch_tasks = Vec::new();
         match res {
             Ok(tasks) => {
                 #[cfg(test)]
@@ -980,6 +1259,7 @@ impl IndexScheduler {
                 for (i, mut task) in tasks.into_iter().enumerate() {
                     task.started_at = Some(started_at);
                     task.finished_at = Some(finished_at);
+                    task.batch_uid = Some(batch_uid);
 
                     #[cfg(test)]
                     self.maybe_fail(
@@ -993,6 +1273,7 @@ impl IndexScheduler {
                     if let Err(e) = self.delete_persisted_task_data(&task) {
                         log::error!("Failure to delete the content files associated with task {}. Error: {e}", task.uid);
                     }
+                    batch_tasks.push(task);
                 }
                 log::info!("A batch of tasks was successfully completed.");
             }
@@ -1016,7 +1297,7 @@ impl IndexScheduler {
                 #[cfg(test)]
                 self.breakpoint(Breakpoint::ProcessBatchFailed);
                 let error: ResponseError = err.into();
-                for id in ids {
+                for &id in &ids {
                     let mut task = self
                         .get_task(&wtxn, id)
                         .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e)))?
@@ -1026,6 +1307,7 @@ impl IndexScheduler {
                     task.status = Status::Failed;
                     task.error = Some(error.clone());
                     task.details = task.details.map(|d| d.to_failed());
+                    task.batch_uid = Some(batch_uid);
 
                     #[cfg(test)]
                     self.maybe_fail(tests::FailureLocation::UpdatingTaskAfterProcessBatchFailure)?;
@@ -1035,23 +1317,118 @@ impl IndexScheduler {
                     }
                     self.update_task(&mut wtxn, &task)
                         .map_err(|e| Error::TaskDatabaseUpdate(Box::new(e)))?;
+                    batch_tasks.push(task);
                 }
             }
         }
 
-        self.processing_tasks.write().unwrap().stop_processing();
+        batch_tasks.sort_unstable_by_key(|task| task.uid);
+        let timings = {
+            let mut processing_tasks = self.processing_tasks.write().unwrap();
+            let timings = processing_tasks.finish_timings();
+            processing_tasks.stop_processing();
+            timings
+        };
+
+        let batch = Batch {
+            uid: batch_uid,
+            task_uids: batch_tasks.iter().map(|task| task.uid).collect(),
+            details: utils::merge_details(&batch_tasks),
+            timings,
+            started_at,
+            finished_at,
+        };
+        self.batches.put(&mut wtxn, &BEU32::new(batch_uid), &batch)?;
 
         #[cfg(test)]
         self.maybe_fail(tests::FailureLocation::CommittingWtxn)?;
 
         wtxn.commit().map_err(Error::HeedTransaction)?;
 
+        if let Err(e) = self.notify_finished_tasks(&ids) {
+            log::error!("Could not notify the finished tasks: {}", e);
+        }
+
         #[cfg(test)]
         self.breakpoint(Breakpoint::AfterProcessing);
 
         Ok(processed_tasks)
     }
 
+    /// Broadcast the tasks that just started processing to the subscribers of the task updates.
+    fn broadcast_processing_tasks(
+        &self,
+        tasks: RoaringBitmap,
+        started_at: OffsetDateTime,
+        batch_uid: BatchId,
+    ) -> Result<()> {
+        if self.task_updates.receiver_count() == 0 || tasks.is_empty() {
+            return Ok(());
+        }
+
+        let rtxn = self.env.read_txn()?;
+        for task in self.get_existing_tasks(&rtxn, tasks)? {
+            let task = Task {
+                status: Status::Processing,
+                started_at: Some(started_at),
+                batch_uid: Some(batch_uid),
+                ..task
+            };
+            let _ = self.task_updates.send(task);
+        }
+        Ok(())
+    }
+
+    /// Send the tasks of the batch, along with the tasks they canceled, to the webhook
+    /// and to the subscribers of the task updates.
+    fn notify_finished_tasks(&self, ids: &[TaskId]) -> Result<()> {
+        if self.webhook.is_none() && self.task_updates.receiver_count() == 0 {
+            return Ok(());
+        }
+
+        let rtxn = self.env.read_txn()?;
+        let mut finished_tasks = RoaringBitmap::from_sorted_iter(ids.iter().copied()).unwrap();
+        for id in ids {
+            if let Some(canceled_tasks) = self.canceled_by.get(&rtxn, &BEU32::new(*id))? {
+                finished_tasks |= canceled_tasks;
+            }
+        }
+
+        let tasks = self.get_existing_tasks(&rtxn, finished_tasks)?;
+        if let Some(webhook) = &self.webhook {
+            webhook.notify(tasks.iter().map(TaskView::from_task).collect());
+        }
+        for task in tasks {
+            let _ = self.task_updates.send(task);
+        }
+        Ok(())
+    }
+
+    /// Record the step the indexation of the processing tasks reached.
+    pub(crate) fn record_indexing_progress(&self, indexing_step: UpdateIndexingStep) {
+        log::debug!("update: {:?}", indexing_step);
+        if let Ok(mut processing_tasks) = self.processing_tasks.write() {
+            processing_tasks.timer.enter(Some(indexing_step.into()));
+            processing_tasks.progress = Some(indexing_step.into());
+        }
+    }
+
+    /// Start measuring the time spent in the given phase of the running batch,
+    /// or stop measuring it if no phase is given.
+    pub(crate) fn enter_batch_phase(&self, phase: Option<BatchPhase>) {
+        if let Ok(mut processing_tasks) = self.processing_tasks.write() {
+            processing_tasks.timer.enter(phase);
+        }
+    }
+
+    /// Commit a transaction of the running batch, measuring it as its commit phase.
+    pub(crate) fn commit_batch_txn(&self, wtxn: RwTxn) -> heed::Result<()> {
+        self.enter_batch_phase(Some(BatchPhase::Commit));
+        let result = wtxn.commit();
+        self.enter_batch_phase(None);
+        result
+    }
+
     pub(crate) fn delete_persisted_task_data(&self, task: &Task) -> Result<()> {
         match task.content_uuid() {
             Some(content_file) => self.delete_update_file(content_file),
@@ -1098,7 +1475,7 @@ mod tests {
     use meilisearch_types::milli::update::IndexDocumentsMethod::{
         ReplaceDocuments, UpdateDocuments,
     };
-    use meilisearch_types::tasks::IndexSwap;
+    use meilisearch_types::tasks::{IndexSwap, IndexingStep};
     use meilisearch_types::VERSION_FILE_NAME;
     use tempfile::{NamedTempFile, TempDir};
     use time::Duration;
@@ -1113,6 +1490,7 @@ mod tests {
         InsideCreateBatch,
         InsideProcessBatch,
         PanicInsideProcessBatch,
+        IndexUnavailableInsideProcessBatch,
         AcquiringWtxn,
         UpdatingTaskAfterProcessBatchSuccess { task_uid: u32 },
         UpdatingTaskAfterProcessBatchFailure,
@@ -1137,8 +1515,12 @@ mod tests {
                 dumps_path: tempdir.path().join("dumps"),
                 task_db_size: 1000 * 1000, // 1 MB, we don't use MiB on purpose.
                 index_size: 1000 * 1000,   // 1 MB, we don't use MiB on purpose.
+                index_growth_amount: 1000 * 1000, // 1 MB
+                index_count: 5,
                 indexer_config: IndexerConfig::default(),
                 autobatching_enabled,
+                webhook_url: None,
+                webhook_authorization_header: None,
             };
 
             let index_scheduler = Self::new(options, sender, planned_failures).unwrap();
@@ -1171,6 +1553,9 @@ mod tests {
                     FailureLocation::PanicInsideProcessBatch => {
                         panic!("simulated panic")
                     }
+                    FailureLocation::IndexUnavailableInsideProcessBatch => {
+                        Err(Error::IndexUnavailable(S("simulated")))
+                    }
                     _ => Err(Error::CorruptedTaskQueue),
                 }
             } else {
@@ -1330,6 +1715,8 @@ mod tests {
                     // the process_batch function can call itself recursively, thus we need to
                     // accept as may InsideProcessBatch as possible before moving to the next state.
                     InsideProcessBatch => (),
+                    // the index was full, the same batch is processed again with a bigger map size.
+                    IndexResized => self.advance_till([Start, BatchCreated]),
                     // the batch went successfully, we can stop the loop and go on with the next states.
                     ProcessBatchSucceeded => break,
                     AbortedIndexation => panic!("The batch was aborted."),
@@ -1350,6 +1737,8 @@ mod tests {
                     // the process_batch function can call itself recursively, thus we need to
                     // accept as may InsideProcessBatch as possible before moving to the next state.
                     InsideProcessBatch => (),
+                    // the index was full, the same batch is processed again with a bigger map size.
+                    IndexResized => self.advance_till([Start, BatchCreated]),
                     // the batch went failed, we can stop the loop and go on with the next states.
                     ProcessBatchFailed => break,
                     ProcessBatchSucceeded => panic!("The batch succeeded. (and it wasn't supposed to sorry)"),
@@ -3403,4 +3792,227 @@ mod tests {
         // No matter what happens in process_batch, the index_scheduler should be internally consistent
         snapshot!(snapshot_index_scheduler(&index_scheduler), name: "index_creation_failed");
     }
+
+    #[test]
+    fn document_addition_grows_a_full_index() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        // the documents can't fit in the 1 MB the index is opened with.
+        let content = (0..10_000)
+            .map(|i| format!(r#"{{ "id": {i}, "doggo": "bob the {i}th doggo of the kennel" }}"#))
+            .collect::<Vec<_>>()
+            .join(",");
+        let content = format!("[{content}]");
+
+        let (uuid, mut file) = index_scheduler.create_update_file_with_uuid(0).unwrap();
+        let documents_count = read_json(content.as_bytes(), file.as_file_mut()).unwrap();
+        file.persist().unwrap();
+        index_scheduler
+            .register(KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: uuid,
+                documents_count,
+                allow_index_creation: true,
+            })
+            .unwrap();
+
+        // the batch is processed again, as many times as needed, with a bigger map size.
+        handle.advance_one_successful_batch();
+
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, 0).unwrap().unwrap();
+        assert_eq!(task.status, Status::Succeeded);
+
+        let index = index_scheduler.index("doggos").unwrap();
+        assert!(index.map_size().unwrap() > 1000 * 1000);
+        let index_rtxn = index.read_txn().unwrap();
+        assert_eq!(index.number_of_documents(&index_rtxn).unwrap(), 10_000);
+    }
+
+    #[test]
+    fn fail_the_batch_after_too_many_retries() {
+        let planned_failures = (1..=MAX_BATCH_RETRIES + 1)
+            .map(|iteration| (iteration, FailureLocation::IndexUnavailableInsideProcessBatch))
+            .collect();
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, planned_failures);
+
+        index_scheduler.register(index_creation_task("catto", "mouse")).unwrap();
+        handle.advance_till([Start, BatchCreated]);
+        let started_at = index_scheduler.processing_tasks.read().unwrap().started_at;
+
+        // the unavailable index is waited for by processing the same batch again.
+        for _ in 0..MAX_BATCH_RETRIES {
+            handle.advance_till([Start, BatchCreated]);
+            assert_eq!(index_scheduler.processing_tasks.read().unwrap().started_at, started_at);
+        }
+        handle.advance_till([ProcessBatchFailed, AfterProcessing]);
+
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let task = index_scheduler.get_task(&rtxn, 0).unwrap().unwrap();
+        assert_eq!(task.status, Status::Failed);
+        assert_eq!(task.started_at, Some(started_at));
+    }
+
+    #[test]
+    fn document_addition_records_the_indexing_progress() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let (file, documents_count) = sample_documents(&index_scheduler, 0, 0);
+        file.persist().unwrap();
+        index_scheduler
+            .register(KindWithContent::DocumentAdditionOrUpdate {
+                index_uid: S("doggos"),
+                primary_key: Some(S("id")),
+                method: ReplaceDocuments,
+                content_file: Uuid::from_u128(0),
+                documents_count,
+                allow_index_creation: true,
+            })
+            .unwrap();
+        assert_eq!(index_scheduler.task_indexing_progress(0).unwrap(), None);
+
+        handle.advance_till([Start, BatchCreated]);
+        assert_eq!(index_scheduler.task_indexing_progress(0).unwrap(), None);
+
+        // the last step reached is kept until the task is marked as finished.
+        handle.advance_till([InsideProcessBatch, ProcessBatchSucceeded]);
+        let progress = index_scheduler.task_indexing_progress(0).unwrap().unwrap();
+        assert_eq!(progress.step, IndexingStep::MergeDataIntoFinalDatabase);
+        assert_eq!((progress.current_step, progress.total_steps), (4, 4));
+        assert_eq!(index_scheduler.indexing_progress().unwrap(), Some(progress));
+
+        handle.advance_till([AfterProcessing]);
+        assert_eq!(index_scheduler.task_indexing_progress(0).unwrap(), None);
+        assert_eq!(index_scheduler.indexing_progress().unwrap(), None);
+    }
+
+    #[test]
+    fn task_updates_are_broadcast() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+        let mut receiver = index_scheduler.subscribe_to_task_updates();
+
+        let kind = KindWithContent::IndexCreation { index_uid: S("catto"), primary_key: None };
+        index_scheduler.register(kind.clone()).unwrap();
+        // the second creation of the same index fails.
+        index_scheduler.register(kind).unwrap();
+
+        handle.advance_one_successful_batch();
+        handle.advance_one_failed_batch();
+
+        let mut updates = Vec::new();
+        while let Ok(task) = receiver.try_recv() {
+            updates.push((task.uid, task.status));
+        }
+        assert_eq!(
+            updates,
+            vec![
+                (0, Status::Enqueued),
+                (1, Status::Enqueued),
+                (0, Status::Processing),
+                (0, Status::Succeeded),
+                (1, Status::Processing),
+                (1, Status::Failed),
+            ]
+        );
+    }
+
+    #[test]
+    fn processed_batches_are_recorded() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        for i in 0..2 {
+            let (file, documents_count) = sample_documents(&index_scheduler, i, i as usize);
+            file.persist().unwrap();
+            index_scheduler
+                .register(KindWithContent::DocumentAdditionOrUpdate {
+                    index_uid: S("doggos"),
+                    primary_key: Some(S("id")),
+                    method: ReplaceDocuments,
+                    content_file: Uuid::from_u128(i),
+                    documents_count,
+                    allow_index_creation: true,
+                })
+                .unwrap();
+        }
+        let kind = KindWithContent::IndexCreation { index_uid: S("catto"), primary_key: None };
+        index_scheduler.register(kind.clone()).unwrap();
+        // the second creation of the same index fails.
+        index_scheduler.register(kind).unwrap();
+
+        // the two document additions are autobatched together.
+        handle.advance_one_successful_batch();
+        handle.advance_one_successful_batch();
+        handle.advance_one_failed_batch();
+
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        let batch_uids: Vec<_> = index_scheduler
+            .get_existing_tasks(&rtxn, 0..4)
+            .unwrap()
+            .into_iter()
+            .map(|task| task.batch_uid)
+            .collect();
+        assert_eq!(batch_uids, vec![Some(0), Some(0), Some(1), Some(2)]);
+
+        let batch = index_scheduler.get_batch(&rtxn, 0).unwrap().unwrap();
+        assert_eq!(batch.task_uids, RoaringBitmap::from_iter([0, 1]));
+        assert_eq!(batch.details.get("receivedDocuments"), Some(&serde_json::json!(2)));
+        assert_eq!(batch.details.get("indexedDocuments"), Some(&serde_json::json!(2)));
+        assert!(batch.started_at <= batch.finished_at);
+        let BatchTimings { transform, extraction, prefix_databases, commit } = batch.timings;
+        assert!(transform.is_some());
+        assert!(extraction.is_some());
+        assert!(prefix_databases.is_some());
+        assert!(commit.is_some());
+
+        // the index creations don't go through any of the timed phases.
+        let batch = index_scheduler.get_batch(&rtxn, 2).unwrap().unwrap();
+        assert_eq!(batch.task_uids, RoaringBitmap::from_iter([3]));
+        assert_eq!(batch.timings, BatchTimings::default());
+        drop(rtxn);
+
+        let query = Query { statuses: Some(vec![Status::Succeeded]), ..Query::default() };
+        let batches = index_scheduler.get_batches_from_authorized_indexes(query, None).unwrap();
+        assert_eq!(batches.iter().map(|batch| batch.uid).collect::<Vec<_>>(), vec![1, 0]);
+
+        let query = Query { from: Some(1), limit: Some(1), ..Query::default() };
+        let batches = index_scheduler.get_batches_from_authorized_indexes(query, None).unwrap();
+        assert_eq!(batches.iter().map(|batch| batch.uid).collect::<Vec<_>>(), vec![1]);
+
+        let batches = index_scheduler
+            .get_batches_from_authorized_indexes(Query::default(), Some(vec![S("doggos")]))
+            .unwrap();
+        assert_eq!(batches.iter().map(|batch| batch.uid).collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    fn deleted_tasks_are_removed_from_their_batch() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        let kind = KindWithContent::IndexCreation { index_uid: S("catto"), primary_key: None };
+        index_scheduler.register(kind).unwrap();
+        handle.advance_one_successful_batch();
+
+        index_scheduler
+            .register(KindWithContent::TaskDeletion {
+                query: "test_query".to_owned(),
+                tasks: RoaringBitmap::from_iter([0]),
+            })
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        // the emptied batch is deleted but its uid isn't given to another batch.
+        let rtxn = index_scheduler.env.read_txn().unwrap();
+        assert!(index_scheduler.get_batch(&rtxn, 0).unwrap().is_none());
+        let batch = index_scheduler.get_batch(&rtxn, 1).unwrap().unwrap();
+        assert_eq!(batch.task_uids, RoaringBitmap::from_iter([1]));
+        assert_eq!(batch.details.get("deletedTasks"), Some(&serde_json::json!(1)));
+        assert_eq!(index_scheduler.next_batch_id(&rtxn).unwrap(), 2);
+        drop(rtxn);
+
+        let batches =
+            index_scheduler.get_batches_from_authorized_indexes(Query::default(), None).unwrap();
+        assert_eq!(batches.iter().map(|batch| batch.uid).collect::<Vec<_>>(), vec![1]);
+    }
 }
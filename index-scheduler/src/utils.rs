@@ -3,11 +3,14 @@
 use std::collections::{BTreeSet, HashSet};
 use std::ops::Bound;
 
+use meilisearch_types::batches::{Batch, BatchId};
 use meilisearch_types::heed::types::{DecodeIgnore, OwnedType};
 use meilisearch_types::heed::{Database, RoTxn, RwTxn};
 use meilisearch_types::milli::{CboRoaringBitmapCodec, BEU32};
+use meilisearch_types::task_view::DetailsView;
 use meilisearch_types::tasks::{Details, IndexSwap, Kind, KindWithContent, Status};
 use roaring::{MultiOps, RoaringBitmap};
+use serde_json::{Map, Value};
 use time::OffsetDateTime;
 
 use crate::{Error, IndexScheduler, Result, Task, TaskId, BEI128};
@@ -29,6 +32,18 @@ impl IndexScheduler {
         Ok(self.all_tasks.get(rtxn, &BEU32::new(task_id))?)
     }
 
+    pub(crate) fn next_batch_id(&self, rtxn: &RoTxn) -> Result<BatchId> {
+        Ok(self
+            .batches
+            .remap_data_type::<DecodeIgnore>()
+            .last(rtxn)?
+            .map_or(0, |(k, _)| k.get() + 1))
+    }
+
+    pub(crate) fn get_batch(&self, rtxn: &RoTxn, batch_id: BatchId) -> Result<Option<Batch>> {
+        Ok(self.batches.get(rtxn, &BEU32::new(batch_id))?)
+    }
+
     /// Convert an iterator to a `Vec` of tasks. The tasks MUST exist or a
     /// `CorruptedTaskQueue` error will be throwed.
     pub(crate) fn get_existing_tasks(
@@ -224,6 +239,15 @@ pub(crate) fn keep_tasks_within_datetimes(
     Ok(())
 }
 
+/// Whether the date is strictly between the `after` and `before` bounds, when they are defined.
+pub(crate) fn is_within_datetimes(
+    date: OffsetDateTime,
+    after: Option<OffsetDateTime>,
+    before: Option<OffsetDateTime>,
+) -> bool {
+    after.map_or(true, |after| date > after) && before.map_or(true, |before| date < before)
+}
+
 // TODO: remove when Bound::map ( https://github.com/rust-lang/rust/issues/86026 ) is available on stable
 pub(crate) fn map_bound<T, U>(bound: Bound<T>, map: impl FnOnce(T) -> U) -> Bound<U> {
     match bound {
@@ -239,6 +263,7 @@ pub fn swap_index_uid_in_task(task: &mut Task, swap: (&str, &str)) {
     match &mut task.kind {
         K::DocumentAdditionOrUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentDeletion { index_uid, .. } => index_uids.push(index_uid),
+        K::DocumentDeletionByFilter { index_uid, .. } => index_uids.push(index_uid),
         K::DocumentClear { index_uid } => index_uids.push(index_uid),
         K::SettingsUpdate { index_uid, .. } => index_uids.push(index_uid),
         K::IndexDeletion { index_uid } => index_uids.push(index_uid),
@@ -324,6 +349,27 @@ pub(crate) fn check_index_swap_validity(task: &Task) -> Result<()> {
     Ok(())
 }
 
+/// Merge the details of the tasks of a batch, as they are displayed to the user.
+///
+/// The counters are summed, the other values are the ones of the last task defining them.
+pub(crate) fn merge_details(tasks: &[Task]) -> Map<String, Value> {
+    let mut merged = Map::new();
+    for details in tasks.iter().filter_map(|task| task.details.clone()) {
+        let details = match serde_json::to_value(DetailsView::from(details)) {
+            Ok(Value::Object(details)) => details,
+            _ => continue,
+        };
+        for (key, value) in details {
+            let value = match (merged.get(&key).and_then(Value::as_u64), value.as_u64()) {
+                (Some(merged), Some(count)) => Value::from(merged + count),
+                _ => value,
+            };
+            merged.insert(key, value);
+        }
+    }
+    merged
+}
+
 /// Clamp the provided value to be a multiple of system page size.
 pub fn clamp_to_page_size(size: usize) -> usize {
     size / page_size::get() * page_size::get()
@@ -342,6 +388,7 @@ impl IndexScheduler {
 
             let Task {
                 uid,
+                batch_uid,
                 enqueued_at,
                 started_at,
                 finished_at,
@@ -382,6 +429,10 @@ impl IndexScheduler {
                     .unwrap();
                 assert!(db_finished_at.contains(task_id));
             }
+            if let Some(batch_uid) = batch_uid {
+                let batch = self.get_batch(&rtxn, batch_uid).unwrap().unwrap();
+                assert!(batch.task_uids.contains(uid));
+            }
             if let Some(canceled_by) = canceled_by {
                 let db_canceled_tasks = self.get_status(&rtxn, Status::Canceled).unwrap();
                 assert!(db_canceled_tasks.contains(uid));
@@ -455,6 +506,25 @@ impl IndexScheduler {
                             assert_ne!(status, Status::Succeeded);
                         }
                     }
+                    Details::DocumentDeletionByFilter { deleted_documents, original_filter: _ } => {
+                        assert_eq!(kind.as_kind(), Kind::DocumentDeletion);
+                        match &kind {
+                            KindWithContent::DocumentDeletionByFilter { index_uid, .. } => {
+                                assert_eq!(&task_index_uid.unwrap(), index_uid);
+                            }
+                            _ => panic!(),
+                        }
+
+                        match status {
+                            Status::Enqueued | Status::Processing => (),
+                            Status::Succeeded => {
+                                assert!(deleted_documents.is_some());
+                            }
+                            Status::Failed | Status::Canceled => {
+                                assert!(deleted_documents == Some(0));
+                            }
+                        }
+                    }
                     Details::ClearAll { deleted_documents } => {
                         assert!(matches!(
                             kind.as_kind(),
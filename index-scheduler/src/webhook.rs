@@ -0,0 +1,190 @@
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+use std::thread;
+use std::time::Duration;
+
+use log::{debug, error, warn};
+use meilisearch_types::task_view::TaskView;
+use reqwest::blocking::Client;
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+
+/// The number of notifications that can wait to be delivered
+/// before the new ones are dropped.
+const QUEUE_CAPACITY: usize = 1024;
+/// The number of times the delivery of a notification is attempted.
+const MAX_ATTEMPTS: u32 = 8;
+/// The time given to the receiver to answer each attempt. The notifications are delivered
+/// one by one, a receiver that hangs delays the next ones by up to `MAX_ATTEMPTS` times it.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// The time waited after the first failed attempt, doubled after each new failure.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Notifies an HTTP endpoint of the tasks that finished.
+///
+/// The notifications are delivered by a dedicated thread, so that a slow
+/// or failing receiver never blocks the scheduler.
+#[derive(Clone)]
+pub struct Webhook {
+    sender: SyncSender<Vec<TaskView>>,
+}
+
+impl Webhook {
+    pub fn new(url: String, authorization: Option<String>) -> Self {
+        let (sender, receiver) = sync_channel(QUEUE_CAPACITY);
+        thread::Builder::new()
+            .name(String::from("webhook"))
+            .spawn(move || deliver_notifications(receiver, &url, authorization.as_deref()))
+            .unwrap();
+        Self { sender }
+    }
+
+    /// Queue the tasks to be sent to the webhook, without waiting for the delivery.
+    pub fn notify(&self, tasks: Vec<TaskView>) {
+        if tasks.is_empty() {
+            return;
+        }
+
+        match self.sender.try_send(tasks) {
+            Ok(()) => (),
+            Err(TrySendError::Full(tasks)) => {
+                error!("Too many webhook notifications are pending, the notification of {} tasks was dropped.", tasks.len())
+            }
+            Err(TrySendError::Disconnected(_)) => error!("The webhook thread stopped."),
+        }
+    }
+}
+
+fn deliver_notifications(
+    receiver: Receiver<Vec<TaskView>>,
+    url: &str,
+    authorization: Option<&str>,
+) {
+    let client = match Client::builder().timeout(REQUEST_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            error!("Could not create the webhook client: {}", e);
+            return;
+        }
+    };
+
+    for tasks in receiver {
+        // The tasks are sent as newline-delimited JSON.
+        let mut body = Vec::new();
+        for task in &tasks {
+            if let Err(e) = serde_json::to_writer(&mut body, task) {
+                error!("Could not serialize the task {} for the webhook: {}", task.uid, e);
+                continue;
+            }
+            body.push(b'\n');
+        }
+
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 1..=MAX_ATTEMPTS {
+            let mut request =
+                client.post(url).header(CONTENT_TYPE, "application/x-ndjson").body(body.clone());
+            if let Some(authorization) = authorization {
+                request = request.header(AUTHORIZATION, authorization);
+            }
+
+            let error = match request.send().and_then(|response| response.error_for_status()) {
+                Ok(_) => {
+                    debug!("Notified the webhook of {} finished tasks.", tasks.len());
+                    break;
+                }
+                Err(e) => e,
+            };
+
+            if attempt == MAX_ATTEMPTS {
+                error!("Could not notify the webhook after {} attempts: {}", attempt, error);
+            } else {
+                warn!("Could not notify the webhook, retrying in {:?}: {}", backoff, error);
+                thread::sleep(backoff);
+                backoff *= 2;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::time::Instant;
+
+    use big_s::S;
+    use meilisearch_types::tasks::{KindWithContent, Status, Task};
+    use time::OffsetDateTime;
+
+    use super::*;
+
+    fn task_view(uid: u32) -> TaskView {
+        TaskView::from_task(&Task {
+            uid,
+            batch_uid: Some(0),
+            enqueued_at: OffsetDateTime::now_utc(),
+            started_at: None,
+            finished_at: None,
+            error: None,
+            canceled_by: None,
+            details: None,
+            status: Status::Succeeded,
+            kind: KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None },
+        })
+    }
+
+    #[test]
+    fn send_the_tasks_as_ndjson() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/webhook", listener.local_addr().unwrap());
+        let webhook = Webhook::new(url, Some(S("Bearer doggo")));
+        webhook.notify(vec![task_view(0), task_view(1)]);
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        assert_eq!(request_line, "POST /webhook HTTP/1.1\r\n");
+
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            match line.trim_end().split_once(": ") {
+                Some((name, value)) => headers.push((name.to_lowercase(), value.to_string())),
+                None => break,
+            }
+        }
+        let header = |name: &str| {
+            headers.iter().find(|(header, _)| header == name).map(|(_, value)| value.as_str())
+        };
+        assert_eq!(header("authorization"), Some("Bearer doggo"));
+        assert_eq!(header("content-type"), Some("application/x-ndjson"));
+
+        let content_length = header("content-length").unwrap().parse().unwrap();
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        reader.get_mut().write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").unwrap();
+
+        let body = String::from_utf8(body).unwrap();
+        assert!(body.ends_with('\n'));
+        let uids: Vec<_> = body
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["uid"].clone())
+            .collect();
+        assert_eq!(uids, [0, 1]);
+    }
+
+    #[test]
+    fn drop_the_notifications_when_the_queue_is_full() {
+        // the receiver never answers, the first notification is stuck being delivered.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let webhook = Webhook::new(url, None);
+
+        // notifying never waits for the queue to have some room.
+        let started_at = Instant::now();
+        for uid in 0..QUEUE_CAPACITY as u32 + 2 {
+            webhook.notify(vec![task_view(uid)]);
+        }
+        assert!(started_at.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,61 @@
+use serde::Serialize;
+use time::{Duration, OffsetDateTime};
+
+use crate::batches::{Batch, BatchId, BatchTimings};
+use crate::tasks::{serialize_duration, TaskId};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchView {
+    pub uid: BatchId,
+    pub task_uids: Vec<TaskId>,
+    pub details: serde_json::Map<String, serde_json::Value>,
+    pub timings: BatchTimingsView,
+    #[serde(serialize_with = "serialize_duration", default)]
+    pub duration: Option<Duration>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub finished_at: OffsetDateTime,
+}
+
+impl BatchView {
+    pub fn from_batch(batch: &Batch) -> BatchView {
+        BatchView {
+            uid: batch.uid,
+            task_uids: batch.task_uids.iter().collect(),
+            details: batch.details.clone(),
+            timings: BatchTimingsView::from(batch.timings),
+            duration: Some(batch.finished_at - batch.started_at),
+            started_at: batch.started_at,
+            finished_at: batch.finished_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchTimingsView {
+    #[serde(serialize_with = "serialize_duration")]
+    pub transform: Option<Duration>,
+    #[serde(serialize_with = "serialize_duration")]
+    pub extraction: Option<Duration>,
+    #[serde(serialize_with = "serialize_duration")]
+    pub prefix_databases: Option<Duration>,
+    #[serde(serialize_with = "serialize_duration")]
+    pub commit: Option<Duration>,
+}
+
+impl From<BatchTimings> for BatchTimingsView {
+    fn from(timings: BatchTimings) -> Self {
+        let convert = |duration: Option<std::time::Duration>| {
+            duration.and_then(|duration| Duration::try_from(duration).ok())
+        };
+        BatchTimingsView {
+            transform: convert(timings.transform),
+            extraction: convert(timings.extraction),
+            prefix_databases: convert(timings.prefix_databases),
+            commit: convert(timings.commit),
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+pub type BatchId = u32;
+
+/// A group of tasks that were processed together by the index scheduler.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Batch {
+    pub uid: BatchId,
+    /// The tasks processed by the batch that were not deleted since.
+    pub task_uids: RoaringBitmap,
+    /// The details of the tasks, merged together, as they are displayed to the user.
+    pub details: serde_json::Map<String, serde_json::Value>,
+    pub timings: BatchTimings,
+
+    #[serde(with = "time::serde::rfc3339")]
+    pub started_at: OffsetDateTime,
+    #[serde(with = "time::serde::rfc3339")]
+    pub finished_at: OffsetDateTime,
+}
+
+/// The time spent in each phase of the processing of a batch.
+///
+/// A phase the batch didn't go through is set to `None`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchTimings {
+    /// Reading the documents and merging them with the ones already in the index.
+    pub transform: Option<Duration>,
+    /// Extracting the words and facets of the documents and writing them in the databases.
+    pub extraction: Option<Duration>,
+    /// Computing the prefix databases.
+    pub prefix_databases: Option<Duration>,
+    /// Committing the changes made by the batch, to the index or to the task queue.
+    pub commit: Option<Duration>,
+}
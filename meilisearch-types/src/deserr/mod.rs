@@ -6,8 +6,8 @@ use deserr::{DeserializeError, MergeWithError, ValuePointerRef};
 
 use crate::error::deserr_codes::{self, *};
 use crate::error::{
-    unwrap_any, Code, DeserrParseBoolError, DeserrParseIntError, ErrorCode, InvalidTaskDateError,
-    ParseOffsetDateTimeError,
+    unwrap_any, Code, DeserrParseBoolError, DeserrParseIntError, DeserrRankingScoreThresholdError,
+    ErrorCode, InvalidTaskDateError, ParseOffsetDateTimeError,
 };
 use crate::index_uid::IndexUidFormatError;
 use crate::tasks::{ParseTaskKindError, ParseTaskStatusError};
@@ -99,6 +99,11 @@ make_missing_field_convenience_builder!(MissingApiKeyActions, missing_api_key_ac
 make_missing_field_convenience_builder!(MissingApiKeyExpiresAt, missing_api_key_expires_at);
 make_missing_field_convenience_builder!(MissingApiKeyIndexes, missing_api_key_indexes);
 make_missing_field_convenience_builder!(MissingSwapIndexes, missing_swap_indexes);
+make_missing_field_convenience_builder!(MissingDocumentFilter, missing_document_filter);
+make_missing_field_convenience_builder!(
+    MissingFacetSearchFacetName,
+    missing_facet_search_facet_name
+);
 
 // Integrate a sub-error into a [`DeserrError`] by taking its error message but using
 // the default error code (C) from `Self`
@@ -126,9 +131,11 @@ macro_rules! merge_with_error_impl_take_error_message {
 // All these errors can be merged into a `DeserrError`
 merge_with_error_impl_take_error_message!(DeserrParseIntError);
 merge_with_error_impl_take_error_message!(DeserrParseBoolError);
+merge_with_error_impl_take_error_message!(DeserrRankingScoreThresholdError);
 merge_with_error_impl_take_error_message!(uuid::Error);
 merge_with_error_impl_take_error_message!(InvalidTaskDateError);
 merge_with_error_impl_take_error_message!(ParseOffsetDateTimeError);
 merge_with_error_impl_take_error_message!(ParseTaskKindError);
 merge_with_error_impl_take_error_message!(ParseTaskStatusError);
 merge_with_error_impl_take_error_message!(IndexUidFormatError);
+merge_with_error_impl_take_error_message!(milli::LocaleParseError);
@@ -1,3 +1,5 @@
+pub mod batch_view;
+pub mod batches;
 pub mod compression;
 pub mod deserr;
 pub mod document_formats;
@@ -6,6 +8,7 @@ pub mod index_uid;
 pub mod keys;
 pub mod settings;
 pub mod star_or;
+pub mod task_view;
 pub mod tasks;
 pub mod versioning;
 pub use milli::{heed, Index};
@@ -8,7 +8,7 @@ use std::str::FromStr;
 use deserr::{DeserializeError, DeserializeFromValue, ErrorKind, MergeWithError, ValuePointerRef};
 use fst::IntoStreamer;
 use milli::update::Setting;
-use milli::{Criterion, CriterionError, Index, DEFAULT_VALUES_PER_FACET};
+use milli::{Criterion, CriterionError, Index, LocalizedAttributesRule, DEFAULT_VALUES_PER_FACET};
 use serde::{Deserialize, Serialize, Serializer};
 
 use crate::deserr::DeserrJsonError;
@@ -102,6 +102,94 @@ pub struct FacetingSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
     pub max_values_per_facet: Setting<usize>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub sort_facet_values_by: Setting<BTreeMap<String, FacetValuesSort>>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, DeserializeFromValue)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum FacetValuesSort {
+    /// Facet values are sorted in alphabetical order, ascending from A to Z.
+    Alpha,
+    /// Facet values are sorted by decreasing count.
+    /// The count is the number of records containing this facet value in the results of the query.
+    Count,
+}
+
+impl From<FacetValuesSort> for milli::OrderBy {
+    fn from(val: FacetValuesSort) -> Self {
+        match val {
+            FacetValuesSort::Alpha => milli::OrderBy::Lexicographic,
+            FacetValuesSort::Count => milli::OrderBy::Count,
+        }
+    }
+}
+
+impl From<milli::OrderBy> for FacetValuesSort {
+    fn from(val: milli::OrderBy) -> Self {
+        match val {
+            milli::OrderBy::Lexicographic => FacetValuesSort::Alpha,
+            milli::OrderBy::Count => FacetValuesSort::Count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, DeserializeFromValue)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum ProximityPrecisionView {
+    /// The proximity is computed from the distance between the words.
+    ByWord,
+    /// The proximity only tells whether the words are in the same attribute.
+    ByAttribute,
+}
+
+impl From<ProximityPrecisionView> for milli::ProximityPrecision {
+    fn from(view: ProximityPrecisionView) -> Self {
+        match view {
+            ProximityPrecisionView::ByWord => milli::ProximityPrecision::ByWord,
+            ProximityPrecisionView::ByAttribute => milli::ProximityPrecision::ByAttribute,
+        }
+    }
+}
+
+impl From<milli::ProximityPrecision> for ProximityPrecisionView {
+    fn from(precision: milli::ProximityPrecision) -> Self {
+        match precision {
+            milli::ProximityPrecision::ByWord => ProximityPrecisionView::ByWord,
+            milli::ProximityPrecision::ByAttribute => ProximityPrecisionView::ByAttribute,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, DeserializeFromValue)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum PrefixSearchView {
+    /// The prefix databases are computed at indexing time.
+    IndexingTime,
+    /// The prefix databases are not computed and the last query word must match entirely.
+    Disabled,
+}
+
+impl From<PrefixSearchView> for milli::PrefixSearch {
+    fn from(view: PrefixSearchView) -> Self {
+        match view {
+            PrefixSearchView::IndexingTime => milli::PrefixSearch::IndexingTime,
+            PrefixSearchView::Disabled => milli::PrefixSearch::Disabled,
+        }
+    }
+}
+
+impl From<milli::PrefixSearch> for PrefixSearchView {
+    fn from(prefix_search: milli::PrefixSearch) -> Self {
+        match prefix_search {
+            milli::PrefixSearch::IndexingTime => PrefixSearchView::IndexingTime,
+            milli::PrefixSearch::Disabled => PrefixSearchView::Disabled,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, DeserializeFromValue)]
@@ -167,12 +255,27 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsStopWords>)]
     pub stop_words: Setting<BTreeSet<String>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsNonSeparatorTokens>)]
+    pub non_separator_tokens: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSeparatorTokens>)]
+    pub separator_tokens: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDictionary>)]
+    pub dictionary: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsLocalizedAttributes>)]
+    pub localized_attributes: Setting<Vec<LocalizedAttributesRule>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSynonyms>)]
     pub synonyms: Setting<BTreeMap<String, Vec<String>>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsDistinctAttribute>)]
     pub distinct_attribute: Setting<String>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsProximityPrecision>)]
+    pub proximity_precision: Setting<ProximityPrecisionView>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsTypoTolerance>)]
     pub typo_tolerance: Setting<TypoSettings>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
@@ -181,6 +284,12 @@ pub struct Settings<T> {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsPagination>)]
     pub pagination: Setting<PaginationSettings>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchCutoffMs>)]
+    pub search_cutoff_ms: Setting<u64>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsPrefixSearch>)]
+    pub prefix_search: Setting<PrefixSearchView>,
 
     #[serde(skip)]
     #[deserr(skip)]
@@ -196,11 +305,18 @@ impl Settings<Checked> {
             sortable_attributes: Setting::Reset,
             ranking_rules: Setting::Reset,
             stop_words: Setting::Reset,
+            non_separator_tokens: Setting::Reset,
+            separator_tokens: Setting::Reset,
+            dictionary: Setting::Reset,
+            localized_attributes: Setting::Reset,
             synonyms: Setting::Reset,
             distinct_attribute: Setting::Reset,
+            proximity_precision: Setting::Reset,
             typo_tolerance: Setting::Reset,
             faceting: Setting::Reset,
             pagination: Setting::Reset,
+            search_cutoff_ms: Setting::Reset,
+            prefix_search: Setting::Reset,
             _kind: PhantomData,
         }
     }
@@ -213,11 +329,18 @@ impl Settings<Checked> {
             sortable_attributes,
             ranking_rules,
             stop_words,
+            non_separator_tokens,
+            separator_tokens,
+            dictionary,
+            localized_attributes,
             synonyms,
             distinct_attribute,
+            proximity_precision,
             typo_tolerance,
             faceting,
             pagination,
+            search_cutoff_ms,
+            prefix_search,
             ..
         } = self;
 
@@ -228,11 +351,18 @@ impl Settings<Checked> {
             sortable_attributes,
             ranking_rules,
             stop_words,
+            non_separator_tokens,
+            separator_tokens,
+            dictionary,
+            localized_attributes,
             synonyms,
             distinct_attribute,
+            proximity_precision,
             typo_tolerance,
             faceting,
             pagination,
+            search_cutoff_ms,
+            prefix_search,
             _kind: PhantomData,
         }
     }
@@ -269,11 +399,18 @@ impl Settings<Unchecked> {
             sortable_attributes: self.sortable_attributes,
             ranking_rules: self.ranking_rules,
             stop_words: self.stop_words,
+            non_separator_tokens: self.non_separator_tokens,
+            separator_tokens: self.separator_tokens,
+            dictionary: self.dictionary,
+            localized_attributes: self.localized_attributes,
             synonyms: self.synonyms,
             distinct_attribute: self.distinct_attribute,
+            proximity_precision: self.proximity_precision,
             typo_tolerance: self.typo_tolerance,
             faceting: self.faceting,
             pagination: self.pagination,
+            search_cutoff_ms: self.search_cutoff_ms,
+            prefix_search: self.prefix_search,
             _kind: PhantomData,
         }
     }
@@ -331,6 +468,34 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.non_separator_tokens {
+        Setting::Set(ref non_separator_tokens) => {
+            builder.set_non_separator_tokens(non_separator_tokens.clone())
+        }
+        Setting::Reset => builder.reset_non_separator_tokens(),
+        Setting::NotSet => (),
+    }
+
+    match settings.separator_tokens {
+        Setting::Set(ref separator_tokens) => {
+            builder.set_separator_tokens(separator_tokens.clone())
+        }
+        Setting::Reset => builder.reset_separator_tokens(),
+        Setting::NotSet => (),
+    }
+
+    match settings.dictionary {
+        Setting::Set(ref dictionary) => builder.set_dictionary(dictionary.clone()),
+        Setting::Reset => builder.reset_dictionary(),
+        Setting::NotSet => (),
+    }
+
+    match settings.localized_attributes {
+        Setting::Set(ref rules) => builder.set_localized_attributes_rules(rules.clone()),
+        Setting::Reset => builder.reset_localized_attributes_rules(),
+        Setting::NotSet => (),
+    }
+
     match settings.synonyms {
         Setting::Set(ref synonyms) => builder.set_synonyms(synonyms.clone().into_iter().collect()),
         Setting::Reset => builder.reset_synonyms(),
@@ -343,6 +508,12 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match settings.proximity_precision {
+        Setting::Set(precision) => builder.set_proximity_precision(precision.into()),
+        Setting::Reset => builder.reset_proximity_precision(),
+        Setting::NotSet => (),
+    }
+
     match settings.typo_tolerance {
         Setting::Set(ref value) => {
             match value.enabled {
@@ -399,12 +570,24 @@ pub fn apply_settings_to_builder(
     }
 
     match settings.faceting {
-        Setting::Set(ref value) => match value.max_values_per_facet {
-            Setting::Set(val) => builder.set_max_values_per_facet(val),
-            Setting::Reset => builder.reset_max_values_per_facet(),
-            Setting::NotSet => (),
-        },
-        Setting::Reset => builder.reset_max_values_per_facet(),
+        Setting::Set(FacetingSettings { max_values_per_facet, ref sort_facet_values_by }) => {
+            match max_values_per_facet {
+                Setting::Set(val) => builder.set_max_values_per_facet(val),
+                Setting::Reset => builder.reset_max_values_per_facet(),
+                Setting::NotSet => (),
+            }
+            match sort_facet_values_by {
+                Setting::Set(val) => builder.set_sort_facet_values_by(
+                    val.iter().map(|(name, order)| (name.clone(), (*order).into())).collect(),
+                ),
+                Setting::Reset => builder.reset_sort_facet_values_by(),
+                Setting::NotSet => (),
+            }
+        }
+        Setting::Reset => {
+            builder.reset_max_values_per_facet();
+            builder.reset_sort_facet_values_by();
+        }
         Setting::NotSet => (),
     }
 
@@ -417,6 +600,18 @@ pub fn apply_settings_to_builder(
         Setting::Reset => builder.reset_pagination_max_total_hits(),
         Setting::NotSet => (),
     }
+
+    match settings.search_cutoff_ms {
+        Setting::Set(cutoff) => builder.set_search_cutoff(cutoff),
+        Setting::Reset => builder.reset_search_cutoff(),
+        Setting::NotSet => (),
+    }
+
+    match settings.prefix_search {
+        Setting::Set(prefix_search) => builder.set_prefix_search(prefix_search.into()),
+        Setting::Reset => builder.reset_prefix_search(),
+        Setting::NotSet => (),
+    }
 }
 
 pub fn settings(
@@ -443,7 +638,13 @@ pub fn settings(
         })
         .transpose()?
         .unwrap_or_default();
+    let non_separator_tokens = index.non_separator_tokens(rtxn)?.unwrap_or_default();
+    let separator_tokens = index.separator_tokens(rtxn)?.unwrap_or_default();
+    let dictionary = index.dictionary(rtxn)?.unwrap_or_default();
+    let localized_attributes_rules = index.localized_attributes_rules(rtxn)?;
     let distinct_field = index.distinct_field(rtxn)?.map(String::from);
+    let proximity_precision = index.proximity_precision(rtxn)?.unwrap_or_default();
+    let prefix_search = index.prefix_search(rtxn)?.unwrap_or_default();
 
     // in milli each word in the synonyms map were split on their separator. Since we lost
     // this information we are going to put space between words.
@@ -472,10 +673,18 @@ pub fn settings(
         disable_on_attributes: Setting::Set(disabled_attributes),
     };
 
+    let mut sort_facet_values_by: BTreeMap<_, FacetValuesSort> = index
+        .sort_facet_values_by(rtxn)?
+        .into_iter()
+        .map(|(name, order)| (name, order.into()))
+        .collect();
+    sort_facet_values_by.entry("*".to_string()).or_insert(FacetValuesSort::Alpha);
+
     let faceting = FacetingSettings {
         max_values_per_facet: Setting::Set(
             index.max_values_per_facet(rtxn)?.unwrap_or(DEFAULT_VALUES_PER_FACET),
         ),
+        sort_facet_values_by: Setting::Set(sort_facet_values_by),
     };
 
     let pagination = PaginationSettings {
@@ -497,14 +706,27 @@ pub fn settings(
         sortable_attributes: Setting::Set(sortable_attributes),
         ranking_rules: Setting::Set(criteria.iter().map(|c| c.clone().into()).collect()),
         stop_words: Setting::Set(stop_words),
+        non_separator_tokens: Setting::Set(non_separator_tokens),
+        separator_tokens: Setting::Set(separator_tokens),
+        dictionary: Setting::Set(dictionary),
+        localized_attributes: match localized_attributes_rules {
+            Some(rules) => Setting::Set(rules),
+            None => Setting::Reset,
+        },
         distinct_attribute: match distinct_field {
             Some(field) => Setting::Set(field),
             None => Setting::Reset,
         },
+        proximity_precision: Setting::Set(proximity_precision.into()),
         synonyms: Setting::Set(synonyms),
         typo_tolerance: Setting::Set(typo_tolerance),
         faceting: Setting::Set(faceting),
         pagination: Setting::Set(pagination),
+        search_cutoff_ms: match index.search_cutoff(rtxn)? {
+            Some(cutoff) => Setting::Set(cutoff),
+            None => Setting::Reset,
+        },
+        prefix_search: Setting::Set(prefix_search.into()),
         _kind: PhantomData,
     })
 }
@@ -619,11 +841,18 @@ pub(crate) mod test {
             sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            non_separator_tokens: Setting::NotSet,
+            separator_tokens: Setting::NotSet,
+            dictionary: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
+            proximity_precision: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            search_cutoff_ms: Setting::NotSet,
+            prefix_search: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -640,11 +869,18 @@ pub(crate) mod test {
             sortable_attributes: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
+            non_separator_tokens: Setting::NotSet,
+            separator_tokens: Setting::NotSet,
+            dictionary: Setting::NotSet,
+            localized_attributes: Setting::NotSet,
             synonyms: Setting::NotSet,
             distinct_attribute: Setting::NotSet,
+            proximity_precision: Setting::NotSet,
             typo_tolerance: Setting::NotSet,
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
+            search_cutoff_ms: Setting::NotSet,
+            prefix_search: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
         };
 
@@ -4,12 +4,13 @@ use std::fmt::{Display, Write};
 use std::str::FromStr;
 
 use enum_iterator::Sequence;
-use milli::update::IndexDocumentsMethod;
+use milli::update::{IndexDocumentsMethod, UpdateIndexingStep};
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize, Serializer};
 use time::{Duration, OffsetDateTime};
 use uuid::Uuid;
 
+use crate::batches::BatchId;
 use crate::error::ResponseError;
 use crate::keys::Key;
 use crate::settings::{Settings, Unchecked};
@@ -21,6 +22,9 @@ pub type TaskId = u32;
 #[serde(rename_all = "camelCase")]
 pub struct Task {
     pub uid: TaskId,
+    /// The batch the task was processed in, if it was processed.
+    #[serde(default)]
+    pub batch_uid: Option<BatchId>,
 
     #[serde(with = "time::serde::rfc3339")]
     pub enqueued_at: OffsetDateTime,
@@ -49,6 +53,7 @@ impl Task {
             | IndexSwap { .. } => None,
             DocumentAdditionOrUpdate { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
+            | DocumentDeletionByFilter { index_uid, .. }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
@@ -67,6 +72,7 @@ impl Task {
         match self.kind {
             KindWithContent::DocumentAdditionOrUpdate { content_file, .. } => Some(content_file),
             KindWithContent::DocumentDeletion { .. }
+            | KindWithContent::DocumentDeletionByFilter { .. }
             | KindWithContent::DocumentClear { .. }
             | KindWithContent::SettingsUpdate { .. }
             | KindWithContent::IndexDeletion { .. }
@@ -96,6 +102,10 @@ pub enum KindWithContent {
         index_uid: String,
         documents_ids: Vec<String>,
     },
+    DocumentDeletionByFilter {
+        index_uid: String,
+        filter_expr: serde_json::Value,
+    },
     DocumentClear {
         index_uid: String,
     },
@@ -145,6 +155,7 @@ impl KindWithContent {
         match self {
             KindWithContent::DocumentAdditionOrUpdate { .. } => Kind::DocumentAdditionOrUpdate,
             KindWithContent::DocumentDeletion { .. } => Kind::DocumentDeletion,
+            KindWithContent::DocumentDeletionByFilter { .. } => Kind::DocumentDeletion,
             KindWithContent::DocumentClear { .. } => Kind::DocumentDeletion,
             KindWithContent::SettingsUpdate { .. } => Kind::SettingsUpdate,
             KindWithContent::IndexCreation { .. } => Kind::IndexCreation,
@@ -168,6 +179,7 @@ impl KindWithContent {
             | TaskDeletion { .. } => vec![],
             DocumentAdditionOrUpdate { index_uid, .. }
             | DocumentDeletion { index_uid, .. }
+            | DocumentDeletionByFilter { index_uid, .. }
             | DocumentClear { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
@@ -200,6 +212,12 @@ impl KindWithContent {
                     deleted_documents: None,
                 })
             }
+            KindWithContent::DocumentDeletionByFilter { index_uid: _, filter_expr } => {
+                Some(Details::DocumentDeletionByFilter {
+                    original_filter: filter_expr.to_string(),
+                    deleted_documents: None,
+                })
+            }
             KindWithContent::DocumentClear { .. } | KindWithContent::IndexDeletion { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
@@ -242,6 +260,12 @@ impl KindWithContent {
                     deleted_documents: Some(0),
                 })
             }
+            KindWithContent::DocumentDeletionByFilter { index_uid: _, filter_expr } => {
+                Some(Details::DocumentDeletionByFilter {
+                    original_filter: filter_expr.to_string(),
+                    deleted_documents: Some(0),
+                })
+            }
             KindWithContent::DocumentClear { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
@@ -282,6 +306,7 @@ impl From<&KindWithContent> for Option<Details> {
                 })
             }
             KindWithContent::DocumentDeletion { .. } => None,
+            KindWithContent::DocumentDeletionByFilter { .. } => None,
             KindWithContent::DocumentClear { .. } => None,
             KindWithContent::SettingsUpdate { new_settings, .. } => {
                 Some(Details::SettingsUpdate { settings: new_settings.clone() })
@@ -369,6 +394,56 @@ impl fmt::Display for ParseTaskStatusError {
 }
 impl std::error::Error for ParseTaskStatusError {}
 
+/// The progress of the indexation of the processing tasks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexingProgress {
+    pub step: IndexingStep,
+    /// The position of the step, starting at one.
+    pub current_step: usize,
+    pub total_steps: usize,
+    /// The number of documents, or databases, processed during this step.
+    pub processed: usize,
+    /// The number of documents, or databases, to process during this step, if known.
+    pub total: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IndexingStep {
+    RemapDocumentAddition,
+    ComputeIdsAndMergeDocuments,
+    IndexDocuments,
+    MergeDataIntoFinalDatabase,
+}
+
+impl From<UpdateIndexingStep> for IndexingProgress {
+    fn from(step: UpdateIndexingStep) -> Self {
+        let (indexing_step, processed, total) = match step {
+            UpdateIndexingStep::RemapDocumentAddition { documents_seen } => {
+                (IndexingStep::RemapDocumentAddition, documents_seen, None)
+            }
+            UpdateIndexingStep::ComputeIdsAndMergeDocuments { documents_seen, total_documents } => {
+                (IndexingStep::ComputeIdsAndMergeDocuments, documents_seen, Some(total_documents))
+            }
+            UpdateIndexingStep::IndexDocuments { documents_seen, total_documents } => {
+                (IndexingStep::IndexDocuments, documents_seen, Some(total_documents))
+            }
+            UpdateIndexingStep::MergeDataIntoFinalDatabase { databases_seen, total_databases } => {
+                (IndexingStep::MergeDataIntoFinalDatabase, databases_seen, Some(total_databases))
+            }
+        };
+
+        IndexingProgress {
+            step: indexing_step,
+            current_step: step.step() + 1,
+            total_steps: step.number_of_steps(),
+            processed,
+            total,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Sequence)]
 #[serde(rename_all = "camelCase")]
 pub enum Kind {
@@ -478,6 +553,7 @@ pub enum Details {
     SettingsUpdate { settings: Box<Settings<Unchecked>> },
     IndexInfo { primary_key: Option<String> },
     DocumentDeletion { provided_ids: usize, deleted_documents: Option<u64> },
+    DocumentDeletionByFilter { original_filter: String, deleted_documents: Option<u64> },
     ClearAll { deleted_documents: Option<u64> },
     TaskCancelation { matched_tasks: u64, canceled_tasks: Option<u64>, original_filter: String },
     TaskDeletion { matched_tasks: u64, deleted_tasks: Option<u64>, original_filter: String },
@@ -493,6 +569,9 @@ impl Details {
                 *indexed_documents = Some(0)
             }
             Self::DocumentDeletion { deleted_documents, .. } => *deleted_documents = Some(0),
+            Self::DocumentDeletionByFilter { deleted_documents, .. } => {
+                *deleted_documents = Some(0)
+            }
             Self::ClearAll { deleted_documents } => *deleted_documents = Some(0),
             Self::TaskCancelation { canceled_tasks, .. } => *canceled_tasks = Some(0),
             Self::TaskDeletion { deleted_tasks, .. } => *deleted_tasks = Some(0),
@@ -3,7 +3,6 @@ use aweb::error::{JsonPayloadError, QueryPayloadError};
 use meilisearch_types::document_formats::{DocumentFormatError, PayloadType};
 use meilisearch_types::error::{Code, ErrorCode, ResponseError};
 use meilisearch_types::index_uid::{IndexUid, IndexUidFormatError};
-use serde_json::Value;
 use tokio::task::JoinError;
 
 #[derive(Debug, thiserror::Error)]
@@ -18,8 +17,8 @@ pub enum MeilisearchHttpError {
     InvalidContentType(String, Vec<String>),
     #[error("Document `{0}` not found.")]
     DocumentNotFound(String),
-    #[error("Invalid syntax for the filter parameter: `expected {}, found: {1}`.", .0.join(", "))]
-    InvalidExpression(&'static [&'static str], Value),
+    #[error("The filter cannot be empty.")]
+    EmptyFilter,
     #[error("A {0} payload is missing.")]
     MissingPayload(PayloadType),
     #[error("The provided payload reached the size limit.")]
@@ -28,6 +27,14 @@ pub enum MeilisearchHttpError {
         .0.iter().map(|uid| format!("\"{uid}\"")).collect::<Vec<_>>().join(", "), .0.len()
     )]
     SwapIndexPayloadWrongLength(Vec<IndexUid>),
+    #[error("Using `federationOptions` is not allowed in a non-federated search. Hint: remove `federationOptions` from the query or add `federation` to the request.")]
+    FederationOptionsInNonFederatedRequest,
+    #[error("Using pagination options is not allowed in federated queries. Hint: remove `{0}` from the query or remove `federation` from the request.")]
+    PaginationInFederatedQuery(&'static str),
+    #[error("Using facets is not allowed in federated queries. Hint: remove `facets` from the query or remove `federation` from the request.")]
+    FacetsInFederatedQuery,
+    #[error("The federation weight must be a positive number, but `{0}` was given.")]
+    NegativeFederationWeight(f64),
     #[error(transparent)]
     IndexUid(#[from] IndexUidFormatError),
     #[error(transparent)]
@@ -55,9 +62,17 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::MissingPayload(_) => Code::MissingPayload,
             MeilisearchHttpError::InvalidContentType(_, _) => Code::InvalidContentType,
             MeilisearchHttpError::DocumentNotFound(_) => Code::DocumentNotFound,
-            MeilisearchHttpError::InvalidExpression(_, _) => Code::InvalidSearchFilter,
+            MeilisearchHttpError::EmptyFilter => Code::InvalidDocumentFilter,
             MeilisearchHttpError::PayloadTooLarge => Code::PayloadTooLarge,
             MeilisearchHttpError::SwapIndexPayloadWrongLength(_) => Code::InvalidSwapIndexes,
+            MeilisearchHttpError::FederationOptionsInNonFederatedRequest => {
+                Code::InvalidMultiSearchFederationOptions
+            }
+            MeilisearchHttpError::PaginationInFederatedQuery(_) => {
+                Code::InvalidMultiSearchQueryPagination
+            }
+            MeilisearchHttpError::FacetsInFederatedQuery => Code::InvalidMultiSearchQueryFacets,
+            MeilisearchHttpError::NegativeFederationWeight(_) => Code::InvalidMultiSearchWeight,
             MeilisearchHttpError::IndexUid(e) => e.error_code(),
             MeilisearchHttpError::SerdeJson(_) => Code::Internal,
             MeilisearchHttpError::HeedError(_) => Code::Internal,
@@ -14,6 +14,7 @@ use meilisearch_types::error::{Code, ResponseError};
 pub struct GuardedData<P, D> {
     data: D,
     filters: AuthFilter,
+    credentials: Credentials,
     _marker: PhantomData<P>,
 }
 
@@ -22,6 +23,10 @@ impl<P, D> GuardedData<P, D> {
         &self.filters
     }
 
+    pub fn credentials(&self) -> &Credentials {
+        &self.credentials
+    }
+
     async fn auth_bearer(
         auth: AuthController,
         token: String,
@@ -32,10 +37,11 @@ impl<P, D> GuardedData<P, D> {
         P: Policy + 'static,
     {
         let missing_master_key = auth.get_master_key().is_none();
+        let credentials = Credentials { auth, token, index, policy: P::authenticate };
 
-        match Self::authenticate(auth, token, index).await? {
+        match credentials.authenticate().await? {
             Some(filters) => match data {
-                Some(data) => Ok(Self { data, filters, _marker: PhantomData }),
+                Some(data) => Ok(Self { data, filters, credentials, _marker: PhantomData }),
                 None => Err(AuthenticationError::IrretrievableState.into()),
             },
             None if missing_master_key => Err(AuthenticationError::MissingMasterKey.into()),
@@ -48,26 +54,36 @@ impl<P, D> GuardedData<P, D> {
         P: Policy + 'static,
     {
         let missing_master_key = auth.get_master_key().is_none();
+        let credentials =
+            Credentials { auth, token: String::new(), index: None, policy: P::authenticate };
 
-        match Self::authenticate(auth, String::new(), None).await? {
+        match credentials.authenticate().await? {
             Some(filters) => match data {
-                Some(data) => Ok(Self { data, filters, _marker: PhantomData }),
+                Some(data) => Ok(Self { data, filters, credentials, _marker: PhantomData }),
                 None => Err(AuthenticationError::IrretrievableState.into()),
             },
             None if missing_master_key => Err(AuthenticationError::MissingMasterKey.into()),
             None => Err(AuthenticationError::MissingAuthorizationHeader.into()),
         }
     }
+}
 
-    async fn authenticate(
-        auth: AuthController,
-        token: String,
-        index: Option<String>,
-    ) -> Result<Option<AuthFilter>, ResponseError>
-    where
-        P: Policy + 'static,
-    {
-        tokio::task::spawn_blocking(move || P::authenticate(auth, token.as_ref(), index.as_deref()))
+/// The credentials a request was authenticated with, along with the policy they were checked against.
+#[derive(Clone)]
+pub struct Credentials {
+    auth: AuthController,
+    token: String,
+    index: Option<String>,
+    policy: fn(AuthController, &str, Option<&str>) -> Option<AuthFilter>,
+}
+
+impl Credentials {
+    /// Authenticates the credentials against the policy and returns their filters, or `None`
+    /// if they are not allowed anymore. A response that outlives its request uses it to
+    /// check that the key was not deleted and did not expire in the meantime.
+    pub async fn authenticate(&self) -> Result<Option<AuthFilter>, ResponseError> {
+        let Credentials { auth, token, index, policy } = self.clone();
+        tokio::task::spawn_blocking(move || policy(auth, &token, index.as_deref()))
             .await
             .map_err(|e| ResponseError::from_msg(e.to_string(), Code::Internal))
     }
@@ -41,7 +41,7 @@ use meilisearch_types::tasks::KindWithContent;
 use meilisearch_types::versioning::{check_version_file, create_version_file};
 use meilisearch_types::{compression, milli, VERSION_FILE_NAME};
 pub use option::Opt;
-use option::ScheduleSnapshot;
+use option::{ScheduleSnapshot, INDEX_GROWTH_AMOUNT};
 
 use crate::error::MeilisearchHttpError;
 
@@ -206,8 +206,12 @@ fn open_or_create_database_unchecked(
             dumps_path: opt.dump_dir.clone(),
             task_db_size: opt.max_task_db_size.get_bytes() as usize,
             index_size: opt.max_index_size.get_bytes() as usize,
+            index_growth_amount: INDEX_GROWTH_AMOUNT as usize,
+            index_count: opt.max_opened_indexes,
             indexer_config: (&opt.indexer_options).try_into()?,
             autobatching_enabled: true,
+            webhook_url: opt.task_webhook_url.clone(),
+            webhook_authorization_header: opt.task_webhook_authorization_header.clone(),
         })?)
     };
 
@@ -345,6 +349,11 @@ fn import_dump(
         let (task, file) = ret?;
         index_scheduler.register_dumped_task(task, file)?;
     }
+
+    // 5. Import the batches.
+    for batch in dump_reader.batches()? {
+        index_scheduler.register_dumped_batch(batch?)?;
+    }
     Ok(())
 }
 
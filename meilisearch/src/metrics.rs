@@ -1,7 +1,8 @@
 use lazy_static::lazy_static;
 use prometheus::{
-    opts, register_histogram_vec, register_int_counter_vec, register_int_gauge,
-    register_int_gauge_vec, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
+    opts, register_histogram_vec, register_int_counter, register_int_counter_vec,
+    register_int_gauge, register_int_gauge_vec, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec,
 };
 
 const HTTP_RESPONSE_TIME_CUSTOM_BUCKETS: &[f64; 14] = &[
@@ -33,4 +34,24 @@ lazy_static! {
         HTTP_RESPONSE_TIME_CUSTOM_BUCKETS.to_vec()
     )
     .expect("Can't create a metric");
+    pub static ref MEILISEARCH_INDEXING_STEP: IntGauge = register_int_gauge!(opts!(
+        "meilisearch_indexing_step",
+        "Meilisearch Current Indexing Step, 0 When Not Indexing"
+    ))
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_INDEXING_PROCESSED: IntGauge = register_int_gauge!(opts!(
+        "meilisearch_indexing_processed",
+        "Meilisearch Documents Or Databases Processed During The Current Indexing Step"
+    ))
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_INDEXING_TOTAL: IntGauge = register_int_gauge!(opts!(
+        "meilisearch_indexing_total",
+        "Meilisearch Documents Or Databases To Process During The Current Indexing Step"
+    ))
+    .expect("Can't create a metric");
+    pub static ref MEILISEARCH_DEGRADED_SEARCH_REQUESTS: IntCounter = register_int_counter!(opts!(
+        "meilisearch_degraded_search_requests",
+        "Meilisearch number of degraded search requests"
+    ))
+    .expect("Can't create a metric");
 }
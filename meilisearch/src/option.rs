@@ -30,6 +30,7 @@ const MEILI_ENV: &str = "MEILI_ENV";
 #[cfg(all(not(debug_assertions), feature = "analytics"))]
 const MEILI_NO_ANALYTICS: &str = "MEILI_NO_ANALYTICS";
 const MEILI_HTTP_PAYLOAD_SIZE_LIMIT: &str = "MEILI_HTTP_PAYLOAD_SIZE_LIMIT";
+const MEILI_MAX_OPENED_INDEXES: &str = "MEILI_MAX_OPENED_INDEXES";
 const MEILI_SSL_CERT_PATH: &str = "MEILI_SSL_CERT_PATH";
 const MEILI_SSL_KEY_PATH: &str = "MEILI_SSL_KEY_PATH";
 const MEILI_SSL_AUTH_PATH: &str = "MEILI_SSL_AUTH_PATH";
@@ -47,6 +48,8 @@ const MEILI_IGNORE_MISSING_DUMP: &str = "MEILI_IGNORE_MISSING_DUMP";
 const MEILI_IGNORE_DUMP_IF_DB_EXISTS: &str = "MEILI_IGNORE_DUMP_IF_DB_EXISTS";
 const MEILI_DUMP_DIR: &str = "MEILI_DUMP_DIR";
 const MEILI_LOG_LEVEL: &str = "MEILI_LOG_LEVEL";
+const MEILI_TASK_WEBHOOK_URL: &str = "MEILI_TASK_WEBHOOK_URL";
+const MEILI_TASK_WEBHOOK_AUTHORIZATION_HEADER: &str = "MEILI_TASK_WEBHOOK_AUTHORIZATION_HEADER";
 #[cfg(feature = "metrics")]
 const MEILI_ENABLE_METRICS_ROUTE: &str = "MEILI_ENABLE_METRICS_ROUTE";
 
@@ -67,8 +70,11 @@ const DEFAULT_LOG_EVERY_N: usize = 100_000;
 // Each environment (index and task-db) is taking space in the virtual address space.
 //
 // The size of the virtual address space is limited by the OS. About 100TB for Linux and about 10TB for Windows.
-// This means that the number of indexes is limited to about 200 for Linux and about 20 for Windows.
+// This is why, by default, at most `INDEX_COUNT` indexes are opened at the same time, the least
+// recently used ones being closed, and why a full index only grows its map size by `INDEX_GROWTH_AMOUNT`.
 pub const INDEX_SIZE: u64 = 536_870_912_000; // 500 GiB
+pub const INDEX_GROWTH_AMOUNT: u64 = 10_737_418_240; // 10 GiB
+pub const INDEX_COUNT: usize = 20;
 pub const TASK_DB_SIZE: u64 = 10_737_418_240; // 10 GiB
 
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
@@ -179,6 +185,12 @@ pub struct Opt {
     #[serde(default = "default_http_payload_size_limit")]
     pub http_payload_size_limit: Byte,
 
+    /// Sets the maximum number of indexes opened at the same time. When this limit is reached,
+    /// the least recently used indexes are closed and reopened the next time they are accessed.
+    #[clap(long, env = MEILI_MAX_OPENED_INDEXES, default_value_t = default_max_opened_indexes(), value_parser = parse_max_opened_indexes)]
+    #[serde(default = "default_max_opened_indexes")]
+    pub max_opened_indexes: usize,
+
     /// Sets the server's SSL certificates.
     #[clap(long, env = MEILI_SSL_CERT_PATH, value_parser)]
     pub ssl_cert_path: Option<PathBuf>,
@@ -287,6 +299,16 @@ pub struct Opt {
     #[serde(default)]
     pub log_level: LogLevel,
 
+    /// Sets the URL Meilisearch notifies, with a POST request, each time a batch of tasks finishes.
+    ///
+    /// The finished tasks are sent as newline-delimited JSON.
+    #[clap(long, env = MEILI_TASK_WEBHOOK_URL)]
+    pub task_webhook_url: Option<String>,
+
+    /// Sets the value of the `Authorization` header sent along with the webhook notifications.
+    #[clap(long, env = MEILI_TASK_WEBHOOK_AUTHORIZATION_HEADER, requires = "task_webhook_url")]
+    pub task_webhook_authorization_header: Option<String>,
+
     /// Enables Prometheus metrics and /metrics route.
     #[cfg(feature = "metrics")]
     #[clap(long, env = MEILI_ENABLE_METRICS_ROUTE)]
@@ -363,6 +385,7 @@ impl Opt {
             max_index_size: _,
             max_task_db_size: _,
             http_payload_size_limit,
+            max_opened_indexes,
             ssl_cert_path,
             ssl_key_path,
             ssl_auth_path,
@@ -374,6 +397,8 @@ impl Opt {
             schedule_snapshot,
             dump_dir,
             log_level,
+            task_webhook_url,
+            task_webhook_authorization_header,
             indexer_options,
             import_snapshot: _,
             ignore_missing_snapshot: _,
@@ -401,6 +426,7 @@ impl Opt {
             MEILI_HTTP_PAYLOAD_SIZE_LIMIT,
             http_payload_size_limit.to_string(),
         );
+        export_to_env_if_not_present(MEILI_MAX_OPENED_INDEXES, max_opened_indexes.to_string());
         if let Some(ssl_cert_path) = ssl_cert_path {
             export_to_env_if_not_present(MEILI_SSL_CERT_PATH, ssl_cert_path);
         }
@@ -423,6 +449,15 @@ impl Opt {
 
         export_to_env_if_not_present(MEILI_DUMP_DIR, dump_dir);
         export_to_env_if_not_present(MEILI_LOG_LEVEL, log_level.to_string());
+        if let Some(task_webhook_url) = task_webhook_url {
+            export_to_env_if_not_present(MEILI_TASK_WEBHOOK_URL, task_webhook_url);
+        }
+        if let Some(task_webhook_authorization_header) = task_webhook_authorization_header {
+            export_to_env_if_not_present(
+                MEILI_TASK_WEBHOOK_AUTHORIZATION_HEADER,
+                task_webhook_authorization_header,
+            );
+        }
         #[cfg(feature = "metrics")]
         {
             export_to_env_if_not_present(
@@ -704,6 +739,18 @@ fn default_http_payload_size_limit() -> Byte {
     Byte::from_str(DEFAULT_HTTP_PAYLOAD_SIZE_LIMIT).unwrap()
 }
 
+fn default_max_opened_indexes() -> usize {
+    INDEX_COUNT
+}
+
+fn parse_max_opened_indexes(s: &str) -> Result<usize, String> {
+    match s.parse() {
+        Ok(0) => Err(String::from("at least one index must be allowed to be opened")),
+        Ok(max_opened_indexes) => Ok(max_opened_indexes),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
 fn default_snapshot_dir() -> PathBuf {
     PathBuf::from(DEFAULT_SNAPSHOT_DIR)
 }
@@ -822,6 +869,13 @@ mod test {
         assert!(Opt::try_parse_from(Some("")).is_ok());
     }
 
+    #[test]
+    fn test_max_opened_indexes() {
+        let opt = Opt::try_parse_from(["meilisearch", "--max-opened-indexes", "3"]).unwrap();
+        assert_eq!(opt.max_opened_indexes, 3);
+        assert!(Opt::try_parse_from(["meilisearch", "--max-opened-indexes", "0"]).is_err());
+    }
+
     #[test]
     #[ignore]
     fn test_meilli_config_file_path_valid() {
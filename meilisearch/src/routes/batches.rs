@@ -0,0 +1,167 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::DeserializeFromValue;
+use index_scheduler::{IndexScheduler, Query};
+use meilisearch_types::batch_view::BatchView;
+use meilisearch_types::batches::BatchId;
+use meilisearch_types::deserr::query_params::Param;
+use meilisearch_types::deserr::DeserrQueryParamError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::{InvalidTaskDateError, ResponseError};
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::star_or::{OptionStarOr, OptionStarOrList};
+use meilisearch_types::tasks::{Kind, Status};
+use serde::Serialize;
+use serde_json::json;
+use time::OffsetDateTime;
+
+use super::tasks::{deserialize_date_after, deserialize_date_before};
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::query_parameters::QueryParameter;
+use crate::extractors::sequential_extractor::SeqHandler;
+
+const DEFAULT_LIMIT: u32 = 20;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::get().to(SeqHandler(get_batches))))
+        .service(web::resource("/{batch_id}").route(web::get().to(SeqHandler(get_batch))));
+}
+
+#[derive(Debug, DeserializeFromValue)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct BatchesFilterQuery {
+    #[deserr(default = Param(DEFAULT_LIMIT), error = DeserrQueryParamError<InvalidBatchLimit>)]
+    pub limit: Param<u32>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidBatchFrom>)]
+    pub from: Option<Param<BatchId>>,
+
+    #[deserr(default, error = DeserrQueryParamError<InvalidBatchUids>)]
+    pub uids: OptionStarOrList<u32>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskTypes>)]
+    pub types: OptionStarOrList<Kind>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskStatuses>)]
+    pub statuses: OptionStarOrList<Status>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidIndexUid>)]
+    pub index_uids: OptionStarOrList<IndexUid>,
+
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskAfterStartedAt>, from(OptionStarOr<String>) = deserialize_date_after -> InvalidTaskDateError)]
+    pub after_started_at: OptionStarOr<OffsetDateTime>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskBeforeStartedAt>, from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
+    pub before_started_at: OptionStarOr<OffsetDateTime>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskAfterFinishedAt>, from(OptionStarOr<String>) = deserialize_date_after -> InvalidTaskDateError)]
+    pub after_finished_at: OptionStarOr<OffsetDateTime>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskBeforeFinishedAt>, from(OptionStarOr<String>) = deserialize_date_before -> InvalidTaskDateError)]
+    pub before_finished_at: OptionStarOr<OffsetDateTime>,
+}
+impl BatchesFilterQuery {
+    fn into_query(self) -> Query {
+        Query {
+            limit: Some(self.limit.0),
+            from: self.from.as_deref().copied(),
+            statuses: self.statuses.merge_star_and_none(),
+            types: self.types.merge_star_and_none(),
+            index_uids: self.index_uids.map(|x| x.to_string()).merge_star_and_none(),
+            uids: self.uids.merge_star_and_none(),
+            before_started_at: self.before_started_at.merge_star_and_none(),
+            after_started_at: self.after_started_at.merge_star_and_none(),
+            before_finished_at: self.before_finished_at.merge_star_and_none(),
+            after_finished_at: self.after_finished_at.merge_star_and_none(),
+            ..Query::default()
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AllBatches {
+    results: Vec<BatchView>,
+    limit: u32,
+    from: Option<u32>,
+    next: Option<u32>,
+}
+
+async fn get_batches(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    params: QueryParameter<BatchesFilterQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let mut params = params.into_inner();
+    analytics.publish(
+        "Batches Seen".to_string(),
+        json!({
+            "filtered_by_uid": params.uids.is_some(),
+            "filtered_by_type": params.types.is_some(),
+            "filtered_by_status": params.statuses.is_some(),
+            "filtered_by_index_uid": params.index_uids.is_some(),
+            "filtered_by_after_started_at": params.after_started_at.is_some(),
+            "filtered_by_before_started_at": params.before_started_at.is_some(),
+            "filtered_by_after_finished_at": params.after_finished_at.is_some(),
+            "filtered_by_before_finished_at": params.before_finished_at.is_some(),
+        }),
+        Some(&req),
+    );
+
+    // We +1 just to know if there is more after this "page" or not.
+    params.limit.0 = params.limit.0.saturating_add(1);
+    let limit = params.limit.0;
+    let query = params.into_query();
+
+    let mut batches_results: Vec<BatchView> = index_scheduler
+        .get_batches_from_authorized_indexes(
+            query,
+            index_scheduler.filters().search_rules.authorized_indexes(),
+        )?
+        .iter()
+        .map(BatchView::from_batch)
+        .collect();
+
+    // If we were able to fetch the number +1 batches we asked
+    // it means that there is more to come.
+    let next = if batches_results.len() == limit as usize {
+        batches_results.pop().map(|b| b.uid)
+    } else {
+        None
+    };
+
+    let from = batches_results.first().map(|b| b.uid);
+
+    let batches =
+        AllBatches { results: batches_results, limit: limit.saturating_sub(1), from, next };
+    Ok(HttpResponse::Ok().json(batches))
+}
+
+async fn get_batch(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    batch_uid: web::Path<String>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let batch_uid_string = batch_uid.into_inner();
+
+    let batch_uid: BatchId = match batch_uid_string.parse() {
+        Ok(id) => id,
+        Err(_e) => {
+            return Err(
+                index_scheduler::Error::InvalidBatchUids { batch_uid: batch_uid_string }.into()
+            )
+        }
+    };
+
+    analytics.publish("Batches Seen".to_string(), json!({ "per_batch_uid": true }), Some(&req));
+
+    let query = index_scheduler::Query { uids: Some(vec![batch_uid]), ..Query::default() };
+
+    if let Some(batch) = index_scheduler
+        .get_batches_from_authorized_indexes(
+            query,
+            index_scheduler.filters().search_rules.authorized_indexes(),
+        )?
+        .first()
+    {
+        Ok(HttpResponse::Ok().json(BatchView::from_batch(batch)))
+    } else {
+        Err(index_scheduler::Error::BatchNotFound(batch_uid).into())
+    }
+}
@@ -1,4 +1,5 @@
 use std::io::ErrorKind;
+use std::str::FromStr;
 
 use actix_web::http::header::CONTENT_TYPE;
 use actix_web::web::Data;
@@ -12,10 +13,12 @@ use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::document_formats::{read_csv, read_json, read_ndjson, PayloadType};
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
+use meilisearch_types::milli::{AscDesc, DocumentId};
+use meilisearch_types::serde_cs::vec::CS;
 use meilisearch_types::star_or::OptionStarOrList;
 use meilisearch_types::tasks::KindWithContent;
 use meilisearch_types::{milli, Document, Index};
@@ -32,6 +35,7 @@ use crate::error::MeilisearchHttpError;
 use crate::error::PayloadError::ReceivePayload;
 use crate::extractors::authentication::policies::*;
 use crate::extractors::authentication::GuardedData;
+use crate::extractors::json::ValidatedJson;
 use crate::extractors::payload::Payload;
 use crate::extractors::query_parameters::QueryParameter;
 use crate::extractors::sequential_extractor::SeqHandler;
@@ -73,6 +77,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
     )
     // this route needs to be before the /documents/{document_id} to match properly
     .service(web::resource("/delete-batch").route(web::post().to(SeqHandler(delete_documents))))
+    .service(web::resource("/delete").route(web::post().to(SeqHandler(delete_documents_by_filter))))
+    .service(web::resource("/fetch").route(web::post().to(SeqHandler(documents_by_query_post))))
     .service(
         web::resource("/{document_id}")
             .route(web::get().to(SeqHandler(get_document)))
@@ -127,29 +133,84 @@ pub async fn delete_document(
 
 #[derive(Debug, DeserializeFromValue)]
 #[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
-pub struct BrowseQuery {
+pub struct BrowseQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidDocumentOffset>)]
     offset: Param<usize>,
     #[deserr(default = Param(PAGINATION_DEFAULT_LIMIT), error = DeserrQueryParamError<InvalidDocumentLimit>)]
     limit: Param<usize>,
     #[deserr(default, error = DeserrQueryParamError<InvalidDocumentFields>)]
     fields: OptionStarOrList<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentFilter>)]
+    filter: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentSort>)]
+    sort: Option<CS<String>>,
+}
+
+#[derive(Debug, DeserializeFromValue)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct BrowseQuery {
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentOffset>)]
+    offset: usize,
+    #[deserr(default = PAGINATION_DEFAULT_LIMIT, error = DeserrJsonError<InvalidDocumentLimit>)]
+    limit: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentFields>)]
+    fields: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
+    filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentSort>)]
+    sort: Option<Vec<String>>,
+}
+
+impl From<BrowseQueryGet> for BrowseQuery {
+    fn from(other: BrowseQueryGet) -> Self {
+        let filter = match other.filter {
+            Some(f) => match serde_json::from_str(&f) {
+                Ok(v) => Some(v),
+                _ => Some(Value::String(f)),
+            },
+            None => None,
+        };
+
+        Self {
+            offset: other.offset.0,
+            limit: other.limit.0,
+            fields: other.fields.merge_star_and_none(),
+            filter,
+            sort: other.sort.map(|sort| sort.into_iter().collect()),
+        }
+    }
 }
 
 pub async fn get_all_documents(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
-    params: QueryParameter<BrowseQuery, DeserrQueryParamError>,
+    params: QueryParameter<BrowseQueryGet, DeserrQueryParamError>,
 ) -> Result<HttpResponse, ResponseError> {
-    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
     debug!("called with params: {:?}", params);
-    let BrowseQuery { limit, offset, fields } = params.into_inner();
-    let attributes_to_retrieve = fields.merge_star_and_none();
+    documents_by_query(&index_scheduler, index_uid, params.into_inner().into())
+}
+
+pub async fn documents_by_query_post(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_GET }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: ValidatedJson<BrowseQuery, DeserrJsonError>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with body: {:?}", body);
+    documents_by_query(&index_scheduler, index_uid, body.into_inner())
+}
+
+fn documents_by_query(
+    index_scheduler: &IndexScheduler,
+    index_uid: web::Path<String>,
+    query: BrowseQuery,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let BrowseQuery { offset, limit, fields, filter, sort } = query;
 
     let index = index_scheduler.index(&index_uid)?;
-    let (total, documents) = retrieve_documents(&index, offset.0, limit.0, attributes_to_retrieve)?;
+    let (total, documents) = retrieve_documents(&index, offset, limit, filter, sort, fields)?;
 
-    let ret = PaginationView::new(offset.0, limit.0, total as usize, documents);
+    let ret = PaginationView::new(offset, limit, total as usize, documents);
 
     debug!("returns: {:?}", ret);
     Ok(HttpResponse::Ok().json(ret))
@@ -362,6 +423,46 @@ pub async fn delete_documents(
     Ok(HttpResponse::Accepted().json(task))
 }
 
+#[derive(Debug, DeserializeFromValue)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct DocumentDeletionByFilter {
+    #[deserr(error = DeserrJsonError<InvalidDocumentFilter>, missing_field_error = DeserrJsonError::missing_document_filter)]
+    filter: Value,
+}
+
+pub async fn delete_documents_by_filter(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    body: ValidatedJson<DocumentDeletionByFilter, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    debug!("called with params: {:?}", body);
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+    let index_uid = index_uid.into_inner();
+    let filter = body.into_inner().filter;
+
+    analytics.delete_documents(DocumentDeletionKind::PerFilter, &req);
+
+    // we ensure the filter is well formed before enqueuing it
+    match milli::Filter::from_json(&filter) {
+        Ok(Some(_)) => (),
+        Ok(None) => return Err(MeilisearchHttpError::EmptyFilter.into()),
+        // whatever the error was, it must be reported as an invalid document filter
+        Err(err) => {
+            return Err(ResponseError::from_msg(err.to_string(), Code::InvalidDocumentFilter))
+        }
+    }
+
+    let task = KindWithContent::DocumentDeletionByFilter { index_uid, filter_expr: filter };
+
+    let task: SummarizedTaskView =
+        tokio::task::spawn_blocking(move || index_scheduler.register(task)).await??.into();
+
+    debug!("returns: {:?}", task);
+    Ok(HttpResponse::Accepted().json(task))
+}
+
 pub async fn clear_all_documents(
     index_scheduler: GuardedData<ActionPolicy<{ actions::DOCUMENTS_DELETE }>, Data<IndexScheduler>>,
     index_uid: web::Path<String>,
@@ -393,16 +494,87 @@ fn all_documents<'a>(
     }))
 }
 
+fn some_documents<'a>(
+    index: &Index,
+    rtxn: &'a RoTxn,
+    doc_ids: impl IntoIterator<Item = DocumentId>,
+) -> Result<impl Iterator<Item = Result<Document, ResponseError>> + 'a, ResponseError> {
+    let fields_ids_map = index.fields_ids_map(rtxn)?;
+    let all_fields: Vec<_> = fields_ids_map.iter().map(|(id, _)| id).collect();
+
+    Ok(index.documents(rtxn, doc_ids)?.into_iter().map(move |(_id, document)| {
+        Ok(milli::obkv_to_json(&all_fields, &fields_ids_map, document)?)
+    }))
+}
+
 fn retrieve_documents<S: AsRef<str>>(
     index: &Index,
     offset: usize,
     limit: usize,
+    filter: Option<Value>,
+    sort: Option<Vec<String>>,
     attributes_to_retrieve: Option<Vec<S>>,
 ) -> Result<(u64, Vec<Document>), ResponseError> {
     let rtxn = index.read_txn()?;
 
-    let mut documents = Vec::new();
-    for document in all_documents(index, &rtxn)?.skip(offset).take(limit) {
+    // whatever the error is, it must be reported as an invalid document filter or sort
+    let invalid_filter = |err: milli::Error| match err {
+        milli::Error::UserError(milli::UserError::InvalidFilter(_))
+        | milli::Error::UserError(milli::UserError::InvalidFilterExpression(..)) => {
+            ResponseError::from_msg(err.to_string(), Code::InvalidDocumentFilter)
+        }
+        e => e.into(),
+    };
+    let invalid_sort = |err: milli::Error| match err {
+        milli::Error::UserError(milli::UserError::InvalidSortableAttribute { .. })
+        | milli::Error::UserError(milli::UserError::SortError(_)) => {
+            ResponseError::from_msg(err.to_string(), Code::InvalidDocumentSort)
+        }
+        e => e.into(),
+    };
+
+    let filter = match &filter {
+        Some(filter) => milli::Filter::from_json(filter).map_err(invalid_filter)?,
+        None => None,
+    };
+    let sort = match sort {
+        Some(sort) => {
+            let sort = sort
+                .iter()
+                .map(|s| AscDesc::from_str(s))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| invalid_sort(milli::SortError::from(err).into()))?;
+            Some(sort)
+        }
+        None => None,
+    };
+
+    let (total, documents): (u64, Box<dyn Iterator<Item = Result<Document, ResponseError>>>) =
+        match (filter, sort) {
+            // without any filter nor sort we can directly iterate over the documents
+            (None, None) => (
+                index.number_of_documents(&rtxn)?,
+                Box::new(all_documents(index, &rtxn)?.skip(offset).take(limit)),
+            ),
+            (filter, sort) => {
+                let candidates = match filter {
+                    Some(filter) => filter.evaluate(&rtxn, index).map_err(invalid_filter)?,
+                    None => index.documents_ids(&rtxn)?,
+                };
+                let total = candidates.len();
+                let doc_ids = match sort {
+                    Some(sort) => {
+                        milli::sort_documents_ids(index, &rtxn, &sort, candidates, offset, limit)
+                            .map_err(invalid_sort)?
+                    }
+                    None => candidates.into_iter().skip(offset).take(limit).collect(),
+                };
+                (total, Box::new(some_documents(index, &rtxn, doc_ids)?))
+            }
+        };
+
+    let mut results = Vec::new();
+    for document in documents {
         let document = match &attributes_to_retrieve {
             Some(attributes_to_retrieve) => permissive_json_pointer::select_values(
                 &document?,
@@ -410,11 +582,10 @@ fn retrieve_documents<S: AsRef<str>>(
             ),
             None => document?,
         };
-        documents.push(document);
+        results.push(document);
     }
 
-    let number_of_documents = index.number_of_documents(&rtxn)?;
-    Ok((number_of_documents, documents))
+    Ok((total, results))
 }
 
 fn retrieve_document<S: AsRef<str>>(
@@ -26,6 +26,7 @@ use crate::extractors::query_parameters::QueryParameter;
 use crate::extractors::sequential_extractor::SeqHandler;
 
 pub mod documents;
+pub mod facet_search;
 pub mod search;
 pub mod settings;
 
@@ -46,6 +47,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .service(web::resource("/stats").route(web::get().to(SeqHandler(get_index_stats))))
             .service(web::scope("/documents").configure(documents::configure))
             .service(web::scope("/search").configure(search::configure))
+            .service(web::scope("/facet-search").configure(facet_search::configure))
             .service(web::scope("/settings").configure(settings::configure)),
     );
 }
@@ -92,12 +94,13 @@ pub async fn list_indexes(
     paginate: QueryParameter<ListIndexes, DeserrQueryParamError>,
 ) -> Result<HttpResponse, ResponseError> {
     let search_rules = &index_scheduler.filters().search_rules;
-    let indexes: Vec<_> = index_scheduler.indexes()?;
-    let indexes = indexes
-        .into_iter()
-        .filter(|(name, _)| search_rules.is_index_authorized(name))
-        .map(|(name, index)| IndexView::new(name, &index))
-        .collect::<Result<Vec<_>, _>>()?;
+    let indexes: Vec<Option<IndexView>> = index_scheduler.try_for_each_index(|uid, index| {
+        if !search_rules.is_index_authorized(uid) {
+            return Ok(None);
+        }
+        Ok(Some(IndexView::new(uid.to_string(), index)?))
+    })?;
+    let indexes = indexes.into_iter().flatten().collect::<Vec<_>>();
 
     let ret = paginate.as_pagination().auto_paginate_sized(indexes.into_iter());
 
@@ -5,7 +5,9 @@ use log::debug;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::ResponseError;
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::settings::{settings, RankingRuleView, Settings, Unchecked};
+use meilisearch_types::settings::{
+    settings, FacetValuesSort, RankingRuleView, Settings, Unchecked,
+};
 use meilisearch_types::tasks::KindWithContent;
 use serde_json::json;
 
@@ -307,6 +309,106 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/non-separator-tokens",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsNonSeparatorTokens,
+    >,
+    non_separator_tokens,
+    "nonSeparatorTokens",
+    analytics,
+    |non_separator_tokens: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "NonSeparatorTokens Updated".to_string(),
+            json!({
+                "non_separator_tokens": {
+                    "total": non_separator_tokens.as_ref().map(|non_separator_tokens| non_separator_tokens.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/separator-tokens",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSeparatorTokens,
+    >,
+    separator_tokens,
+    "separatorTokens",
+    analytics,
+    |separator_tokens: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "SeparatorTokens Updated".to_string(),
+            json!({
+                "separator_tokens": {
+                    "total": separator_tokens.as_ref().map(|separator_tokens| separator_tokens.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/dictionary",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsDictionary,
+    >,
+    dictionary,
+    "dictionary",
+    analytics,
+    |dictionary: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Dictionary Updated".to_string(),
+            json!({
+                "dictionary": {
+                    "total": dictionary.as_ref().map(|dictionary| dictionary.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/localized-attributes",
+    put,
+    Vec<meilisearch_types::milli::LocalizedAttributesRule>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsLocalizedAttributes,
+    >,
+    localized_attributes,
+    "localizedAttributes",
+    analytics,
+    |rules: &Option<Vec<meilisearch_types::milli::LocalizedAttributesRule>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "LocalizedAttributesRules Updated".to_string(),
+            json!({
+                "localized_attributes": {
+                    "total": rules.as_ref().map(|rules| rules.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/synonyms",
     put,
@@ -356,6 +458,31 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/proximity-precision",
+    put,
+    meilisearch_types::settings::ProximityPrecisionView,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsProximityPrecision,
+    >,
+    proximity_precision,
+    "proximityPrecision",
+    analytics,
+    |precision: &Option<meilisearch_types::settings::ProximityPrecisionView>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "ProximityPrecision Updated".to_string(),
+            json!({
+                "proximity_precision": {
+                    "set": precision.is_some(),
+                    "value": precision,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/ranking-rules",
     put,
@@ -405,6 +532,10 @@ make_setting_route!(
             json!({
                 "faceting": {
                     "max_values_per_facet": setting.as_ref().and_then(|s| s.max_values_per_facet.set()),
+                    "sort_facet_values_by_star_count": setting.as_ref().and_then(|s| {
+                        s.sort_facet_values_by.as_ref().set().map(|s| s.iter().any(|(k, v)| k == "*" && v == &meilisearch_types::settings::FacetValuesSort::Count))
+                    }),
+                    "sort_facet_values_by_total": setting.as_ref().and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
                 },
             }),
             Some(req),
@@ -437,6 +568,56 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/search-cutoff-ms",
+    put,
+    u64,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsSearchCutoffMs,
+    >,
+    search_cutoff_ms,
+    "searchCutoffMs",
+    analytics,
+    |setting: &Option<u64>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "Search Cutoff Updated".to_string(),
+            json!({
+                "search_cutoff_ms": {
+                    "search_cutoff_ms": setting,
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/prefix-search",
+    put,
+    meilisearch_types::settings::PrefixSearchView,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsPrefixSearch,
+    >,
+    prefix_search,
+    "prefixSearch",
+    analytics,
+    |prefix_search: &Option<meilisearch_types::settings::PrefixSearchView>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "PrefixSearch Updated".to_string(),
+            json!({
+                "prefix_search": {
+                    "set": prefix_search.is_some(),
+                    "value": prefix_search,
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
 macro_rules! generate_configure {
     ($($mod:ident),*) => {
         pub fn configure(cfg: &mut web::ServiceConfig) {
@@ -457,12 +638,19 @@ generate_configure!(
     displayed_attributes,
     searchable_attributes,
     distinct_attribute,
+    proximity_precision,
     stop_words,
+    non_separator_tokens,
+    separator_tokens,
+    dictionary,
+    localized_attributes,
     synonyms,
     ranking_rules,
     typo_tolerance,
     pagination,
-    faceting
+    faceting,
+    search_cutoff_ms,
+    prefix_search
 );
 
 pub async fn update_all(
@@ -507,6 +695,10 @@ pub async fn update_all(
             "distinct_attribute": {
                 "set": new_settings.distinct_attribute.as_ref().set().is_some()
             },
+            "proximity_precision": {
+                "set": new_settings.proximity_precision.as_ref().set().is_some(),
+                "value": new_settings.proximity_precision.as_ref().set(),
+            },
             "typo_tolerance": {
                 "enabled": new_settings.typo_tolerance
                     .as_ref()
@@ -543,6 +735,16 @@ pub async fn update_all(
                     .as_ref()
                     .set()
                     .and_then(|s| s.max_values_per_facet.as_ref().set()),
+                "sort_facet_values_by_star_count": new_settings.faceting
+                    .as_ref()
+                    .set()
+                    .and_then(|s| {
+                        s.sort_facet_values_by.as_ref().set().map(|s| s.iter().any(|(k, v)| k == "*" && v == &FacetValuesSort::Count))
+                    }),
+                "sort_facet_values_by_total": new_settings.faceting
+                    .as_ref()
+                    .set()
+                    .and_then(|s| s.sort_facet_values_by.as_ref().set().map(|s| s.len())),
             },
             "pagination": {
                 "max_total_hits": new_settings.pagination
@@ -550,9 +752,28 @@ pub async fn update_all(
                     .set()
                     .and_then(|s| s.max_total_hits.as_ref().set()),
             },
+            "search_cutoff_ms": {
+                "search_cutoff_ms": new_settings.search_cutoff_ms.as_ref().set(),
+            },
+            "prefix_search": {
+                "set": new_settings.prefix_search.as_ref().set().is_some(),
+                "value": new_settings.prefix_search.as_ref().set(),
+            },
             "stop_words": {
                 "total": new_settings.stop_words.as_ref().set().map(|stop_words| stop_words.len()),
             },
+            "non_separator_tokens": {
+                "total": new_settings.non_separator_tokens.as_ref().set().map(|non_separator_tokens| non_separator_tokens.len()),
+            },
+            "separator_tokens": {
+                "total": new_settings.separator_tokens.as_ref().set().map(|separator_tokens| separator_tokens.len()),
+            },
+            "dictionary": {
+                "total": new_settings.dictionary.as_ref().set().map(|dictionary| dictionary.len()),
+            },
+            "localized_attributes": {
+                "total": new_settings.localized_attributes.as_ref().set().map(|rules| rules.len()),
+            },
             "synonyms": {
                 "total": new_settings.synonyms.as_ref().set().map(|synonyms| synonyms.len()),
             },
@@ -0,0 +1,192 @@
+use std::time::Instant;
+
+use actix_web::http::StatusCode;
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::DeserializeFromValue;
+use index_scheduler::IndexScheduler;
+use log::debug;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::ResponseError;
+use serde::Serialize;
+
+use crate::analytics::{Analytics, MultiSearchAggregator};
+use crate::error::MeilisearchHttpError;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::{AuthenticationError, GuardedData};
+use crate::extractors::json::ValidatedJson;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::routes::indexes::search::add_search_rules;
+use crate::search::{
+    merge_federated_results, perform_search, FederatedQueryResult, Federation,
+    SearchQueryWithIndex, SearchResultWithIndex,
+};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(multi_search_with_post))));
+}
+
+#[derive(Serialize)]
+struct SearchResults {
+    results: Vec<SearchResultWithIndex>,
+}
+
+#[derive(Debug, DeserializeFromValue)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchQueries {
+    queries: Vec<SearchQueryWithIndex>,
+    #[deserr(default)]
+    federation: Option<Federation>,
+}
+
+pub async fn multi_search_with_post(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    params: ValidatedJson<SearchQueries, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let before_search = Instant::now();
+    let SearchQueries { queries, federation } = params.into_inner();
+
+    let mut multi_aggregate = MultiSearchAggregator::from_queries(&queries, &federation, &req);
+
+    // Explicitly expect a `(ResponseError, usize)` for the error type rather than `ResponseError` only,
+    // so that `?` doesn't work if it doesn't use `with_index`, ensuring that it is not forgotten in case of code
+    // changes.
+    let search_results: Result<_, (ResponseError, usize)> = async {
+        let mut search_results = Vec::with_capacity(queries.len());
+        for (query_index, query) in queries.into_iter().enumerate() {
+            // The pagination of a federated search is given at the federation level only.
+            if federation.is_some() {
+                let pagination = [
+                    ("offset", query.offset.is_some()),
+                    ("limit", query.limit.is_some()),
+                    ("page", query.page.is_some()),
+                    ("hitsPerPage", query.hits_per_page.is_some()),
+                ];
+                if let Some((name, _)) = pagination.iter().find(|(_, given)| *given) {
+                    return Err(MeilisearchHttpError::PaginationInFederatedQuery(name))
+                        .with_index(query_index);
+                }
+            }
+
+            let (index_uid, mut query, federation_options) = query.into_index_query();
+            debug!("multi-search #{query_index}: called with params: {:?}", query);
+
+            let show_ranking_score = query.show_ranking_score;
+            match &federation {
+                Some(federation) => {
+                    if query.facets.is_some() {
+                        return Err(MeilisearchHttpError::FacetsInFederatedQuery)
+                            .with_index(query_index);
+                    }
+                    // The pagination of the federation is applied on the merged hits,
+                    // so each query must return enough hits to fill the requested page.
+                    query.offset = 0;
+                    query.limit = federation.offset.saturating_add(federation.limit);
+                    // The ranking score is needed to merge the hits of the queries.
+                    query.show_ranking_score = true;
+                }
+                None if federation_options.is_some() => {
+                    return Err(MeilisearchHttpError::FederationOptionsInNonFederatedRequest)
+                        .with_index(query_index);
+                }
+                None => (),
+            }
+
+            let federation_options = federation_options.unwrap_or_default();
+            if federation_options.weight < 0.0 {
+                return Err(MeilisearchHttpError::NegativeFederationWeight(
+                    federation_options.weight,
+                ))
+                .with_index(query_index);
+            }
+
+            // Check index from API key
+            if !index_scheduler.filters().search_rules.is_index_authorized(&index_uid) {
+                return Err(AuthenticationError::InvalidToken).with_index(query_index);
+            }
+            // Apply search rules from tenant token
+            if let Some(search_rules) =
+                index_scheduler.filters().search_rules.get_index_search_rules(&index_uid)
+            {
+                add_search_rules(&mut query, search_rules);
+            }
+
+            let index = index_scheduler
+                .index(&index_uid)
+                .map_err(|err| {
+                    let mut err = ResponseError::from(err);
+                    // Patch the HTTP status code to 400 as it defaults to 404 for `index_not_found`, but
+                    // here the resource not found is not part of the URL.
+                    err.code = StatusCode::BAD_REQUEST;
+                    err
+                })
+                .with_index(query_index)?;
+
+            let search_result = tokio::task::spawn_blocking(move || perform_search(&index, query))
+                .await
+                .with_index(query_index)?;
+
+            search_results.push(FederatedQueryResult {
+                result: SearchResultWithIndex {
+                    index_uid: index_uid.into_inner(),
+                    result: search_result.with_index(query_index)?,
+                },
+                federation_options,
+                show_ranking_score,
+            });
+        }
+        Ok(search_results)
+    }
+    .await;
+
+    if search_results.is_ok() {
+        multi_aggregate.succeed();
+    }
+    analytics.post_multi_search(multi_aggregate);
+
+    let search_results = search_results.map_err(|(mut err, query_index)| {
+        // Add the query index that failed as context for the error message.
+        // We're doing it only here and not directly in the `WithIndex` trait so that the `with_index` function returns a different type
+        // of result and we can benefit from static typing.
+        err.message = format!("Inside `.queries[{query_index}]`: {}", err.message);
+        err
+    })?;
+
+    match federation {
+        Some(federation) => {
+            let search_result = merge_federated_results(
+                search_results,
+                federation,
+                before_search.elapsed().as_millis(),
+            );
+
+            debug!("returns: {:?}", search_result);
+
+            Ok(HttpResponse::Ok().json(search_result))
+        }
+        None => {
+            let search_results: Vec<_> =
+                search_results.into_iter().map(|query_result| query_result.result).collect();
+
+            debug!("returns: {:?}", search_results);
+
+            Ok(HttpResponse::Ok().json(SearchResults { results: search_results }))
+        }
+    }
+}
+
+/// Local `Result` extension trait to avoid `map_err` boilerplate.
+trait WithIndex {
+    type T;
+    /// convert the error type inside of the `Result` to a `ResponseError`, and return a couple of it + the usize.
+    fn with_index(self, index: usize) -> Result<Self::T, (ResponseError, usize)>;
+}
+
+impl<T, E: Into<ResponseError>> WithIndex for Result<T, E> {
+    type T = T;
+    fn with_index(self, index: usize) -> Result<T, (ResponseError, usize)> {
+        self.map_err(|err| (err.into(), index))
+    }
+}
@@ -1,32 +1,39 @@
-use actix_web::web::Data;
+use std::convert::Infallible;
+
+use actix_web::http::header::{CacheControl, CacheDirective, ContentEncoding};
+use actix_web::web::{Bytes, Data};
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::DeserializeFromValue;
+use futures::stream;
 use index_scheduler::{IndexScheduler, Query, TaskId};
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::DeserrQueryParamError;
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{InvalidTaskDateError, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
-use meilisearch_types::settings::{Settings, Unchecked};
 use meilisearch_types::star_or::{OptionStarOr, OptionStarOrList};
-use meilisearch_types::tasks::{
-    serialize_duration, Details, IndexSwap, Kind, KindWithContent, Status, Task,
-};
+use meilisearch_types::task_view::TaskView;
+use meilisearch_types::tasks::{Kind, KindWithContent, Status, Task};
 use serde::Serialize;
 use serde_json::json;
 use time::format_description::well_known::Rfc3339;
 use time::macros::format_description;
 use time::{Date, Duration, OffsetDateTime, Time};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
 use tokio::task;
+use tokio::time::{interval_at, Instant, Interval};
 
 use super::SummarizedTaskView;
 use crate::analytics::Analytics;
 use crate::extractors::authentication::policies::*;
-use crate::extractors::authentication::GuardedData;
+use crate::extractors::authentication::{Credentials, GuardedData};
 use crate::extractors::query_parameters::QueryParameter;
 use crate::extractors::sequential_extractor::SeqHandler;
 
 const DEFAULT_LIMIT: u32 = 20;
+/// The interval at which a comment is sent on the task streams to keep the connections alive.
+const HEARTBEAT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(15);
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -35,133 +42,10 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .route(web::delete().to(SeqHandler(delete_tasks))),
     )
     .service(web::resource("/cancel").route(web::post().to(SeqHandler(cancel_tasks))))
+    .service(web::resource("/stream").route(web::get().to(SeqHandler(get_tasks_stream))))
     .service(web::resource("/{task_id}").route(web::get().to(SeqHandler(get_task))));
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct TaskView {
-    pub uid: TaskId,
-    #[serde(default)]
-    pub index_uid: Option<String>,
-    pub status: Status,
-    #[serde(rename = "type")]
-    pub kind: Kind,
-    pub canceled_by: Option<TaskId>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub details: Option<DetailsView>,
-    pub error: Option<ResponseError>,
-    #[serde(serialize_with = "serialize_duration", default)]
-    pub duration: Option<Duration>,
-    #[serde(with = "time::serde::rfc3339")]
-    pub enqueued_at: OffsetDateTime,
-    #[serde(with = "time::serde::rfc3339::option", default)]
-    pub started_at: Option<OffsetDateTime>,
-    #[serde(with = "time::serde::rfc3339::option", default)]
-    pub finished_at: Option<OffsetDateTime>,
-}
-
-impl TaskView {
-    pub fn from_task(task: &Task) -> TaskView {
-        TaskView {
-            uid: task.uid,
-            index_uid: task.index_uid().map(ToOwned::to_owned),
-            status: task.status,
-            kind: task.kind.as_kind(),
-            canceled_by: task.canceled_by,
-            details: task.details.clone().map(DetailsView::from),
-            error: task.error.clone(),
-            duration: task.started_at.zip(task.finished_at).map(|(start, end)| end - start),
-            enqueued_at: task.enqueued_at,
-            started_at: task.started_at,
-            finished_at: task.finished_at,
-        }
-    }
-}
-
-#[derive(Default, Debug, PartialEq, Eq, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DetailsView {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub received_documents: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub indexed_documents: Option<Option<u64>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub primary_key: Option<Option<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub provided_ids: Option<usize>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub deleted_documents: Option<Option<u64>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub matched_tasks: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub canceled_tasks: Option<Option<u64>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub deleted_tasks: Option<Option<u64>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub original_filter: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub dump_uid: Option<Option<String>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(flatten)]
-    pub settings: Option<Box<Settings<Unchecked>>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub swaps: Option<Vec<IndexSwap>>,
-}
-
-impl From<Details> for DetailsView {
-    fn from(details: Details) -> Self {
-        match details {
-            Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
-                DetailsView {
-                    received_documents: Some(received_documents),
-                    indexed_documents: Some(indexed_documents),
-                    ..DetailsView::default()
-                }
-            }
-            Details::SettingsUpdate { settings } => {
-                DetailsView { settings: Some(settings), ..DetailsView::default() }
-            }
-            Details::IndexInfo { primary_key } => {
-                DetailsView { primary_key: Some(primary_key), ..DetailsView::default() }
-            }
-            Details::DocumentDeletion {
-                provided_ids: received_document_ids,
-                deleted_documents,
-            } => DetailsView {
-                provided_ids: Some(received_document_ids),
-                deleted_documents: Some(deleted_documents),
-                ..DetailsView::default()
-            },
-            Details::ClearAll { deleted_documents } => {
-                DetailsView { deleted_documents: Some(deleted_documents), ..DetailsView::default() }
-            }
-            Details::TaskCancelation { matched_tasks, canceled_tasks, original_filter } => {
-                DetailsView {
-                    matched_tasks: Some(matched_tasks),
-                    canceled_tasks: Some(canceled_tasks),
-                    original_filter: Some(original_filter),
-                    ..DetailsView::default()
-                }
-            }
-            Details::TaskDeletion { matched_tasks, deleted_tasks, original_filter } => {
-                DetailsView {
-                    matched_tasks: Some(matched_tasks),
-                    deleted_tasks: Some(deleted_tasks),
-                    original_filter: Some(original_filter),
-                    ..DetailsView::default()
-                }
-            }
-            Details::Dump { dump_uid } => {
-                DetailsView { dump_uid: Some(dump_uid), ..DetailsView::default() }
-            }
-            Details::IndexSwap { swaps } => {
-                DetailsView { swaps: Some(swaps), ..Default::default() }
-            }
-        }
-    }
-}
-
 #[derive(Debug, DeserializeFromValue)]
 #[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
 pub struct TasksFilterQuery {
@@ -214,6 +98,27 @@ impl TasksFilterQuery {
     }
 }
 
+#[derive(Debug, DeserializeFromValue)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+pub struct TasksStreamQuery {
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskTypes>)]
+    pub types: OptionStarOrList<Kind>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidTaskStatuses>)]
+    pub statuses: OptionStarOrList<Status>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidIndexUid>)]
+    pub index_uids: OptionStarOrList<IndexUid>,
+}
+impl TasksStreamQuery {
+    fn into_query(self) -> Query {
+        Query {
+            statuses: self.statuses.merge_star_and_none(),
+            types: self.types.merge_star_and_none(),
+            index_uids: self.index_uids.map(|x| x.to_string()).merge_star_and_none(),
+            ..Query::default()
+        }
+    }
+}
+
 impl TaskDeletionOrCancelationQuery {
     fn is_empty(&self) -> bool {
         matches!(
@@ -444,13 +349,128 @@ async fn get_task(
         )?
         .first()
     {
-        let task_view = TaskView::from_task(task);
+        let mut task_view = TaskView::from_task(task);
+        if task_view.status == Status::Processing {
+            task_view.progress = index_scheduler.task_indexing_progress(task_uid)?;
+        }
         Ok(HttpResponse::Ok().json(task_view))
     } else {
         Err(index_scheduler::Error::TaskNotFound(task_uid).into())
     }
 }
 
+async fn get_tasks_stream(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::TASKS_GET }>, Data<IndexScheduler>>,
+    params: QueryParameter<TasksStreamQuery, DeserrQueryParamError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let query = params.into_inner().into_query();
+    analytics.publish(
+        "Tasks Stream Opened".to_string(),
+        json!({
+            "filtered_by_type": query.types.is_some(),
+            "filtered_by_status": query.statuses.is_some(),
+            "filtered_by_index_uid": query.index_uids.is_some(),
+        }),
+        Some(&req),
+    );
+
+    let state = TasksStream {
+        receiver: index_scheduler.subscribe_to_task_updates(),
+        heartbeat: interval_at(Instant::now() + HEARTBEAT_INTERVAL, HEARTBEAT_INTERVAL),
+        query,
+        authorized_indexes: index_scheduler.filters().search_rules.authorized_indexes(),
+        credentials: index_scheduler.credentials().clone(),
+    };
+    let events = stream::unfold(state, |mut state| async move {
+        let event = state.next_event().await?;
+        Some((Ok::<_, Infallible>(event), state))
+    });
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(CacheControl(vec![CacheDirective::NoCache]))
+        // compressing the response would buffer the events.
+        .insert_header(ContentEncoding::Identity)
+        .streaming(events))
+}
+
+/// A stream of the updates of the tasks matching a query.
+struct TasksStream {
+    receiver: broadcast::Receiver<Task>,
+    heartbeat: Interval,
+    query: Query,
+    authorized_indexes: Option<Vec<String>>,
+    credentials: Credentials,
+}
+
+impl TasksStream {
+    /// Waits for the next event to send, returns `None` once the stream must be closed.
+    ///
+    /// The credentials of the request are checked again before sending each event, including
+    /// the heartbeats, so that the stream is closed once its key is deleted or expires.
+    async fn next_event(&mut self) -> Option<Bytes> {
+        loop {
+            let event = tokio::select! {
+                _ = self.heartbeat.tick() => Bytes::from_static(b":\n\n"),
+                update = self.receiver.recv() => match update {
+                    Ok(task) if is_task_visible(&self.query, &self.authorized_indexes, &task) => {
+                        match serde_json::to_string(&TaskView::from_task(&task)) {
+                            Ok(task_view) => Bytes::from(format!("data: {}\n\n", task_view)),
+                            Err(e) => {
+                                log::error!("Could not serialize the task {}: {}", task.uid, e);
+                                continue;
+                            }
+                        }
+                    }
+                    Ok(_) => continue,
+                    // the client must fetch the tasks again to know their current status.
+                    Err(RecvError::Lagged(missed)) => {
+                        log::warn!("A task stream missed the update of {} tasks.", missed);
+                        let data = json!({ "missedUpdates": missed });
+                        Bytes::from(format!("event: lagged\ndata: {}\n\n", data))
+                    }
+                    Err(RecvError::Closed) => return None,
+                },
+            };
+
+            return match self.credentials.authenticate().await {
+                Ok(Some(_)) => Some(event),
+                Ok(None) => None,
+                Err(e) => {
+                    log::error!("Could not authenticate a task stream again: {}", e);
+                    None
+                }
+            };
+        }
+    }
+}
+
+/// Return `true` if the task matches the statuses, types and index uids of the query
+/// and the user is allowed to see it, following the rules of `GET /tasks`.
+fn is_task_visible(query: &Query, authorized_indexes: &Option<Vec<String>>, task: &Task) -> bool {
+    let kind = task.kind.as_kind();
+
+    // The tasks that aren't associated to one and only one index are hidden
+    // as soon as the indexes are filtered or restricted.
+    if (query.index_uids.is_some() || authorized_indexes.is_some()) && !kind.related_to_one_index()
+    {
+        return false;
+    }
+    if let Some(authorized_indexes) = authorized_indexes {
+        if task.indexes().iter().any(|index| !authorized_indexes.iter().any(|i| i == index)) {
+            return false;
+        }
+    }
+
+    query.statuses.as_ref().map_or(true, |statuses| statuses.contains(&task.status))
+        && query.types.as_ref().map_or(true, |types| types.contains(&kind))
+        && query.index_uids.as_ref().map_or(true, |index_uids| {
+            task.index_uid().map_or(false, |uid| index_uids.iter().any(|i| i == uid))
+        })
+}
+
 pub enum DeserializeDateOption {
     Before,
     After,
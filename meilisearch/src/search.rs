@@ -1,18 +1,24 @@
 use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::str::FromStr;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use deserr::DeserializeFromValue;
-use either::Either;
+use indexmap::IndexMap;
+use meilisearch_types::deserr::query_params::FromQueryParameter;
 use meilisearch_types::deserr::DeserrJsonError;
 use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::DeserrRankingScoreThresholdError;
+use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
 use meilisearch_types::{milli, Document};
+use milli::heed::RoTxn;
+use milli::score_details::ScoreDetails;
 use milli::tokenizer::TokenizerBuilder;
 use milli::{
-    AscDesc, FieldId, FieldsIdsMap, Filter, FormatOptions, Index, MatchBounds, MatcherBuilder,
-    SortError, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    locales_allow_list, AscDesc, FacetValueHit, FieldId, FieldsIdsMap, Filter, FormatOptions,
+    Index, Locale, MatchBounds, MatcherBuilder, SearchForFacetValues, SortError,
+    TermsMatchingStrategy, TimeBudget, TokenizerSettings, DEFAULT_VALUES_PER_FACET,
 };
 use regex::Regex;
 use serde::Serialize;
@@ -28,12 +34,17 @@ pub const DEFAULT_CROP_LENGTH: fn() -> usize = || 10;
 pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
+pub const DEFAULT_FEDERATION_WEIGHT: fn() -> f64 = || 1.0;
+/// The time a search can take, when neither the query nor the index settings define it.
+pub const DEFAULT_SEARCH_CUTOFF_MS: u64 = 1500;
 
-#[derive(Debug, Clone, Default, PartialEq, Eq, DeserializeFromValue)]
+#[derive(Debug, Clone, Default, PartialEq, DeserializeFromValue)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
 pub struct SearchQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
     pub q: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchVector>)]
+    pub vector: Option<Vec<f32>>,
     #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSearchOffset>)]
     pub offset: usize,
     #[deserr(default = DEFAULT_SEARCH_LIMIT(), error = DeserrJsonError<InvalidSearchLimit>)]
@@ -52,6 +63,10 @@ pub struct SearchQuery {
     pub attributes_to_highlight: Option<HashSet<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchesPosition>, default)]
     pub show_matches_position: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScore>, default)]
+    pub show_ranking_score: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScoreDetails>, default)]
+    pub show_ranking_score_details: bool,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
@@ -66,6 +81,16 @@ pub struct SearchQuery {
     pub crop_marker: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
     pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>)]
+    pub attributes_to_search_on: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>)]
+    pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCutoffMs>)]
+    pub search_cutoff_ms: Option<u64>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
+    pub distinct: Option<String>,
 }
 
 impl SearchQuery {
@@ -74,6 +99,196 @@ impl SearchQuery {
     }
 }
 
+/// A ranking score between `0.0` and `1.0`, below which the hits are not returned.
+#[derive(Debug, Clone, Copy, PartialEq, DeserializeFromValue)]
+#[deserr(from(f64) = RankingScoreThreshold::try_from -> DeserrRankingScoreThresholdError)]
+pub struct RankingScoreThreshold(f64);
+
+impl RankingScoreThreshold {
+    pub fn as_f64(&self) -> f64 {
+        self.0
+    }
+}
+
+impl TryFrom<f64> for RankingScoreThreshold {
+    type Error = DeserrRankingScoreThresholdError;
+
+    fn try_from(threshold: f64) -> Result<Self, Self::Error> {
+        if (0.0..=1.0).contains(&threshold) {
+            Ok(Self(threshold))
+        } else {
+            Err(DeserrRankingScoreThresholdError(threshold.to_string()))
+        }
+    }
+}
+
+impl FromQueryParameter for RankingScoreThreshold {
+    type Err = DeserrRankingScoreThresholdError;
+
+    fn from_query_param(p: &str) -> Result<Self, Self::Err> {
+        let threshold: f64 =
+            p.parse().map_err(|_| DeserrRankingScoreThresholdError(p.to_owned()))?;
+        Self::try_from(threshold)
+    }
+}
+
+/// A `SearchQuery` + an index UID.
+// This struct contains the fields of `SearchQuery` inline.
+// This is because neither deserr nor serde support `flatten` when using `deny_unknown_fields.
+// The `From<SearchQueryWithIndex>` implementation ensures both structs remain up to date.
+#[derive(Debug, Clone, PartialEq, DeserializeFromValue)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SearchQueryWithIndex {
+    #[deserr(error = DeserrJsonError<InvalidIndexUid>, missing_field_error = DeserrJsonError::missing_index_uid)]
+    pub index_uid: IndexUid,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
+    pub q: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchVector>)]
+    pub vector: Option<Vec<f32>>,
+    // `None` when not given, so that it can be rejected in federated queries.
+    #[deserr(default, error = DeserrJsonError<InvalidSearchOffset>)]
+    pub offset: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLimit>)]
+    pub limit: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchPage>)]
+    pub page: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHitsPerPage>)]
+    pub hits_per_page: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToRetrieve>)]
+    pub attributes_to_retrieve: Option<BTreeSet<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToCrop>)]
+    pub attributes_to_crop: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCropLength>, default = DEFAULT_CROP_LENGTH())]
+    pub crop_length: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToHighlight>)]
+    pub attributes_to_highlight: Option<HashSet<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowMatchesPosition>, default)]
+    pub show_matches_position: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScore>, default)]
+    pub show_ranking_score: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchShowRankingScoreDetails>, default)]
+    pub show_ranking_score_details: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
+    pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
+    pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
+    pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
+    pub highlight_pre_tag: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
+    pub highlight_post_tag: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCropMarker>, default = DEFAULT_CROP_MARKER())]
+    pub crop_marker: String,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchMatchingStrategy>, default)]
+    pub matching_strategy: MatchingStrategy,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>)]
+    pub attributes_to_search_on: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingScoreThreshold>)]
+    pub ranking_score_threshold: Option<RankingScoreThreshold>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchLocales>)]
+    pub locales: Option<Vec<Locale>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchCutoffMs>)]
+    pub search_cutoff_ms: Option<u64>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
+    pub distinct: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidMultiSearchFederationOptions>)]
+    pub federation_options: Option<FederationOptions>,
+}
+
+impl SearchQueryWithIndex {
+    pub fn into_index_query(self) -> (IndexUid, SearchQuery, Option<FederationOptions>) {
+        let SearchQueryWithIndex {
+            index_uid,
+            q,
+            vector,
+            offset,
+            limit,
+            page,
+            hits_per_page,
+            attributes_to_retrieve,
+            attributes_to_crop,
+            crop_length,
+            attributes_to_highlight,
+            show_matches_position,
+            show_ranking_score,
+            show_ranking_score_details,
+            filter,
+            sort,
+            facets,
+            highlight_pre_tag,
+            highlight_post_tag,
+            crop_marker,
+            matching_strategy,
+            attributes_to_search_on,
+            ranking_score_threshold,
+            locales,
+            search_cutoff_ms,
+            distinct,
+            federation_options,
+        } = self;
+        (
+            index_uid,
+            SearchQuery {
+                q,
+                vector,
+                offset: offset.unwrap_or_else(DEFAULT_SEARCH_OFFSET),
+                limit: limit.unwrap_or_else(DEFAULT_SEARCH_LIMIT),
+                page,
+                hits_per_page,
+                attributes_to_retrieve,
+                attributes_to_crop,
+                crop_length,
+                attributes_to_highlight,
+                show_matches_position,
+                show_ranking_score,
+                show_ranking_score_details,
+                filter,
+                sort,
+                facets,
+                highlight_pre_tag,
+                highlight_post_tag,
+                crop_marker,
+                matching_strategy,
+                attributes_to_search_on,
+                ranking_score_threshold,
+                locales,
+                search_cutoff_ms,
+                distinct,
+                // do not use ..Default::default() here,
+                // rather add any missing field from `SearchQuery` to `SearchQueryWithIndex`
+            },
+            federation_options,
+        )
+    }
+}
+
+/// Turns a multi-search into a federated search: the hits of all the queries are merged
+/// into a single list, and the pagination is applied to the merged list.
+#[derive(Debug, Clone, PartialEq, Eq, DeserializeFromValue)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct Federation {
+    #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSearchOffset>)]
+    pub offset: usize,
+    #[deserr(default = DEFAULT_SEARCH_LIMIT(), error = DeserrJsonError<InvalidSearchLimit>)]
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, DeserializeFromValue)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct FederationOptions {
+    /// The ranking scores of the hits of the query are multiplied by this weight
+    /// before being compared to the hits of the other queries.
+    #[deserr(default = DEFAULT_FEDERATION_WEIGHT(), error = DeserrJsonError<InvalidMultiSearchWeight>)]
+    pub weight: f64,
+}
+
+impl Default for FederationOptions {
+    fn default() -> Self {
+        Self { weight: DEFAULT_FEDERATION_WEIGHT() }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, DeserializeFromValue)]
 #[deserr(rename_all = camelCase)]
 pub enum MatchingStrategy {
@@ -98,7 +313,7 @@ impl From<MatchingStrategy> for TermsMatchingStrategy {
     }
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, PartialEq)]
 pub struct SearchHit {
     #[serde(flatten)]
     pub document: Document,
@@ -106,9 +321,13 @@ pub struct SearchHit {
     pub formatted: Document,
     #[serde(rename = "_matchesPosition", skip_serializing_if = "Option::is_none")]
     pub matches_position: Option<MatchesPosition>,
+    #[serde(rename = "_rankingScore", skip_serializing_if = "Option::is_none")]
+    pub ranking_score: Option<f64>,
+    #[serde(rename = "_rankingScoreDetails", skip_serializing_if = "Option::is_none")]
+    pub ranking_score_details: Option<serde_json::Map<String, Value>>,
 }
 
-#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Serialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SearchResult {
     pub hits: Vec<SearchHit>,
@@ -117,7 +336,38 @@ pub struct SearchResult {
     #[serde(flatten)]
     pub hits_info: HitsInfo,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub facet_distribution: Option<BTreeMap<String, BTreeMap<String, u64>>>,
+    pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    /// The time budget was exceeded and the hits are not entirely ranked.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub degraded: bool,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FacetStats {
+    pub min: f64,
+    pub max: f64,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchResultWithIndex {
+    pub index_uid: String,
+    #[serde(flatten)]
+    pub result: SearchResult,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FederatedSearchResult {
+    pub hits: Vec<SearchHit>,
+    pub processing_time_ms: u128,
+    #[serde(flatten)]
+    pub hits_info: HitsInfo,
+    /// The time budget of at least one of the queries was exceeded.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub degraded: bool,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq, Eq)]
@@ -129,24 +379,38 @@ pub enum HitsInfo {
     OffsetLimit { limit: usize, offset: usize, estimated_total_hits: usize },
 }
 
-pub fn perform_search(
-    index: &Index,
-    query: SearchQuery,
-) -> Result<SearchResult, MeilisearchHttpError> {
-    let before_search = Instant::now();
-    let rtxn = index.read_txn()?;
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetSearchResult {
+    pub facet_hits: Vec<FacetValueHit>,
+    pub facet_query: Option<String>,
+    pub processing_time_ms: u128,
+}
 
-    let mut search = index.search(&rtxn);
+fn prepare_search<'t>(
+    index: &'t Index,
+    rtxn: &'t RoTxn,
+    query: &'t SearchQuery,
+) -> Result<(milli::Search<'t>, bool, usize, usize), MeilisearchHttpError> {
+    let mut search = index.search(rtxn);
 
     if let Some(ref query) = query.q {
         search.query(query);
     }
 
+    if let Some(ref vector) = query.vector {
+        search.vector(vector.clone());
+    }
+
     let is_finite_pagination = query.is_finite_pagination();
     search.terms_matching_strategy(query.matching_strategy.into());
 
+    if let Some(ref searchable) = query.attributes_to_search_on {
+        search.searchable_attributes(searchable);
+    }
+
     let max_total_hits = index
-        .pagination_max_total_hits(&rtxn)
+        .pagination_max_total_hits(rtxn)
         .map_err(milli::Error::from)?
         .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
 
@@ -171,8 +435,29 @@ pub fn perform_search(
     search.offset(offset);
     search.limit(limit);
 
+    if let Some(ranking_score_threshold) = query.ranking_score_threshold {
+        search.ranking_score_threshold(ranking_score_threshold.as_f64());
+    }
+
+    if let Some(ref locales) = query.locales {
+        search.locales(locales.clone());
+    }
+
+    if let Some(ref distinct) = query.distinct {
+        search.distinct(distinct.clone());
+    }
+
+    let search_cutoff_ms = match query.search_cutoff_ms {
+        Some(search_cutoff_ms) => search_cutoff_ms,
+        None => index
+            .search_cutoff(rtxn)
+            .map_err(milli::Error::from)?
+            .unwrap_or(DEFAULT_SEARCH_CUTOFF_MS),
+    };
+    search.time_budget(TimeBudget::new(Duration::from_millis(search_cutoff_ms)));
+
     if let Some(ref filter) = query.filter {
-        if let Some(facets) = parse_filter(filter)? {
+        if let Some(facets) = Filter::from_json(filter)? {
             search.filter(facets);
         }
     }
@@ -188,7 +473,31 @@ pub fn perform_search(
         search.sort_criteria(sort);
     }
 
-    let milli::SearchResult { documents_ids, matching_words, candidates, .. } = search.execute()?;
+    Ok((search, is_finite_pagination, max_total_hits, offset))
+}
+
+pub fn perform_search(
+    index: &Index,
+    query: SearchQuery,
+) -> Result<SearchResult, MeilisearchHttpError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let (search, is_finite_pagination, max_total_hits, offset) =
+        prepare_search(index, &rtxn, &query)?;
+
+    let milli::SearchResult {
+        documents_ids,
+        matching_words,
+        candidates,
+        document_scores,
+        degraded,
+    } = search.execute()?;
+
+    #[cfg(feature = "metrics")]
+    if degraded {
+        crate::metrics::MEILISEARCH_DEGRADED_SEARCH_REQUESTS.inc();
+    }
 
     let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
 
@@ -243,9 +552,19 @@ pub fn perform_search(
         &displayed_ids,
     );
 
-    let tokenizer = TokenizerBuilder::default().build();
+    let mut tokenizer_builder = TokenizerBuilder::default();
+    let locales = match query.locales {
+        Some(ref locales) => locales.clone(),
+        None => index.localized_attributes_locales(&rtxn)?,
+    };
+    let allow_list = locales_allow_list(&locales);
+    if !allow_list.is_empty() {
+        tokenizer_builder.allow_list(&allow_list);
+    }
+    let tokenizer = tokenizer_builder.build();
 
     let mut formatter_builder = MatcherBuilder::new(matching_words, tokenizer);
+    formatter_builder.tokenizer_settings(TokenizerSettings::from_index(index, &rtxn)?);
     formatter_builder.crop_marker(query.crop_marker);
     formatter_builder.highlight_prefix(query.highlight_pre_tag);
     formatter_builder.highlight_suffix(query.highlight_post_tag);
@@ -254,7 +573,7 @@ pub fn perform_search(
 
     let documents_iter = index.documents(&rtxn, documents_ids)?;
 
-    for (_id, obkv) in documents_iter {
+    for ((_id, obkv), score_details) in documents_iter.into_iter().zip(document_scores) {
         // First generate a document with all the displayed fields
         let displayed_document = make_document(&displayed_ids, &fields_ids_map, obkv)?;
 
@@ -278,7 +597,19 @@ pub fn perform_search(
             insert_geo_distance(sort, &mut document);
         }
 
-        let hit = SearchHit { document, formatted, matches_position };
+        let ranking_score =
+            query.show_ranking_score.then(|| ScoreDetails::global_score(score_details.iter()));
+        let ranking_score_details = query
+            .show_ranking_score_details
+            .then(|| ScoreDetails::to_json_map(score_details.iter()));
+
+        let hit = SearchHit {
+            document,
+            formatted,
+            matches_position,
+            ranking_score,
+            ranking_score_details,
+        };
         documents.push(hit);
     }
 
@@ -300,7 +631,7 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit: query.limit, offset, estimated_total_hits: number_of_hits }
     };
 
-    let facet_distribution = match query.facets {
+    let (facet_distribution, facet_stats) = match query.facets {
         Some(ref fields) => {
             let mut facet_distribution = index.facets_distribution(&rtxn);
 
@@ -310,14 +641,24 @@ pub fn perform_search(
                 .unwrap_or(DEFAULT_VALUES_PER_FACET);
             facet_distribution.max_values_per_facet(max_values_by_facet);
 
+            let sort_facet_values_by =
+                index.sort_facet_values_by(&rtxn).map_err(milli::Error::from)?;
+            facet_distribution.order_by(sort_facet_values_by);
+
             if fields.iter().all(|f| f != "*") {
                 facet_distribution.facets(fields);
             }
-            let distribution = facet_distribution.candidates(candidates).execute()?;
+            facet_distribution.candidates(candidates);
+            let distribution = facet_distribution.execute()?;
+            let stats = facet_distribution
+                .compute_stats()?
+                .into_iter()
+                .map(|(name, (min, max))| (name, FacetStats { min, max }))
+                .collect();
 
-            Some(distribution)
+            (Some(distribution), Some(stats))
         }
-        None => None,
+        None => (None, None),
     };
 
     let result = SearchResult {
@@ -326,10 +667,135 @@ pub fn perform_search(
         query: query.q.clone().unwrap_or_default(),
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
+        facet_stats,
+        degraded,
     };
     Ok(result)
 }
 
+/// The result of one of the queries of a federated search.
+pub struct FederatedQueryResult {
+    pub result: SearchResultWithIndex,
+    pub federation_options: FederationOptions,
+    /// Whether the query asked for the ranking score, it is always computed to merge the hits.
+    pub show_ranking_score: bool,
+}
+
+/// Merges the results of the queries of a federated search into a single list of hits.
+///
+/// The hits are interleaved by their weighted ranking score while keeping the order of the hits
+/// of each query, and the offset and limit of the federation are applied on the merged list.
+pub fn merge_federated_results(
+    results: Vec<FederatedQueryResult>,
+    federation: Federation,
+    processing_time_ms: u128,
+) -> FederatedSearchResult {
+    struct QueryHits {
+        index_uid: String,
+        queries_position: usize,
+        weight: f64,
+        show_ranking_score: bool,
+        hits: std::iter::Peekable<std::vec::IntoIter<SearchHit>>,
+    }
+
+    let Federation { offset, limit } = federation;
+
+    let mut estimated_total_hits = 0;
+    let mut degraded = false;
+    let mut queries_hits = Vec::with_capacity(results.len());
+    for (queries_position, query_result) in results.into_iter().enumerate() {
+        let FederatedQueryResult {
+            result: SearchResultWithIndex { index_uid, result },
+            federation_options,
+            show_ranking_score,
+        } = query_result;
+        estimated_total_hits += match result.hits_info {
+            HitsInfo::OffsetLimit { estimated_total_hits, .. } => estimated_total_hits,
+            HitsInfo::Pagination { total_hits, .. } => total_hits,
+        };
+        degraded |= result.degraded;
+        queries_hits.push(QueryHits {
+            index_uid,
+            queries_position,
+            weight: federation_options.weight,
+            show_ranking_score,
+            hits: result.hits.into_iter().peekable(),
+        });
+    }
+
+    let mut hits = Vec::new();
+    while hits.len() < offset.saturating_add(limit) {
+        // find the query with the best next hit, the first query wins in case of a tie.
+        let mut best: Option<(usize, f64)> = None;
+        for (index, query) in queries_hits.iter_mut().enumerate() {
+            if let Some(hit) = query.hits.peek() {
+                let ranking_score = hit.ranking_score.unwrap_or_default();
+                let weighted_ranking_score = ranking_score * query.weight;
+                if best.map_or(true, |(_, best_score)| weighted_ranking_score > best_score) {
+                    best = Some((index, weighted_ranking_score));
+                }
+            }
+        }
+
+        let (index, weighted_ranking_score) = match best {
+            Some(best) => best,
+            None => break,
+        };
+
+        let query = &mut queries_hits[index];
+        let mut hit = query.hits.next().expect("the hit was peeked");
+        if !query.show_ranking_score {
+            hit.ranking_score = None;
+        }
+        hit.document.insert(
+            "_federation".to_string(),
+            json!({
+                "indexUid": query.index_uid,
+                "queriesPosition": query.queries_position,
+                "weightedRankingScore": weighted_ranking_score,
+            }),
+        );
+        hits.push(hit);
+    }
+
+    let hits = hits.into_iter().skip(offset).collect();
+
+    FederatedSearchResult {
+        hits,
+        processing_time_ms,
+        hits_info: HitsInfo::OffsetLimit { limit, offset, estimated_total_hits },
+        degraded,
+    }
+}
+
+pub fn perform_facet_search(
+    index: &Index,
+    search_query: SearchQuery,
+    facet_query: Option<String>,
+    facet_name: String,
+) -> Result<FacetSearchResult, MeilisearchHttpError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let (search, _, _, _) = prepare_search(index, &rtxn, &search_query)?;
+    let mut facet_search = SearchForFacetValues::new(facet_name, search);
+    if let Some(facet_query) = &facet_query {
+        facet_search.query(facet_query);
+    }
+
+    let max_values_by_facet = index
+        .max_values_per_facet(&rtxn)
+        .map_err(milli::Error::from)?
+        .unwrap_or(DEFAULT_VALUES_PER_FACET);
+    facet_search.max_values(max_values_by_facet);
+
+    Ok(FacetSearchResult {
+        facet_hits: facet_search.execute()?,
+        facet_query,
+        processing_time_ms: before_search.elapsed().as_millis(),
+    })
+}
+
 fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     lazy_static::lazy_static! {
         static ref GEO_REGEX: Regex =
@@ -338,9 +804,18 @@ fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     if let Some(capture_group) = sorts.iter().find_map(|sort| GEO_REGEX.captures(sort)) {
         // TODO: TAMO: milli encountered an internal error, what do we want to do?
         let base = [capture_group[1].parse().unwrap(), capture_group[2].parse().unwrap()];
-        let geo_point = &document.get("_geo").unwrap_or(&json!(null));
-        if let Some((lat, lng)) = geo_point["lat"].as_f64().zip(geo_point["lng"].as_f64()) {
-            let distance = milli::distance_between_two_points(&base, &[lat, lng]);
+        let geo_value = document.get("_geo").unwrap_or(&json!(null));
+        // a document can contain several points, we report the distance to the nearest one
+        let geo_points = match geo_value {
+            Value::Array(points) => points.iter().collect(),
+            point => vec![point],
+        };
+        let distance = geo_points
+            .into_iter()
+            .filter_map(|point| point["lat"].as_f64().zip(point["lng"].as_f64()))
+            .map(|(lat, lng)| milli::distance_between_two_points(&base, &[lat, lng]))
+            .min_by(|a, b| a.total_cmp(b));
+        if let Some(distance) = distance {
             document.insert("_geoDistance".to_string(), json!(distance.round() as usize));
         }
     }
@@ -502,7 +977,8 @@ fn format_fields<A: AsRef<[u8]>>(
             .reduce(|acc, option| acc.merge(option));
         let mut infos = Vec::new();
 
-        *value = format_value(std::mem::take(value), builder, format, &mut infos, compute_matches);
+        *value =
+            format_value(std::mem::take(value), key, builder, format, &mut infos, compute_matches);
 
         if let Some(matches) = matches_position.as_mut() {
             if !infos.is_empty() {
@@ -523,6 +999,7 @@ fn format_fields<A: AsRef<[u8]>>(
 
 fn format_value<A: AsRef<[u8]>>(
     value: Value,
+    field: &str,
     builder: &MatcherBuilder<'_, A>,
     format_options: Option<FormatOptions>,
     infos: &mut Vec<MatchBounds>,
@@ -530,7 +1007,7 @@ fn format_value<A: AsRef<[u8]>>(
 ) -> Value {
     match value {
         Value::String(old_string) => {
-            let mut matcher = builder.build(&old_string);
+            let mut matcher = builder.build_in_field(&old_string, field);
             if compute_matches {
                 let matches = matcher.matches();
                 infos.extend_from_slice(&matches[..]);
@@ -550,6 +1027,7 @@ fn format_value<A: AsRef<[u8]>>(
                 .map(|v| {
                     format_value(
                         v,
+                        field,
                         builder,
                         format_options.map(|format_options| FormatOptions {
                             highlight: format_options.highlight,
@@ -569,6 +1047,7 @@ fn format_value<A: AsRef<[u8]>>(
                         k,
                         format_value(
                             v,
+                            field,
                             builder,
                             format_options.map(|format_options| FormatOptions {
                                 highlight: format_options.highlight,
@@ -584,7 +1063,7 @@ fn format_value<A: AsRef<[u8]>>(
         Value::Number(number) => {
             let s = number.to_string();
 
-            let mut matcher = builder.build(&s);
+            let mut matcher = builder.build_in_field(&s, field);
             if compute_matches {
                 let matches = matcher.matches();
                 infos.extend_from_slice(&matches[..]);
@@ -602,49 +1081,6 @@ fn format_value<A: AsRef<[u8]>>(
     }
 }
 
-fn parse_filter(facets: &Value) -> Result<Option<Filter>, MeilisearchHttpError> {
-    match facets {
-        Value::String(expr) => {
-            let condition = Filter::from_str(expr)?;
-            Ok(condition)
-        }
-        Value::Array(arr) => parse_filter_array(arr),
-        v => Err(MeilisearchHttpError::InvalidExpression(&["String", "Array"], v.clone())),
-    }
-}
-
-fn parse_filter_array(arr: &[Value]) -> Result<Option<Filter>, MeilisearchHttpError> {
-    let mut ands = Vec::new();
-    for value in arr {
-        match value {
-            Value::String(s) => ands.push(Either::Right(s.as_str())),
-            Value::Array(arr) => {
-                let mut ors = Vec::new();
-                for value in arr {
-                    match value {
-                        Value::String(s) => ors.push(s.as_str()),
-                        v => {
-                            return Err(MeilisearchHttpError::InvalidExpression(
-                                &["String"],
-                                v.clone(),
-                            ))
-                        }
-                    }
-                }
-                ands.push(Either::Left(ors));
-            }
-            v => {
-                return Err(MeilisearchHttpError::InvalidExpression(
-                    &["String", "[String]"],
-                    v.clone(),
-                ))
-            }
-        }
-    }
-
-    Ok(Filter::from_array(ands)?)
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -709,4 +1145,25 @@ mod test {
         insert_geo_distance(sorters, &mut document);
         assert_eq!(document.get("_geoDistance"), None);
     }
+
+    #[test]
+    fn test_insert_geo_distance_with_multiple_points() {
+        let value: Document = serde_json::from_str(
+            r#"{
+              "_geo": [
+                { "lat": 48.8566, "lng": 2.3522 },
+                { "lat": 50.629973371633746, "lng": 3.0569447399419567 }
+              ],
+              "city": "Lille",
+              "id": "1"
+            }"#,
+        )
+        .unwrap();
+
+        // the distance to the nearest point is reported
+        let sorters = &["_geoPoint(50.629973371633746, 3.0569447399419567):asc".to_string()];
+        let mut document = value;
+        insert_geo_distance(sorters, &mut document);
+        assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+    }
 }
@@ -0,0 +1,222 @@
+use meili_snap::*;
+use serde_json::json;
+
+use crate::common::Server;
+
+/// Return the uids of the batches of a `GET /batches` response.
+fn batch_uids(response: &serde_json::Value) -> Vec<u64> {
+    response["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|batch| batch["uid"].as_u64().unwrap())
+        .collect()
+}
+
+#[actix_rt::test]
+async fn get_batch() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+    index.add_documents(json!([{ "id": 1, "doggo": "bork" }]), None).await;
+    index.wait_task(1).await;
+
+    let (task, _) = index.get_task(1).await;
+    assert_eq!(task["batchUid"], json!(1));
+
+    let (response, code) = server.get_batch(1).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".timings.*" => "[duration]", ".duration" => "[duration]", ".startedAt" => "[date]", ".finishedAt" => "[date]" }), @r###"
+    {
+      "uid": 1,
+      "taskUids": [
+        1
+      ],
+      "details": {
+        "receivedDocuments": 1,
+        "indexedDocuments": 1
+      },
+      "timings": {
+        "transform": "[duration]",
+        "extraction": "[duration]",
+        "prefixDatabases": "[duration]",
+        "commit": "[duration]"
+      },
+      "duration": "[duration]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
+
+    // the index creation doesn't go through any of the timed phases.
+    let (response, code) = server.get_batch(0).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["timings"]), @r###"
+    {
+      "transform": null,
+      "extraction": null,
+      "prefixDatabases": null,
+      "commit": null
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn list_batches() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+    index.wait_task(0).await;
+    index.add_documents(json!([{ "id": 1, "doggo": "bork" }]), None).await;
+    index.wait_task(1).await;
+    // the index already exists, the creation fails.
+    index.create(None).await;
+    index.wait_task(2).await;
+    server.index("other").create(None).await;
+    server.wait_task(3).await;
+
+    let (response, code) = server.batches_filter("").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", batch_uids(&response)), @"[3, 2, 1, 0]");
+
+    let (response, code) = server.batches_filter("statuses=failed").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", batch_uids(&response)), @"[2]");
+
+    let (response, code) = server.batches_filter("types=indexCreation&indexUids=test").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", batch_uids(&response)), @"[2, 0]");
+
+    let (response, code) = server.batches_filter("uids=0,3").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", batch_uids(&response)), @"[3, 0]");
+
+    let (response, code) = server.batches_filter("limit=2").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", batch_uids(&response)), @"[3, 2]");
+    snapshot!(json_string!(response, { ".results" => "[batches]" }), @r###"
+    {
+      "results": "[batches]",
+      "limit": 2,
+      "from": 3,
+      "next": 1
+    }
+    "###);
+
+    let (response, code) = server.batches_filter("limit=2&from=1").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".results" => "[batches]" }), @r###"
+    {
+      "results": "[batches]",
+      "limit": 2,
+      "from": 1,
+      "next": null
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn list_only_the_batches_of_the_authorized_indexes() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+    server.index("cat").create(None).await;
+    server.index("dog").create(None).await;
+    server.wait_task(1).await;
+
+    let content = json!({
+        "indexes": ["dog"],
+        "actions": ["tasks.get"],
+        "expiresAt": null,
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", &response);
+    let key = response["key"].as_str().unwrap().to_string();
+    server.use_api_key(key);
+
+    let (response, code) = server.batches_filter("").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", batch_uids(&response)), @"[1]");
+
+    let (response, code) = server.get_batch(0).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Batch `0` not found.",
+      "code": "batch_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#batch_not_found"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn batches_bad_parameters() {
+    let server = Server::new().await;
+
+    let (response, code) = server.service.get("/batches/doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Batch uid `doggo` is invalid. It should only contain numeric characters.",
+      "code": "invalid_batch_uids",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_batch_uids"
+    }
+    "###);
+
+    let (response, code) = server.get_batch(42).await;
+    snapshot!(code, @"404 Not Found");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Batch `42` not found.",
+      "code": "batch_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#batch_not_found"
+    }
+    "###);
+
+    let (response, code) = server.batches_filter("uids=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `uids`: could not parse `doggo` as a positive integer",
+      "code": "invalid_batch_uids",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_batch_uids"
+    }
+    "###);
+
+    let (response, code) = server.batches_filter("limit=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `limit`: could not parse `doggo` as a positive integer",
+      "code": "invalid_batch_limit",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_batch_limit"
+    }
+    "###);
+
+    let (response, code) = server.batches_filter("from=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `from`: could not parse `doggo` as a positive integer",
+      "code": "invalid_batch_from",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_batch_from"
+    }
+    "###);
+
+    let (response, code) = server.batches_filter("canceledBy=0").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown parameter `canceledBy`: expected one of `limit`, `from`, `uids`, `types`, `statuses`, `indexUids`, `afterStartedAt`, `beforeStartedAt`, `afterFinishedAt`, `beforeFinishedAt`",
+      "code": "bad_request",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#bad_request"
+    }
+    "###);
+}
@@ -152,6 +152,10 @@ impl Server {
         self.service.post("/dumps", json!(null)).await
     }
 
+    pub async fn multi_search(&self, queries: Value) -> (Value, StatusCode) {
+        self.service.post("/multi-search", queries).await
+    }
+
     pub async fn index_swap(&self, value: Value) -> (Value, StatusCode) {
         self.service.post("/swap-indexes", value).await
     }
@@ -185,6 +189,15 @@ impl Server {
         let url = format!("/tasks/{}", update_id);
         self.service.get(url).await
     }
+
+    pub async fn batches_filter(&self, filter: &str) -> (Value, StatusCode) {
+        self.service.get(format!("/batches?{}", filter)).await
+    }
+
+    pub async fn get_batch(&self, batch_uid: u32) -> (Value, StatusCode) {
+        let url = format!("/batches/{}", batch_uid);
+        self.service.get(url).await
+    }
 }
 
 pub fn default_settings(dir: impl AsRef<Path>) -> Opt {
@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use actix_web::body::BoxBody;
+use actix_web::dev::ServiceResponse;
 use actix_web::http::header::ContentType;
 use actix_web::http::StatusCode;
 use actix_web::test;
@@ -88,7 +90,24 @@ impl Service {
         self.request(req).await
     }
 
-    pub async fn request(&self, mut req: test::TestRequest) -> (Value, StatusCode) {
+    /// Send a test get request and return the body of the response without waiting for its end.
+    pub async fn get_stream(&self, url: impl AsRef<str>) -> (BoxBody, StatusCode) {
+        let req = test::TestRequest::get().uri(url.as_ref());
+        let res = self.call(req).await;
+        let status_code = res.status();
+        (res.into_body(), status_code)
+    }
+
+    pub async fn request(&self, req: test::TestRequest) -> (Value, StatusCode) {
+        let res = self.call(req).await;
+        let status_code = res.status();
+
+        let body = test::read_body(res).await;
+        let response = serde_json::from_slice(&body).unwrap_or_default();
+        (response, status_code)
+    }
+
+    async fn call(&self, mut req: test::TestRequest) -> ServiceResponse<BoxBody> {
         let app = test::init_service(create_app(
             self.index_scheduler.clone().into(),
             self.auth.clone(),
@@ -102,12 +121,7 @@ impl Service {
             req = req.insert_header(("Authorization", ["Bearer ", api_key].concat()));
         }
         let req = req.to_request();
-        let res = test::call_service(&app, req).await;
-        let status_code = res.status();
-
-        let body = test::read_body(res).await;
-        let response = serde_json::from_slice(&body).unwrap_or_default();
-        (response, status_code)
+        test::call_service(&app, req).await.map_into_boxed_body()
     }
 
     fn encode(&self, req: TestRequest, body: Value, encoder: Encoder) -> TestRequest {
@@ -696,6 +696,7 @@ async fn add_documents_no_index_creation() {
         @r###"
     {
       "uid": 0,
+      "batchUid": 0,
       "indexUid": "test",
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
@@ -774,6 +775,7 @@ async fn document_addition_with_primary_key() {
         @r###"
     {
       "uid": 0,
+      "batchUid": 0,
       "indexUid": "test",
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
@@ -848,6 +850,7 @@ async fn replace_document() {
         @r###"
     {
       "uid": 1,
+      "batchUid": 1,
       "indexUid": "test",
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
@@ -936,6 +939,7 @@ async fn error_add_documents_bad_document_id() {
         @r###"
     {
       "uid": 1,
+      "batchUid": 1,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -977,6 +981,7 @@ async fn error_add_documents_missing_document_id() {
         @r###"
     {
       "uid": 1,
+      "batchUid": 1,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1050,6 +1055,7 @@ async fn add_documents_invalid_geo_field() {
         @r###"
     {
       "uid": 2,
+      "batchUid": 2,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1087,6 +1093,7 @@ async fn add_documents_invalid_geo_field() {
         @r###"
     {
       "uid": 3,
+      "batchUid": 3,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1124,6 +1131,7 @@ async fn add_documents_invalid_geo_field() {
         @r###"
     {
       "uid": 4,
+      "batchUid": 4,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1161,6 +1169,7 @@ async fn add_documents_invalid_geo_field() {
         @r###"
     {
       "uid": 5,
+      "batchUid": 5,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1198,6 +1207,7 @@ async fn add_documents_invalid_geo_field() {
         @r###"
     {
       "uid": 6,
+      "batchUid": 6,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1235,6 +1245,7 @@ async fn add_documents_invalid_geo_field() {
         @r###"
     {
       "uid": 7,
+      "batchUid": 7,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1272,6 +1283,7 @@ async fn add_documents_invalid_geo_field() {
         @r###"
     {
       "uid": 8,
+      "batchUid": 8,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1309,6 +1321,7 @@ async fn add_documents_invalid_geo_field() {
         @r###"
     {
       "uid": 9,
+      "batchUid": 9,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1346,6 +1359,7 @@ async fn add_documents_invalid_geo_field() {
         @r###"
     {
       "uid": 10,
+      "batchUid": 10,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1383,6 +1397,7 @@ async fn add_documents_invalid_geo_field() {
         @r###"
     {
       "uid": 11,
+      "batchUid": 11,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1420,6 +1435,7 @@ async fn add_documents_invalid_geo_field() {
         @r###"
     {
       "uid": 12,
+      "batchUid": 12,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1457,6 +1473,7 @@ async fn add_documents_invalid_geo_field() {
         @r###"
     {
       "uid": 13,
+      "batchUid": 13,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1527,6 +1544,7 @@ async fn error_primary_key_inference() {
     @r###"
     {
       "uid": 0,
+      "batchUid": 0,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1567,6 +1585,7 @@ async fn error_primary_key_inference() {
     @r###"
     {
       "uid": 1,
+      "batchUid": 1,
       "indexUid": "test",
       "status": "failed",
       "type": "documentAdditionOrUpdate",
@@ -1605,6 +1624,7 @@ async fn error_primary_key_inference() {
     @r###"
     {
       "uid": 2,
+      "batchUid": 2,
       "indexUid": "test",
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
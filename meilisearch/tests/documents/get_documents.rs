@@ -1,5 +1,6 @@
 use actix_web::test;
 use http::header::ACCEPT_ENCODING;
+use meili_snap::*;
 use serde_json::{json, Value};
 use urlencoding::encode as urlencode;
 
@@ -378,3 +379,94 @@ async fn get_documents_displayed_attributes_is_ignored() {
     assert_eq!(response.as_object().unwrap().keys().count(), 16);
     assert!(response.as_object().unwrap().get("gender").is_some());
 }
+
+#[actix_rt::test]
+async fn get_documents_filter_and_sort() {
+    let server = Server::new().await;
+    let index = server.index("doggos");
+    let (_, code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["color"],
+            "sortableAttributes": ["age", "name"],
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index
+        .add_documents(
+            json!([
+                { "id": 0, "name": "kefir", "color": "white", "age": 3 },
+                { "id": 1, "name": "intel", "color": "brown", "age": 7 },
+                { "id": 2, "name": "fast", "color": "brown", "age": 3 },
+                { "id": 3, "name": "bobby", "color": "white" },
+            ]),
+            None,
+        )
+        .await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.get_all_documents_raw("?filter=color=brown&fields=id").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": 1
+        },
+        {
+          "id": 2
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 2
+    }
+    "###);
+
+    let (response, code) = index.get_all_documents_raw("?sort=age:desc,name:asc&fields=id").await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": 1
+        },
+        {
+          "id": 2
+        },
+        {
+          "id": 0
+        },
+        {
+          "id": 3
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 4
+    }
+    "###);
+
+    let (response, code) = index
+        .fetch_documents(json!({
+            "filter": ["color = white"],
+            "sort": ["age:asc"],
+            "fields": ["id", "name"],
+            "offset": 1,
+            "limit": 1,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": [
+        {
+          "id": 3,
+          "name": "bobby"
+        }
+      ],
+      "offset": 1,
+      "limit": 1,
+      "total": 2
+    }
+    "###);
+}
@@ -0,0 +1,85 @@
+use meili_snap::*;
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+
+use crate::common::Server;
+
+pub(self) static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 1, "product_id": "123", "brand": "apple", "title": "iphone blue" },
+        { "id": 2, "product_id": "123", "brand": "apple", "title": "iphone red" },
+        { "id": 3, "product_id": "456", "brand": "apple", "title": "macbook" },
+        { "id": 4, "product_id": "789", "brand": "samsung", "title": "galaxy" },
+        { "id": 5, "product_id": "789", "brand": "samsung", "title": "galaxy blue" },
+        { "id": 6, "product_id": "000", "brand": "google", "title": "pixel" },
+    ])
+});
+
+#[actix_rt::test]
+async fn distinct_search_parameter() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), Some("id")).await;
+    index
+        .update_settings(json!({
+            "filterableAttributes": ["product_id", "brand"],
+            "distinctAttribute": "product_id",
+        }))
+        .await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({ "page": 1, "hitsPerPage": 10 }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["totalHits"], json!(4), "{}", response);
+        })
+        .await;
+
+    // the search parameter overrides the distinct attribute of the index.
+    index
+        .search(
+            json!({ "distinct": "brand", "page": 1, "hitsPerPage": 10, "facets": ["brand"] }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(response["hits"].as_array().unwrap().len(), 3, "{}", response);
+                assert_eq!(response["totalHits"], json!(3), "{}", response);
+                assert_eq!(
+                    response["facetDistribution"],
+                    json!({ "brand": { "apple": 1, "google": 1, "samsung": 1 } }),
+                    "{}",
+                    response
+                );
+            },
+        )
+        .await;
+
+    index
+        .search(json!({ "q": "blue", "distinct": "brand" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 2, "{}", response);
+            assert_eq!(response["estimatedTotalHits"], json!(2), "{}", response);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn distinct_search_parameter_must_be_filterable() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(DOCUMENTS.clone(), Some("id")).await;
+    index.update_settings(json!({ "filterableAttributes": ["product_id"] })).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.search_post(json!({ "distinct": "brand" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `brand` is not filterable and thus, cannot be used as distinct attribute. Available filterable attributes are: `product_id`.",
+      "code": "invalid_search_distinct",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_distinct"
+    }
+    "###);
+}
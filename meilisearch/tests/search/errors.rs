@@ -55,6 +55,23 @@ async fn search_bad_q() {
     // Can't make the `q` fail with a get search since it'll accept anything as a string.
 }
 
+#[actix_rt::test]
+async fn search_bad_vector() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"vector": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.vector`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_vector",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_vector"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_offset() {
     let server = Server::new().await;
@@ -185,6 +202,55 @@ async fn search_bad_attributes_to_crop() {
     // Can't make the `attributes_to_crop` fail with a get search since it'll accept anything as an array of strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_attributes_to_search_on() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"attributesToSearchOn": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.attributesToSearchOn`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_attributes_to_search_on",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_attributes_to_search_on"
+    }
+    "###);
+    // Can't make the `attributes_to_search_on` fail with a get search since it'll accept anything as an array of strings.
+}
+
+#[actix_rt::test]
+async fn search_non_searchable_attributes_to_search_on() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.update_settings(json!({"searchableAttributes": ["title", "desc"]})).await;
+    index.wait_task(0).await;
+
+    let (response, code) =
+        index.search_post(json!({"q": "doggo", "attributesToSearchOn": ["title", "id"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `id` is not searchable. Available searchable attributes are: `desc, title`.",
+      "code": "invalid_search_attributes_to_search_on",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_attributes_to_search_on"
+    }
+    "###);
+
+    let (response, code) = index.search_get("q=doggo&attributesToSearchOn=title,id").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `id` is not searchable. Available searchable attributes are: `desc, title`.",
+      "code": "invalid_search_attributes_to_search_on",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_attributes_to_search_on"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_crop_length() {
     let server = Server::new().await;
@@ -303,6 +369,136 @@ async fn search_bad_show_matches_position() {
     "###);
 }
 
+#[actix_rt::test]
+async fn search_bad_show_ranking_score() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"showRankingScore": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.showRankingScore`: expected a boolean, but found a string: `\"doggo\"`",
+      "code": "invalid_search_show_ranking_score",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_show_ranking_score"
+    }
+    "###);
+
+    let (response, code) = index.search_get("showRankingScoreDetails=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `showRankingScoreDetails`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_search_show_ranking_score_details",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_show_ranking_score_details"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_ranking_score_threshold() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"rankingScoreThreshold": 1.5})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rankingScoreThreshold`: could not parse `1.5` as a ranking score threshold, expected a number between `0.0` and `1.0`",
+      "code": "invalid_search_ranking_score_threshold",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_score_threshold"
+    }
+    "###);
+
+    let (response, code) = index.search_get("rankingScoreThreshold=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `rankingScoreThreshold`: could not parse `doggo` as a ranking score threshold, expected a number between `0.0` and `1.0`",
+      "code": "invalid_search_ranking_score_threshold",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_score_threshold"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_locales() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"locales": ["japanese"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `japanese` at `.locales[0]`: expected one of `ara`, `cmn`, `dan`, `deu`, `ell`, `eng`, `fra`, `heb`, `hin`, `ita`, `jpn`, `kor`, `nld`, `pol`, `por`, `rus`, `spa`, `swe`, `tha`, `tur`, `ukr`, `vie`",
+      "code": "invalid_search_locales",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_locales"
+    }
+    "###);
+
+    let (response, code) = index.search_get("locales=jpn,japanese").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `locales`: Unsupported locale `japanese`, expected one of `ara`, `cmn`, `dan`, `deu`, `ell`, `eng`, `fra`, `heb`, `hin`, `ita`, `jpn`, `kor`, `nld`, `pol`, `por`, `rus`, `spa`, `swe`, `tha`, `tur`, `ukr`, `vie`.",
+      "code": "invalid_search_locales",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_locales"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_search_cutoff_ms() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"searchCutoffMs": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.searchCutoffMs`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_search_cutoff_ms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_cutoff_ms"
+    }
+    "###);
+
+    let (response, code) = index.search_get("searchCutoffMs=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `searchCutoffMs`: could not parse `doggo` as a positive integer",
+      "code": "invalid_search_cutoff_ms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_cutoff_ms"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_bad_distinct() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.search_post(json!({"distinct": ["doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.distinct`: expected a string, but found an array: `[\"doggo\"]`",
+      "code": "invalid_search_distinct",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_distinct"
+    }
+    "###);
+    // Can't make the `distinct` fail with a get search since it'll accept anything as a string.
+}
+
 #[actix_rt::test]
 async fn search_bad_facets() {
     let server = Server::new().await;
@@ -547,7 +743,7 @@ async fn filter_invalid_syntax_object() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `title & Glass`.\n1:14 title & Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -572,7 +768,7 @@ async fn filter_invalid_syntax_array() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+        "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `title & Glass`.\n1:14 title & Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -672,7 +868,7 @@ async fn filter_reserved_geo_attribute_array() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` field coordinates.\n1:5 _geo = Glass",
+        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])`, or `_geoPolygon([[latitude, longitude], ...])` built-in rules to filter on `_geo` field coordinates.\n1:5 _geo = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -697,7 +893,7 @@ async fn filter_reserved_geo_attribute_string() {
     index.wait_task(1).await;
 
     let expected_response = json!({
-        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` field coordinates.\n1:5 _geo = Glass",
+        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])`, or `_geoPolygon([[latitude, longitude], ...])` built-in rules to filter on `_geo` field coordinates.\n1:5 _geo = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -0,0 +1,178 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+
+use crate::common::Server;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        {
+            "title": "Shazam!",
+            "genres": ["Action", "Comedy"],
+            "id": "287947",
+        },
+        {
+            "title": "Captain Marvel",
+            "genres": ["Action", "Adventure"],
+            "id": "299537",
+        },
+        {
+            "title": "Escape Room",
+            "genres": ["Horror", "Thriller"],
+            "id": "522681",
+        },
+        {
+            "title": "How to Train Your Dragon: The Hidden World",
+            "genres": ["Animation", "Adventure"],
+            "id": "166428",
+        },
+        {
+            "title": "Glass",
+            "genres": ["Drama", "Thriller"],
+            "id": "450465",
+        }
+    ])
+});
+
+#[actix_rt::test]
+async fn simple_facet_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["genres"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "genres", "facetQuery": "a"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetHits"]), @r###"
+    [
+      {
+        "value": "Action",
+        "count": 2
+      },
+      {
+        "value": "Adventure",
+        "count": 2
+      },
+      {
+        "value": "Animation",
+        "count": 1
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "genres", "facetQuery": "adv"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetHits"]), @r###"
+    [
+      {
+        "value": "Adventure",
+        "count": 2
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn facet_search_with_typo() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["genres"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "genres", "facetQuery": "thriler"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetHits"]), @r###"
+    [
+      {
+        "value": "Thriller",
+        "count": 2
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn facet_search_restricted_by_query_and_filter() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["genres"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "genres", "facetQuery": "a", "q": "captain"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetHits"]), @r###"
+    [
+      {
+        "value": "Action",
+        "count": 1
+      },
+      {
+        "value": "Adventure",
+        "count": 1
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "genres", "filter": "genres = Horror"})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["facetHits"]), @r###"
+    [
+      {
+        "value": "Horror",
+        "count": 1
+      },
+      {
+        "value": "Thriller",
+        "count": 1
+      }
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn facet_search_bad_facet_name() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["genres"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.facet_search(json!({"facetName": "title", "facetQuery": "a"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Attribute `title` is not filterable. Available filterable attributes are: `genres`.",
+      "code": "invalid_facet_search_facet_name",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_facet_search_facet_name"
+    }
+    "###);
+
+    let (response, code) = index.facet_search(json!({"facetQuery": "a"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `facetName`",
+      "code": "missing_facet_search_facet_name",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_facet_search_facet_name"
+    }
+    "###);
+}
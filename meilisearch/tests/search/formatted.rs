@@ -442,3 +442,55 @@ async fn displayedattr_2_smol() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn formatted_with_negated_and_field_scoped_terms() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 1, "title": "iphone case", "description": "a case for your iphone" },
+        { "id": 2, "title": "refurbished iphone", "description": "like new" },
+        { "id": 3, "title": "iphone", "description": "refurbished phone" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    // the scoped word is only highlighted in its field and the negated one is never highlighted.
+    index
+        .search(
+            json!({
+                "q": "title:iphone -description:refurbished",
+                "attributesToRetrieve": ["id"],
+                "attributesToHighlight": ["title", "description"],
+                "showMatchesPosition": true,
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(
+                    response["hits"],
+                    json!([
+                        {
+                            "id": 1,
+                            "_formatted": {
+                                "id": "1",
+                                "title": "<em>iphone</em> case",
+                                "description": "a case for your iphone",
+                            },
+                            "_matchesPosition": {"title": [{"start": 0, "length": 6}]},
+                        },
+                        {
+                            "id": 2,
+                            "_formatted": {
+                                "id": "2",
+                                "title": "refurbished <em>iphone</em>",
+                                "description": "like new",
+                            },
+                            "_matchesPosition": {"title": [{"start": 12, "length": 6}]},
+                        },
+                    ])
+                );
+            },
+        )
+        .await;
+}
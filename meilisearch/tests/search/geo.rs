@@ -0,0 +1,207 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+
+use crate::common::index::Index;
+use crate::common::Server;
+
+async fn index_with_documents<'a>(server: &'a Server, documents: &Value) -> Index<'a> {
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["_geo"]})).await;
+    index.add_documents(documents.clone(), None).await;
+    index.wait_task(1).await;
+    index
+}
+
+static GEO_DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+    {
+        "id": 1,
+        "name": "Lille",
+        "_geo": { "lat": 50.6299, "lng": 3.0569 },
+    },
+    {
+        "id": 2,
+        "name": "Lille delivery zone",
+        "_geo": {
+            "type": "Polygon",
+            "coordinates": [[[2.9, 50.5], [3.2, 50.5], [3.2, 50.8], [2.9, 50.8], [2.9, 50.5]]]
+        },
+    },
+    {
+        "id": 3,
+        "name": "Brussels delivery route",
+        "_geo": {
+            "type": "LineString",
+            "coordinates": [[4.3, 50.8], [4.4, 50.9]]
+        },
+    },
+    {
+        "id": 4,
+        "name": "Paris",
+        "_geo": { "type": "Point", "coordinates": [2.3522, 48.8566] },
+    }])
+});
+
+#[actix_rt::test]
+async fn geo_polygon() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &GEO_DOCUMENTS).await;
+
+    let query = json!({
+        "filter": "_geoPolygon([[50.9, 2.8], [50.9, 3.5], [50.4, 3.5], [50.4, 2.8]])",
+        "attributesToRetrieve": ["id"],
+    });
+    index
+        .search(query, |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 2
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    let query = json!({
+        "filter": "_geoPolygon([[49, 2], [49, 2.5], [48, 2.5], [48, 2]])",
+        "attributesToRetrieve": ["id"],
+    });
+    index
+        .search(query, |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 4
+              }
+            ]
+            "###);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn geojson_geometry_contains_point() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &GEO_DOCUMENTS).await;
+
+    // Only the delivery zone contains this point, Lille is several kilometers away.
+    let query = json!({
+        "filter": "_geoRadius(50.7, 3.1, 0)",
+        "attributesToRetrieve": ["id"],
+    });
+    index
+        .search(query, |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    // The delivery route crosses the bounding box without having any position inside it.
+    let query = json!({
+        "filter": "_geoBoundingBox([50.87, 4.34], [50.83, 4.36])",
+        "attributesToRetrieve": ["id"],
+    });
+    index
+        .search(query, |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 3
+              }
+            ]
+            "###);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn bad_geojson_geometry() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"filterableAttributes": ["_geo"]})).await;
+    let documents = json!([
+        {
+            "id": 1,
+            "_geo": { "type": "Polygon", "coordinates": [[[2.9, 50.5], [3.2, 50.5]]] },
+        }
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = index.get_task(1).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["status"], @r###""failed""###);
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The `_geo` field in the document with the id: `1` is not a valid GeoJSON geometry: a polygon ring must contain at least three positions.",
+      "code": "invalid_document_geo_field",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_geo_field"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn geo_sort_with_multiple_points() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({"sortableAttributes": ["_geo"]})).await;
+    let documents = json!([
+        {
+            "id": 1,
+            "name": "Restaurant chain",
+            "_geo": [{ "lat": 48.8566, "lng": 2.3522 }, { "lat": 50.6299, "lng": 3.0569 }],
+        },
+        {
+            "id": 2,
+            "name": "Brussels restaurant",
+            "_geo": { "lat": 50.8466, "lng": 4.3370 },
+        }
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    // The restaurant in Lille is the nearest point of the chain.
+    let query = json!({
+        "sort": ["_geoPoint(50.6299, 3.0569):asc"],
+        "attributesToRetrieve": ["id", "_geo"],
+    });
+    index
+        .search(query, |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(response["hits"][0]["id"], @"1");
+            snapshot!(response["hits"][0]["_geoDistance"], @"0");
+            snapshot!(response["hits"][1]["id"], @"2");
+        })
+        .await;
+
+    let query = json!({
+        "sort": ["_geoPoint(50.8466, 4.3370):asc"],
+        "attributesToRetrieve": ["id", "_geo"],
+    });
+    index
+        .search(query, |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(response["hits"][0]["id"], @"2");
+            snapshot!(response["hits"][0]["_geoDistance"], @"0");
+            snapshot!(response["hits"][1]["id"], @"1");
+        })
+        .await;
+}
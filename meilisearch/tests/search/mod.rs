@@ -1,9 +1,15 @@
 // This modules contains all the test concerning search. Each particular feature of the search
 // should be tested in its own module to isolate tests and keep the tests readable.
 
+mod distinct;
 mod errors;
+mod facet_search;
 mod formatted;
+mod geo;
+mod multi;
 mod pagination;
+mod restrict_searchable;
+mod vector;
 
 use once_cell::sync::Lazy;
 use serde_json::{json, Value};
@@ -641,6 +647,69 @@ async fn search_is_hard_limited() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_facet_stats() {
+    let server = Server::new().await;
+    let index = server.index("nested");
+
+    index.update_settings(json!({"filterableAttributes": ["father", "doggos.age"]})).await;
+
+    let documents = NESTED_DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({
+                "facets": ["father", "doggos.age"]
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response["facetStats"]), @r###"
+                {
+                  "doggos.age": {
+                    "min": 2.0,
+                    "max": 8.0
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "facets": ["father", "doggos.age"],
+                "filter": "father = jean"
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response["facetStats"]), @r###"
+                {
+                  "doggos.age": {
+                    "min": 2.0,
+                    "max": 4.0
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "facets": ["father"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                meili_snap::snapshot!(meili_snap::json_string!(response["facetStats"]), @"{}");
+            },
+        )
+        .await;
+}
+
 #[actix_rt::test]
 async fn faceting_max_values_per_facet() {
     let server = Server::new().await;
@@ -681,3 +750,170 @@ async fn faceting_max_values_per_facet() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn faceting_sort_facet_values_by() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.update_settings(json!({ "filterableAttributes": ["number", "colour"] })).await;
+
+    let colours = ["red", "red", "red", "blue", "blue", "green"];
+    let documents: Vec<_> = (0..600)
+        .map(|id| json!({ "id": id, "number": id % 6, "colour": colours[id % 6] }))
+        .collect();
+    index.add_documents(json!(documents), None).await;
+    index.wait_task(1).await;
+
+    index
+        .update_settings(json!({
+            "faceting": { "maxValuesPerFacet": 2, "sortFacetValuesBy": { "*": "count", "number": "alpha" } }
+        }))
+        .await;
+    index.wait_task(2).await;
+
+    index
+        .search(json!({ "facets": ["number", "colour"] }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            meili_snap::snapshot!(meili_snap::json_string!(response["facetDistribution"]), @r###"
+            {
+              "colour": {
+                "red": 300,
+                "blue": 200
+              },
+              "number": {
+                "0": 100,
+                "1": 100
+              }
+            }
+            "###);
+        })
+        .await;
+
+    let (response, _) = index.settings().await;
+    meili_snap::snapshot!(meili_snap::json_string!(response["faceting"]), @r###"
+    {
+      "maxValuesPerFacet": 2,
+      "sortFacetValuesBy": {
+        "*": "count",
+        "number": "alpha"
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_with_ranking_score() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(json!({ "showRankingScore": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 5);
+            for hit in hits {
+                assert_eq!(hit["_rankingScore"], json!(1.0), "{}", hit);
+                assert!(hit.get("_rankingScoreDetails").is_none(), "{}", hit);
+            }
+        })
+        .await;
+
+    index
+        .search(json!({ "q": "glass", "showRankingScoreDetails": true }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            let hits = response["hits"].as_array().unwrap();
+            assert_eq!(hits.len(), 1);
+            assert!(hits[0].get("_rankingScore").is_none(), "{}", response);
+            let details = &hits[0]["_rankingScoreDetails"];
+            assert_eq!(details["words"]["order"], json!(0), "{}", details);
+            assert_eq!(details["words"]["matchingWords"], json!(1), "{}", details);
+            assert_eq!(details["words"]["maxMatchingWords"], json!(1), "{}", details);
+            assert_eq!(details["typo"]["order"], json!(1), "{}", details);
+            assert_eq!(details["typo"]["typoCount"], json!(0), "{}", details);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_ranking_score_threshold() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    // only one of the two words match, so the score of `Glass` is at most `0.5`.
+    index
+        .search(
+            json!({ "q": "glass captain", "matchingStrategy": "last", "showRankingScore": true }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                let hits = response["hits"].as_array().unwrap();
+                assert_eq!(hits.len(), 1, "{}", response);
+                assert_eq!(hits[0]["id"], json!("450465"), "{}", response);
+                assert!(hits[0]["_rankingScore"].as_f64().unwrap() <= 0.5, "{}", response);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({ "q": "glass captain", "matchingStrategy": "last", "rankingScoreThreshold": 0.6 }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                assert_eq!(response["hits"], json!([]), "{}", response);
+                assert_eq!(response["estimatedTotalHits"], json!(0), "{}", response);
+            },
+        )
+        .await;
+
+    index
+        .search(json!({ "rankingScoreThreshold": 1.0 }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 5, "{}", response);
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_search_cutoff_ms() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    index
+        .search(json!({ "q": "glass" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response.get("degraded"), None, "{}", response);
+        })
+        .await;
+
+    // the budget is exhausted before the proximity criterion, the hits are still returned.
+    index
+        .search(json!({ "q": "glass", "searchCutoffMs": 0 }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["hits"].as_array().unwrap().len(), 1, "{}", response);
+            assert_eq!(response["degraded"], json!(true), "{}", response);
+        })
+        .await;
+
+    // without the query parameter the setting of the index is used.
+    index.update_settings(json!({ "searchCutoffMs": 0 })).await;
+    index.wait_task(1).await;
+
+    index
+        .search(json!({ "q": "glass" }), |response, code| {
+            assert_eq!(code, 200, "{}", response);
+            assert_eq!(response["degraded"], json!(true), "{}", response);
+        })
+        .await;
+}
@@ -0,0 +1,411 @@
+use meili_snap::{json_string, snapshot};
+use serde_json::json;
+
+use super::{DOCUMENTS, NESTED_DOCUMENTS};
+use crate::common::Server;
+
+#[actix_rt::test]
+async fn search_empty_list() {
+    let server = Server::new().await;
+
+    let (response, code) = server.multi_search(json!({"queries": []})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "results": []
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_json_object() {
+    let server = Server::new().await;
+
+    let (response, code) = server.multi_search(json!({})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `queries`",
+      "code": "bad_request",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#bad_request"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_json_array() {
+    let server = Server::new().await;
+
+    let (response, code) = server.multi_search(json!([])).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type: expected an object, but found an array: `[]`",
+      "code": "bad_request",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#bad_request"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn simple_search_single_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid": "test", "q": "captain"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+
+    let results = response["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["indexUid"], "test");
+    assert_eq!(results[0]["query"], "glass");
+    assert_eq!(results[0]["hits"].as_array().unwrap().len(), 1);
+    assert_eq!(results[0]["hits"][0]["id"], "450465");
+    assert_eq!(results[1]["indexUid"], "test");
+    assert_eq!(results[1]["query"], "captain");
+    assert_eq!(results[1]["hits"].as_array().unwrap().len(), 1);
+    assert_eq!(results[1]["hits"][0]["id"], "299537");
+}
+
+#[actix_rt::test]
+async fn simple_search_missing_index_uid() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+        {"q": "glass"},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Missing field `indexUid` inside `.queries[0]`",
+      "code": "missing_index_uid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#missing_index_uid"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn simple_search_illegal_index_uid() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+        {"indexUid": "hé", "q": "glass"},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.queries[0].indexUid`: `hé` is not a valid index uid. Index uid can be an integer or a string containing only alphanumeric characters, hyphens (-) and underscores (_).",
+      "code": "invalid_index_uid",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_index_uid"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn simple_search_two_indexes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let index = server.index("nested");
+    let documents = NESTED_DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid": "nested", "q": "pesti"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+
+    let results = response["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["indexUid"], "test");
+    assert_eq!(results[0]["hits"].as_array().unwrap().len(), 1);
+    assert_eq!(results[1]["indexUid"], "nested");
+    assert_eq!(results[1]["query"], "pesti");
+    assert_eq!(results[1]["hits"].as_array().unwrap().len(), 2);
+}
+
+#[actix_rt::test]
+async fn search_one_index_doesnt_exist() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid": "nested", "q": "pesti"},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[1]`: Index `nested` not found.",
+      "code": "index_not_found",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#index_not_found"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_one_query_error() {
+    let server = Server::new().await;
+
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let index = server.index("nested");
+    let documents = NESTED_DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+        {"indexUid" : "test", "q": "glass", "facets": ["title"]},
+        {"indexUid": "nested", "q": "pesti"},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    assert_eq!(response["code"], "invalid_search_facets", "{}", response);
+    assert!(
+        response["message"].as_str().unwrap().starts_with("Inside `.queries[0]`: "),
+        "{}",
+        response
+    );
+}
+
+#[actix_rt::test]
+async fn federation_single_index() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid": "test", "q": "captain"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+
+    let hits = response["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 2, "{}", response);
+    let mut ids: Vec<_> = hits.iter().map(|hit| hit["id"].as_str().unwrap()).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, vec!["299537", "450465"]);
+    for hit in hits {
+        assert_eq!(hit["_federation"]["indexUid"], "test");
+    }
+    assert_eq!(response["limit"], 20);
+    assert_eq!(response["offset"], 0);
+    assert_eq!(response["estimatedTotalHits"], 2);
+    assert!(response.get("results").is_none());
+}
+
+#[actix_rt::test]
+async fn federation_two_indexes_with_weights() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let index = server.index("nested");
+    let documents = NESTED_DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "federationOptions": {"weight": 0.0}},
+        {"indexUid": "nested", "q": "pesti"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+
+    // the hits of the first query have a weighted ranking score of 0, so they come last.
+    let hits = response["hits"].as_array().unwrap();
+    assert_eq!(hits.len(), 3, "{}", response);
+    assert_eq!(hits[0]["_federation"]["indexUid"], "nested");
+    assert_eq!(hits[0]["_federation"]["queriesPosition"], 1);
+    assert_eq!(hits[1]["_federation"]["indexUid"], "nested");
+    assert_eq!(hits[2]["_federation"]["indexUid"], "test");
+    assert_eq!(hits[2]["_federation"]["queriesPosition"], 0);
+    assert_eq!(hits[2]["_federation"]["weightedRankingScore"], 0.0);
+    assert_eq!(hits[2]["id"], "450465");
+    assert_eq!(response["estimatedTotalHits"], 3);
+
+    // the pagination is applied on the merged hits.
+    let (response, code) = server
+        .multi_search(json!({"federation": {"offset": 1, "limit": 1}, "queries": [
+        {"indexUid" : "test", "q": "glass", "federationOptions": {"weight": 0.0}},
+        {"indexUid": "nested", "q": "pesti"},
+        ]}))
+        .await;
+    snapshot!(code, @"200 OK");
+
+    let page = response["hits"].as_array().unwrap();
+    assert_eq!(page.len(), 1, "{}", response);
+    assert_eq!(page[0], hits[1]);
+    assert_eq!(response["limit"], 1);
+    assert_eq!(response["offset"], 1);
+    assert_eq!(response["estimatedTotalHits"], 3);
+}
+
+#[actix_rt::test]
+async fn federation_options_in_non_federated_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .multi_search(json!({"queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid": "test", "q": "captain", "federationOptions": {"weight": 2.0}},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[1]`: Using `federationOptions` is not allowed in a non-federated search. Hint: remove `federationOptions` from the query or add `federation` to the request.",
+      "code": "invalid_multi_search_federation_options",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_federation_options"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn federation_invalid_queries() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid": "test", "q": "captain", "page": 2},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[1]`: Using pagination options is not allowed in federated queries. Hint: remove `page` from the query or remove `federation` from the request.",
+      "code": "invalid_multi_search_query_pagination",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_pagination"
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "offset": 0},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[0]`: Using pagination options is not allowed in federated queries. Hint: remove `offset` from the query or remove `federation` from the request.",
+      "code": "invalid_multi_search_query_pagination",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_pagination"
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass"},
+        {"indexUid": "test", "q": "captain", "limit": 5},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[1]`: Using pagination options is not allowed in federated queries. Hint: remove `limit` from the query or remove `federation` from the request.",
+      "code": "invalid_multi_search_query_pagination",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_pagination"
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "facets": ["title"]},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[0]`: Using facets is not allowed in federated queries. Hint: remove `facets` from the query or remove `federation` from the request.",
+      "code": "invalid_multi_search_query_facets",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_query_facets"
+    }
+    "###);
+
+    let (response, code) = server
+        .multi_search(json!({"federation": {}, "queries": [
+        {"indexUid" : "test", "q": "glass", "federationOptions": {"weight": -1.0}},
+        ]}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Inside `.queries[0]`: The federation weight must be a positive number, but `-1` was given.",
+      "code": "invalid_multi_search_weight",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_multi_search_weight"
+    }
+    "###);
+}
@@ -0,0 +1,168 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+
+use crate::common::index::Index;
+use crate::common::Server;
+
+async fn index_with_documents<'a>(server: &'a Server, documents: &Value) -> Index<'a> {
+    let index = server.index("test");
+
+    index.add_documents(documents.clone(), None).await;
+    index.wait_task(0).await;
+    index
+}
+
+static VECTOR_DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+    {
+        "id": 1,
+        "title": "up",
+        "_vectors": [0.0, 1.0],
+    },
+    {
+        "id": 2,
+        "title": "right",
+        "_vectors": [[1.0, 0.1], [-1.0, -1.0]],
+    },
+    {
+        "id": 3,
+        "title": "diagonal",
+        "_vectors": [1.0, 1.0],
+    },
+    {
+        "id": 4,
+        "title": "nowhere",
+    }])
+});
+
+#[actix_rt::test]
+async fn simple_vector_search() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &VECTOR_DOCUMENTS).await;
+
+    index
+        .search(json!({"vector": [1.0, 0.0], "attributesToRetrieve": ["id"]}), |response, code| {
+            snapshot!(code, @"200 OK");
+            snapshot!(json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2
+              },
+              {
+                "id": 3
+              },
+              {
+                "id": 1
+              }
+            ]
+            "###);
+            snapshot!(response["estimatedTotalHits"], @"3");
+        })
+        .await;
+}
+
+#[actix_rt::test]
+async fn vector_search_with_filter() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &VECTOR_DOCUMENTS).await;
+
+    let (_, code) = index.update_settings(json!({ "filterableAttributes": ["title"] })).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(1).await;
+
+    index
+        .search(
+            json!({"vector": [1.0, 0.0], "filter": "title != right", "attributesToRetrieve": ["id"]}),
+            |response, code| {
+                snapshot!(code, @"200 OK");
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 3
+                  },
+                  {
+                    "id": 1
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn vector_search_bad_dimensions() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &VECTOR_DOCUMENTS).await;
+
+    let (response, code) = index.search_post(json!({"vector": [1.0, 0.0, 0.0]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid vector dimensions: expected: `2`, found: `3`.",
+      "code": "invalid_vector_dimensions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_vector_dimensions"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn vector_search_with_sort() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server, &VECTOR_DOCUMENTS).await;
+
+    let (_, code) = index.update_settings(json!({ "sortableAttributes": ["title"] })).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(1).await;
+
+    let (response, code) =
+        index.search_post(json!({"vector": [1.0, 0.0], "sort": ["title:asc"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The sort parameter cannot be used along with the vector parameter, the documents are sorted by their similarity with the vector.",
+      "code": "invalid_search_sort",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_sort"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn add_documents_invalid_vectors() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    index.add_documents(json!([{ "id": 1, "_vectors": "foobar" }]), None).await;
+    index.wait_task(0).await;
+    let (response, code) = index.get_task(0).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The `_vectors` field in the document with the id: `1` is not an array. Was expecting an array of floats or an array of arrays of floats but instead got `\"foobar\"`.",
+      "code": "invalid_vectors_type",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_vectors_type"
+    }
+    "###);
+
+    index
+        .add_documents(
+            json!([{ "id": 1, "_vectors": [1.0] }, { "id": 2, "_vectors": [1.0, 0.0] }]),
+            None,
+        )
+        .await;
+    index.wait_task(1).await;
+    let (response, code) = index.get_task(1).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Invalid vector dimensions: expected: `1`, found: `2`.",
+      "code": "invalid_vector_dimensions",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_vector_dimensions"
+    }
+    "###);
+}
@@ -171,6 +171,81 @@ async fn settings_bad_stop_words() {
     "###);
 }
 
+#[actix_rt::test]
+async fn settings_bad_separator_tokens() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "separatorTokens": "&" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.separatorTokens`: expected an array, but found a string: `\"&\"`",
+      "code": "invalid_settings_separator_tokens",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_separator_tokens"
+    }
+    "###);
+
+    let (response, code) = index.update_settings(json!({ "nonSeparatorTokens": "#" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.nonSeparatorTokens`: expected an array, but found a string: `\"#\"`",
+      "code": "invalid_settings_non_separator_tokens",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_non_separator_tokens"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_dictionary() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "dictionary": "J. R. R. Tolkien" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.dictionary`: expected an array, but found a string: `\"J. R. R. Tolkien\"`",
+      "code": "invalid_settings_dictionary",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_dictionary"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_localized_attributes() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "localizedAttributes": "jpn" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.localizedAttributes`: expected an array, but found a string: `\"jpn\"`",
+      "code": "invalid_settings_localized_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_localized_attributes"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "localizedAttributes": [{ "attributePatterns": ["title_ja"], "locales": ["japanese"] }] }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `japanese` at `.localizedAttributes[0].locales[0]`: expected one of `ara`, `cmn`, `dan`, `deu`, `ell`, `eng`, `fra`, `heb`, `hin`, `ita`, `jpn`, `kor`, `nld`, `pol`, `por`, `rus`, `spa`, `swe`, `tha`, `tur`, `ukr`, `vie`",
+      "code": "invalid_settings_localized_attributes",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_localized_attributes"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn settings_bad_synonyms() {
     let server = Server::new().await;
@@ -227,6 +302,57 @@ async fn settings_bad_distinct_attribute() {
     "###);
 }
 
+#[actix_rt::test]
+async fn settings_bad_proximity_precision() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "proximityPrecision": "byDoggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `byDoggo` at `.proximityPrecision`: expected one of `byWord`, `byAttribute`",
+      "code": "invalid_settings_proximity_precision",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_proximity_precision"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_prefix_search() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "prefixSearch": "atQueryTime" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `atQueryTime` at `.prefixSearch`: expected one of `indexingTime`, `disabled`",
+      "code": "invalid_settings_prefix_search",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_prefix_search"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn settings_bad_search_cutoff_ms() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index.update_settings(json!({ "searchCutoffMs": "doggo" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.searchCutoffMs`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_settings_search_cutoff_ms",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_search_cutoff_ms"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn settings_bad_typo_tolerance() {
     let server = Server::new().await;
@@ -308,6 +434,18 @@ async fn settings_bad_faceting() {
       "link": "https://docs.meilisearch.com/errors#invalid_settings_faceting"
     }
     "###);
+
+    let (response, code) =
+        index.update_settings_faceting(json!({ "sortFacetValuesBy": { "genres": "doggo" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` at `.sortFacetValuesBy.genres`: expected one of `alpha`, `count`",
+      "code": "invalid_settings_faceting",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_faceting"
+    }
+    "###);
 }
 
 #[actix_rt::test]
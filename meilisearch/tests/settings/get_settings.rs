@@ -11,16 +11,24 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
     map.insert("searchable_attributes", json!(["*"]));
     map.insert("filterable_attributes", json!([]));
     map.insert("distinct_attribute", json!(Value::Null));
+    map.insert("proximity_precision", json!("byWord"));
     map.insert(
         "ranking_rules",
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"]),
     );
     map.insert("stop_words", json!([]));
+    map.insert("non_separator_tokens", json!([]));
+    map.insert("separator_tokens", json!([]));
+    map.insert("dictionary", json!([]));
+    map.insert("localized_attributes", json!(Value::Null));
     map.insert("synonyms", json!({}));
     map.insert(
         "faceting",
         json!({
             "maxValuesPerFacet": json!(100),
+            "sortFacetValuesBy": {
+                "*": "alpha"
+            }
         }),
     );
     map.insert(
@@ -29,6 +37,8 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
             "maxTotalHits": json!(1000),
         }),
     );
+    map.insert("search_cutoff_ms", json!(Value::Null));
+    map.insert("prefix_search", json!("indexingTime"));
     map
 });
 
@@ -48,21 +58,29 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 11);
+    assert_eq!(settings.keys().len(), 18);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
     assert_eq!(settings["sortableAttributes"], json!([]));
     assert_eq!(settings["distinctAttribute"], json!(null));
+    assert_eq!(settings["proximityPrecision"], json!("byWord"));
     assert_eq!(
         settings["rankingRules"],
         json!(["words", "typo", "proximity", "attribute", "sort", "exactness"])
     );
     assert_eq!(settings["stopWords"], json!([]));
+    assert_eq!(settings["nonSeparatorTokens"], json!([]));
+    assert_eq!(settings["separatorTokens"], json!([]));
+    assert_eq!(settings["dictionary"], json!([]));
+    assert_eq!(settings["localizedAttributes"], json!(null));
     assert_eq!(
         settings["faceting"],
         json!({
             "maxValuesPerFacet": 100,
+            "sortFacetValuesBy": {
+                "*": "alpha"
+            }
         })
     );
     assert_eq!(
@@ -71,6 +89,8 @@ async fn get_settings() {
             "maxTotalHits": 1000,
         })
     );
+    assert_eq!(settings["searchCutoffMs"], json!(null));
+    assert_eq!(settings["prefixSearch"], json!("indexingTime"));
 }
 
 #[actix_rt::test]
@@ -265,11 +285,18 @@ test_setting_routes!(
     displayed_attributes put,
     searchable_attributes put,
     distinct_attribute put,
+    proximity_precision put,
     stop_words put,
+    non_separator_tokens put,
+    separator_tokens put,
+    dictionary put,
+    localized_attributes put,
     ranking_rules put,
     synonyms put,
     pagination patch,
-    faceting patch
+    faceting patch,
+    search_cutoff_ms put,
+    prefix_search put
 );
 
 #[actix_rt::test]
@@ -24,6 +24,7 @@ async fn swap_indexes() {
       "results": [
         {
           "uid": 1,
+          "batchUid": 1,
           "indexUid": "b",
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
@@ -40,6 +41,7 @@ async fn swap_indexes() {
         },
         {
           "uid": 0,
+          "batchUid": 0,
           "indexUid": "a",
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
@@ -75,6 +77,7 @@ async fn swap_indexes() {
       "results": [
         {
           "uid": 2,
+          "batchUid": 2,
           "indexUid": null,
           "status": "succeeded",
           "type": "indexSwap",
@@ -97,6 +100,7 @@ async fn swap_indexes() {
         },
         {
           "uid": 1,
+          "batchUid": 1,
           "indexUid": "a",
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
@@ -113,6 +117,7 @@ async fn swap_indexes() {
         },
         {
           "uid": 0,
+          "batchUid": 0,
           "indexUid": "b",
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
@@ -162,6 +167,7 @@ async fn swap_indexes() {
       "results": [
         {
           "uid": 4,
+          "batchUid": 4,
           "indexUid": "d",
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
@@ -178,6 +184,7 @@ async fn swap_indexes() {
         },
         {
           "uid": 3,
+          "batchUid": 3,
           "indexUid": "c",
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
@@ -223,6 +230,7 @@ async fn swap_indexes() {
       "results": [
         {
           "uid": 5,
+          "batchUid": 5,
           "indexUid": null,
           "status": "succeeded",
           "type": "indexSwap",
@@ -251,6 +259,7 @@ async fn swap_indexes() {
         },
         {
           "uid": 4,
+          "batchUid": 4,
           "indexUid": "c",
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
@@ -267,6 +276,7 @@ async fn swap_indexes() {
         },
         {
           "uid": 3,
+          "batchUid": 3,
           "indexUid": "d",
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
@@ -283,6 +293,7 @@ async fn swap_indexes() {
         },
         {
           "uid": 2,
+          "batchUid": 2,
           "indexUid": null,
           "status": "succeeded",
           "type": "indexSwap",
@@ -305,6 +316,7 @@ async fn swap_indexes() {
         },
         {
           "uid": 1,
+          "batchUid": 1,
           "indexUid": "b",
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
@@ -321,6 +333,7 @@ async fn swap_indexes() {
         },
         {
           "uid": 0,
+          "batchUid": 0,
           "indexUid": "a",
           "status": "succeeded",
           "type": "documentAdditionOrUpdate",
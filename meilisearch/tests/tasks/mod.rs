@@ -1,4 +1,6 @@
 mod errors;
+mod stream;
+mod webhook;
 
 use meili_snap::insta::assert_json_snapshot;
 use serde_json::json;
@@ -371,6 +373,7 @@ async fn test_summarized_document_addition_or_update() {
         @r###"
     {
       "uid": 0,
+      "batchUid": 0,
       "indexUid": "test",
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
@@ -395,6 +398,7 @@ async fn test_summarized_document_addition_or_update() {
         @r###"
     {
       "uid": 1,
+      "batchUid": 1,
       "indexUid": "test",
       "status": "succeeded",
       "type": "documentAdditionOrUpdate",
@@ -424,6 +428,7 @@ async fn test_summarized_delete_batch() {
         @r###"
     {
       "uid": 0,
+      "batchUid": 0,
       "indexUid": "test",
       "status": "failed",
       "type": "documentDeletion",
@@ -454,6 +459,7 @@ async fn test_summarized_delete_batch() {
         @r###"
     {
       "uid": 2,
+      "batchUid": 2,
       "indexUid": "test",
       "status": "succeeded",
       "type": "documentDeletion",
@@ -483,6 +489,7 @@ async fn test_summarized_delete_document() {
         @r###"
     {
       "uid": 0,
+      "batchUid": 0,
       "indexUid": "test",
       "status": "failed",
       "type": "documentDeletion",
@@ -513,6 +520,7 @@ async fn test_summarized_delete_document() {
         @r###"
     {
       "uid": 2,
+      "batchUid": 2,
       "indexUid": "test",
       "status": "succeeded",
       "type": "documentDeletion",
@@ -554,6 +562,7 @@ async fn test_summarized_settings_update() {
         @r###"
     {
       "uid": 0,
+      "batchUid": 0,
       "indexUid": "test",
       "status": "succeeded",
       "type": "settingsUpdate",
@@ -592,6 +601,7 @@ async fn test_summarized_index_creation() {
         @r###"
     {
       "uid": 0,
+      "batchUid": 0,
       "indexUid": "test",
       "status": "succeeded",
       "type": "indexCreation",
@@ -615,6 +625,7 @@ async fn test_summarized_index_creation() {
         @r###"
     {
       "uid": 1,
+      "batchUid": 1,
       "indexUid": "test",
       "status": "failed",
       "type": "indexCreation",
@@ -648,6 +659,7 @@ async fn test_summarized_index_deletion() {
         @r###"
     {
       "uid": 0,
+      "batchUid": 0,
       "indexUid": "test",
       "status": "failed",
       "type": "indexDeletion",
@@ -678,6 +690,7 @@ async fn test_summarized_index_deletion() {
         @r###"
     {
       "uid": 2,
+      "batchUid": 2,
       "indexUid": "test",
       "status": "succeeded",
       "type": "indexDeletion",
@@ -702,6 +715,7 @@ async fn test_summarized_index_deletion() {
         @r###"
     {
       "uid": 2,
+      "batchUid": 2,
       "indexUid": "test",
       "status": "succeeded",
       "type": "indexDeletion",
@@ -731,6 +745,7 @@ async fn test_summarized_index_update() {
         @r###"
     {
       "uid": 0,
+      "batchUid": 0,
       "indexUid": "test",
       "status": "failed",
       "type": "indexUpdate",
@@ -759,6 +774,7 @@ async fn test_summarized_index_update() {
         @r###"
     {
       "uid": 1,
+      "batchUid": 1,
       "indexUid": "test",
       "status": "failed",
       "type": "indexUpdate",
@@ -790,6 +806,7 @@ async fn test_summarized_index_update() {
         @r###"
     {
       "uid": 3,
+      "batchUid": 3,
       "indexUid": "test",
       "status": "succeeded",
       "type": "indexUpdate",
@@ -813,6 +830,7 @@ async fn test_summarized_index_update() {
         @r###"
     {
       "uid": 4,
+      "batchUid": 4,
       "indexUid": "test",
       "status": "succeeded",
       "type": "indexUpdate",
@@ -844,6 +862,7 @@ async fn test_summarized_index_swap() {
         @r###"
     {
       "uid": 0,
+      "batchUid": 0,
       "indexUid": null,
       "status": "failed",
       "type": "indexSwap",
@@ -885,6 +904,7 @@ async fn test_summarized_index_swap() {
         @r###"
     {
       "uid": 3,
+      "batchUid": 3,
       "indexUid": null,
       "status": "succeeded",
       "type": "indexSwap",
@@ -923,6 +943,7 @@ async fn test_summarized_task_cancelation() {
         @r###"
     {
       "uid": 1,
+      "batchUid": 1,
       "indexUid": null,
       "status": "succeeded",
       "type": "taskCancelation",
@@ -956,6 +977,7 @@ async fn test_summarized_task_deletion() {
         @r###"
     {
       "uid": 1,
+      "batchUid": 1,
       "indexUid": null,
       "status": "succeeded",
       "type": "taskDeletion",
@@ -985,6 +1007,7 @@ async fn test_summarized_dump_creation() {
         @r###"
     {
       "uid": 0,
+      "batchUid": 0,
       "indexUid": null,
       "status": "succeeded",
       "type": "dumpCreation",
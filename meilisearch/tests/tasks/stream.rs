@@ -0,0 +1,133 @@
+use std::pin::Pin;
+use std::time::Duration;
+
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::web::Bytes;
+use futures::future::poll_fn;
+use meili_snap::*;
+use serde_json::{json, Value};
+
+use crate::common::Server;
+
+/// Wait for the next event sent by the stream, `None` if the stream ended.
+async fn next_event(stream: &mut BoxBody) -> Option<Bytes> {
+    tokio::time::timeout(
+        Duration::from_secs(10),
+        poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)),
+    )
+    .await
+    .expect("no event was streamed")
+    .map(Result::unwrap)
+}
+
+/// Wait for the next task sent by the stream.
+async fn next_task(stream: &mut BoxBody) -> Value {
+    let event = next_event(stream).await.expect("the stream ended");
+    let event = std::str::from_utf8(&event).unwrap();
+    let data = event.strip_prefix("data: ").unwrap().strip_suffix("\n\n").unwrap();
+    serde_json::from_str(data).unwrap()
+}
+
+#[actix_rt::test]
+async fn stream_the_status_changes_of_the_tasks() {
+    let server = Server::new().await;
+    let (mut stream, code) = server.service.get_stream("/tasks/stream?indexUids=dog").await;
+    snapshot!(code, @"200 OK");
+
+    server.index("cat").create(None).await;
+    let index = server.index("dog");
+    index.add_documents(json!([{ "id": 1, "doggo": "bork" }]), None).await;
+    index.wait_task(1).await;
+
+    // the tasks of the `cat` index are filtered out.
+    for status in ["enqueued", "processing", "succeeded"] {
+        let task = next_task(&mut stream).await;
+        assert_eq!(task["uid"], json!(1));
+        assert_eq!(task["indexUid"], json!("dog"));
+        assert_eq!(task["type"], json!("documentAdditionOrUpdate"));
+        assert_eq!(task["status"], json!(status));
+    }
+}
+
+#[actix_rt::test]
+async fn stream_only_the_tasks_of_the_authorized_indexes() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+
+    let content = json!({
+        "indexes": ["dog"],
+        "actions": ["tasks.get"],
+        "expiresAt": null,
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", &response);
+    let key = response["key"].as_str().unwrap().to_string();
+
+    server.use_api_key(key);
+    let (mut stream, code) = server.service.get_stream("/tasks/stream?statuses=succeeded").await;
+    snapshot!(code, @"200 OK");
+
+    server.use_api_key("MASTER_KEY");
+    server.index("cat").create(None).await;
+    server.index("dog").create(None).await;
+    server.index("dog").wait_task(1).await;
+
+    let task = next_task(&mut stream).await;
+    assert_eq!(task["uid"], json!(1));
+    assert_eq!(task["indexUid"], json!("dog"));
+    assert_eq!(task["status"], json!("succeeded"));
+}
+
+#[actix_rt::test]
+async fn close_the_stream_once_the_key_is_deleted() {
+    let mut server = Server::new_auth().await;
+    server.use_admin_key("MASTER_KEY").await;
+
+    let content = json!({
+        "indexes": ["*"],
+        "actions": ["tasks.get"],
+        "expiresAt": null,
+    });
+    let (response, code) = server.add_api_key(content).await;
+    assert_eq!(201, code, "{:?}", &response);
+    let key = response["key"].as_str().unwrap().to_string();
+
+    server.use_api_key(&key);
+    let (mut stream, code) = server.service.get_stream("/tasks/stream").await;
+    snapshot!(code, @"200 OK");
+
+    server.use_api_key("MASTER_KEY");
+    let (response, code) = server.delete_api_key(&key).await;
+    assert_eq!(204, code, "{:?}", &response);
+
+    // the key is checked again before sending the update of the task.
+    server.index("dog").create(None).await;
+    assert!(next_event(&mut stream).await.is_none());
+}
+
+#[actix_rt::test]
+async fn stream_bad_parameters() {
+    let server = Server::new().await;
+
+    let (response, code) = server.service.get("/tasks/stream?statuses=doggo").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `statuses`: `doggo` is not a valid task status. Available statuses are `enqueued`, `processing`, `succeeded`, `failed`, `canceled`.",
+      "code": "invalid_task_statuses",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_task_statuses"
+    }
+    "###);
+
+    let (response, code) = server.service.get("/tasks/stream?limit=10").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown parameter `limit`: expected one of `types`, `statuses`, `indexUids`",
+      "code": "bad_request",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#bad_request"
+    }
+    "###);
+}
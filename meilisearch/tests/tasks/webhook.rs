@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use actix_http::header::AUTHORIZATION;
+use actix_web::dev::ServerHandle;
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer};
+use meilisearch::Opt;
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+use crate::common::{default_settings, Server};
+
+/// The authorization header and the body of a request received by the webhook.
+type Notification = (Option<String>, web::Bytes);
+
+struct WebhookHandle {
+    url: String,
+    server_handle: ServerHandle,
+    receiver: mpsc::UnboundedReceiver<Notification>,
+}
+
+async fn forward_notification(
+    req: HttpRequest,
+    body: web::Bytes,
+    sender: web::Data<mpsc::UnboundedSender<Notification>>,
+) -> HttpResponse {
+    let authorization =
+        req.headers().get(AUTHORIZATION).map(|value| value.to_str().unwrap().to_string());
+    sender.send((authorization, body)).unwrap();
+    HttpResponse::Ok().finish()
+}
+
+/// Start a local HTTP server forwarding the requests it receives.
+fn create_webhook_server() -> WebhookHandle {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let sender = web::Data::new(sender);
+
+    let server = HttpServer::new(move || {
+        App::new().app_data(sender.clone()).route("/", web::post().to(forward_notification))
+    })
+    .bind(("127.0.0.1", 0))
+    .unwrap();
+    let url = format!("http://{}/", server.addrs()[0]);
+    let server = server.workers(1).disable_signals().run();
+    let server_handle = server.handle();
+    actix_rt::spawn(server);
+
+    WebhookHandle { url, server_handle, receiver }
+}
+
+#[actix_rt::test]
+async fn webhook_is_notified_of_the_finished_tasks() {
+    let mut webhook = create_webhook_server();
+
+    let dir = tempfile::tempdir().unwrap();
+    let options = Opt {
+        task_webhook_url: Some(webhook.url.clone()),
+        task_webhook_authorization_header: Some("Bearer doggo".to_string()),
+        ..default_settings(dir.path())
+    };
+    let server = Server::new_with_options(options).await.unwrap();
+    let index = server.index("test");
+
+    index.add_documents(json!([{ "id": 1, "doggo": "bork" }]), None).await;
+    index.wait_task(0).await;
+    index.add_documents(json!([{ "doggo": "no id" }]), None).await;
+    index.wait_task(1).await;
+
+    let mut tasks = Vec::new();
+    while tasks.len() < 2 {
+        let (authorization, body) =
+            tokio::time::timeout(Duration::from_secs(10), webhook.receiver.recv())
+                .await
+                .expect("the webhook was not notified")
+                .unwrap();
+        assert_eq!(authorization.as_deref(), Some("Bearer doggo"));
+
+        // the tasks are sent as newline-delimited JSON.
+        for line in body.split(|byte| *byte == b'\n').filter(|line| !line.is_empty()) {
+            tasks.push(serde_json::from_slice::<Value>(line).unwrap());
+        }
+    }
+
+    assert_eq!(tasks[0]["uid"], json!(0));
+    assert_eq!(tasks[0]["indexUid"], json!("test"));
+    assert_eq!(tasks[0]["type"], json!("documentAdditionOrUpdate"));
+    assert_eq!(tasks[0]["status"], json!("succeeded"));
+    assert_eq!(tasks[1]["uid"], json!(1));
+    assert_eq!(tasks[1]["status"], json!("failed"));
+    assert_eq!(tasks[1]["error"]["code"], json!("index_primary_key_no_candidate_found"));
+
+    webhook.server_handle.stop(false).await;
+}
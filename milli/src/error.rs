@@ -101,15 +101,46 @@ A document identifier can be of type integer or string, \
 only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and underscores (_).", .document_id.to_string()
     )]
     InvalidDocumentId { document_id: Value },
+    #[error("Attribute `{}` is not filterable and thus, cannot be used as distinct attribute. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured filterable attributes.".to_string(),
+            false => format!("Available filterable attributes are: `{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")
+                ),
+        }
+    )]
+    InvalidDistinctAttribute { field: String, valid_fields: BTreeSet<String> },
     #[error("Invalid facet distribution, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
     InvalidFacetsDistribution {
         invalid_facets_name: BTreeSet<String>,
         valid_facets_name: BTreeSet<String>,
     },
+    #[error("Attribute `{}` is not filterable. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured filterable attributes.".to_string(),
+            false => format!("Available filterable attributes are: `{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", ")
+                ),
+        }
+    )]
+    InvalidFacetSearchFacetName { field: String, valid_fields: BTreeSet<String> },
     #[error(transparent)]
     InvalidGeoField(#[from] GeoError),
     #[error("{0}")]
     InvalidFilter(String),
+    #[error("Invalid syntax for the filter parameter: `expected {}, found: {1}`.", .0.join(", "))]
+    InvalidFilterExpression(&'static [&'static str], Value),
+    #[error("Attribute `{}` is not searchable. Available searchable attributes are: `{}`.",
+        .field,
+        .valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+    )]
+    InvalidSearchableAttribute { field: String, valid_fields: BTreeSet<String> },
+    #[error("The `_vectors` field in the document with the id: `{document_id}` is not an array. Was expecting an array of floats or an array of arrays of floats but instead got `{value}`.")]
+    InvalidVectorsType { document_id: Value, value: Value },
+    #[error("Invalid vector dimensions: expected: `{}`, found: `{}`.", .expected, .found)]
+    InvalidVectorDimensions { expected: usize, found: usize },
     #[error("Attribute `{}` is not sortable. {}",
         .field,
         match .valid_fields.is_empty() {
@@ -122,8 +153,12 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     InvalidSortableAttribute { field: String, valid_fields: BTreeSet<String> },
     #[error("{}", HeedError::BadOpenOptions)]
     InvalidLmdbOpenOptions,
+    #[error("The `_geoPoint` sort rule cannot be used when browsing documents.")]
+    InvalidDocumentsGeoSort,
     #[error("The sort ranking rule must be specified in the ranking rules settings to use the sort parameter at search time.")]
     SortRankingRuleMissing,
+    #[error("The sort parameter cannot be used along with the vector parameter, the documents are sorted by their similarity with the vector.")]
+    SortWithVector,
     #[error("The database file is in an invalid state.")]
     InvalidStoreFile,
     #[error("Maximum database size has been reached.")]
@@ -168,6 +203,10 @@ pub enum GeoError {
     BadLatitude { document_id: Value, value: Value },
     #[error("Could not parse longitude in the document with the id: `{document_id}`. Was expecting a finite number but instead got `{value}`.")]
     BadLongitude { document_id: Value, value: Value },
+    #[error("The `_geo` field in the document with the id: `{document_id}` is not a valid GeoJSON geometry: {error}.")]
+    BadGeoJson { document_id: Value, error: String },
+    #[error("The `_geo` field in the document with the id: `{document_id}` contains {count} points. A document cannot contain more than 65,536 points.")]
+    TooManyPoints { document_id: Value, count: usize },
 }
 
 fn format_invalid_filter_distribution(
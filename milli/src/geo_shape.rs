@@ -0,0 +1,327 @@
+use rstar::AABB;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::distance_between_two_points;
+
+/// The mean radius of the earth in meters, slightly rounded down to stay
+/// conservative when computing the envelope of a radius.
+const EARTH_RADIUS: f64 = 6_371_000.0;
+
+/// A GeoJSON geometry as it can be written in the `_geo` field of a document.
+///
+/// The positions are expressed as `[longitude, latitude]`, as described in the GeoJSON RFC.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", content = "coordinates")]
+enum GeoJsonGeometry {
+    Point([f64; 2]),
+    MultiPoint(Vec<[f64; 2]>),
+    LineString(Vec<[f64; 2]>),
+    MultiLineString(Vec<Vec<[f64; 2]>>),
+    Polygon(Vec<Vec<[f64; 2]>>),
+    MultiPolygon(Vec<Vec<Vec<[f64; 2]>>>),
+}
+
+/// The geometry of a document, all the positions are expressed as `[latitude, longitude]`.
+///
+/// A polygon is a list of rings, the first one is the exterior ring and the other ones are holes.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Geometry {
+    points: Vec<[f64; 2]>,
+    lines: Vec<Vec<[f64; 2]>>,
+    polygons: Vec<Vec<Vec<[f64; 2]>>>,
+}
+
+/// Returns `true` if the `_geo` value must be interpreted as a GeoJSON geometry.
+pub fn is_geojson(value: &Value) -> bool {
+    value.as_object().map_or(false, |object| object.contains_key("type"))
+}
+
+impl Geometry {
+    /// Parses a GeoJSON geometry, validates its coordinates and
+    /// converts the positions into `[latitude, longitude]`.
+    pub fn from_geojson(value: Value) -> Result<Geometry, String> {
+        let geojson: GeoJsonGeometry = serde_json::from_value(value).map_err(|e| e.to_string())?;
+
+        let mut geometry = Geometry::default();
+        match geojson {
+            GeoJsonGeometry::Point(position) => geometry.points.push(to_lat_lng(position)?),
+            GeoJsonGeometry::MultiPoint(positions) => {
+                geometry.points = positions.into_iter().map(to_lat_lng).collect::<Result<_, _>>()?
+            }
+            GeoJsonGeometry::LineString(line) => geometry.lines.push(to_line(line)?),
+            GeoJsonGeometry::MultiLineString(lines) => {
+                geometry.lines = lines.into_iter().map(to_line).collect::<Result<_, _>>()?
+            }
+            GeoJsonGeometry::Polygon(rings) => geometry.polygons.push(to_polygon(rings)?),
+            GeoJsonGeometry::MultiPolygon(polygons) => {
+                geometry.polygons =
+                    polygons.into_iter().map(to_polygon).collect::<Result<_, _>>()?
+            }
+        }
+
+        if geometry.positions().next().is_none() {
+            return Err(String::from("the geometry does not contain any position"));
+        }
+
+        Ok(geometry)
+    }
+
+    /// Returns the smallest rectangle, in terms of latitude and longitude, containing the geometry.
+    pub fn envelope(&self) -> AABB<[f64; 2]> {
+        let mut lower = [f64::INFINITY, f64::INFINITY];
+        let mut upper = [f64::NEG_INFINITY, f64::NEG_INFINITY];
+        for [lat, lng] in self.positions() {
+            lower = [lower[0].min(*lat), lower[1].min(*lng)];
+            upper = [upper[0].max(*lat), upper[1].max(*lng)];
+        }
+        AABB::from_corners(lower, upper)
+    }
+
+    /// Returns `true` if the point is covered by the geometry.
+    pub fn contains_point(&self, point: &[f64; 2]) -> bool {
+        self.points.iter().any(|p| p == point)
+            || self.segments().any(|(a, b)| closest_point_on_segment(point, a, b) == *point)
+            || self.polygons.iter().any(|rings| point_in_polygon_with_holes(point, rings))
+    }
+
+    /// Returns the distance in meters between the point and the closest part of the geometry.
+    pub fn distance_to_point(&self, point: &[f64; 2]) -> f64 {
+        if self.polygons.iter().any(|rings| point_in_polygon_with_holes(point, rings)) {
+            return 0.0;
+        }
+
+        let points = self.points.iter().copied();
+        let segments = self.segments().map(|(a, b)| closest_point_on_segment(point, a, b));
+        points
+            .chain(segments)
+            .map(|closest| distance_between_two_points(point, &closest))
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    /// Returns `true` if the geometry and the polygon share at least one point.
+    pub fn intersects_polygon(&self, polygon: &[[f64; 2]]) -> bool {
+        // a part of the geometry is inside the polygon
+        self.positions().any(|position| point_in_polygon(position, polygon))
+            // the polygon is inside one of the polygons of the geometry
+            || polygon.iter().any(|point| {
+                self.polygons.iter().any(|rings| point_in_polygon_with_holes(point, rings))
+            })
+            // the geometry and the polygon are crossing each other
+            || self.segments().any(|(a, b)| {
+                ring_segments(polygon).any(|(c, d)| segments_intersect(a, b, c, d))
+            })
+    }
+
+    fn positions(&self) -> impl Iterator<Item = &[f64; 2]> {
+        let lines = self.lines.iter().flatten();
+        let polygons = self.polygons.iter().flatten().flatten();
+        self.points.iter().chain(lines).chain(polygons)
+    }
+
+    fn segments(&self) -> impl Iterator<Item = (&[f64; 2], &[f64; 2])> {
+        let lines = self.lines.iter().flat_map(|line| line.windows(2).map(|w| (&w[0], &w[1])));
+        let rings = self.polygons.iter().flatten().flat_map(|ring| ring_segments(ring));
+        lines.chain(rings)
+    }
+}
+
+/// Returns the envelope, in terms of latitude and longitude, of all
+/// the points that are at most `radius` meters away from the `center`.
+pub fn radius_envelope(center: &[f64; 2], radius: f64) -> AABB<[f64; 2]> {
+    let [lat, lng] = *center;
+    let angular_radius = radius / EARTH_RADIUS;
+    let delta_lat = angular_radius.to_degrees();
+
+    let (min_lat, max_lat) = (lat - delta_lat, lat + delta_lat);
+    // if the circle contains one of the poles or crosses the antimeridian
+    // we can't restrict the longitude.
+    if min_lat <= -90.0 || max_lat >= 90.0 || angular_radius.sin() >= lat.to_radians().cos() {
+        return AABB::from_corners([min_lat.max(-90.0), -180.0], [max_lat.min(90.0), 180.0]);
+    }
+
+    let delta_lng = (angular_radius.sin() / lat.to_radians().cos()).asin().to_degrees();
+    let (min_lng, max_lng) = (lng - delta_lng, lng + delta_lng);
+    if min_lng < -180.0 || max_lng > 180.0 {
+        AABB::from_corners([min_lat, -180.0], [max_lat, 180.0])
+    } else {
+        AABB::from_corners([min_lat, min_lng], [max_lat, max_lng])
+    }
+}
+
+/// Returns `true` if the point is inside the polygon, the polygon doesn't need to be closed.
+pub fn point_in_polygon(point: &[f64; 2], polygon: &[[f64; 2]]) -> bool {
+    let [y, x] = *point;
+    let mut inside = false;
+    for (a, b) in ring_segments(polygon) {
+        let ([ay, ax], [by, bx]) = (*a, *b);
+        // the point is on an edge of the polygon
+        if closest_point_on_segment(point, a, b) == *point {
+            return true;
+        }
+        if (ay > y) != (by > y) && x < (bx - ax) * (y - ay) / (by - ay) + ax {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+fn point_in_polygon_with_holes(point: &[f64; 2], rings: &[Vec<[f64; 2]>]) -> bool {
+    match rings.split_first() {
+        Some((exterior, holes)) => {
+            point_in_polygon(point, exterior)
+                && !holes.iter().any(|hole| point_in_polygon(point, hole))
+        }
+        None => false,
+    }
+}
+
+/// Returns all the segments of a ring, including the one closing it.
+fn ring_segments(ring: &[[f64; 2]]) -> impl Iterator<Item = (&[f64; 2], &[f64; 2])> {
+    ring.iter().zip(ring.iter().cycle().skip(1)).filter(|(a, b)| a != b)
+}
+
+fn closest_point_on_segment(point: &[f64; 2], a: &[f64; 2], b: &[f64; 2]) -> [f64; 2] {
+    let (ab, ap) = ([b[0] - a[0], b[1] - a[1]], [point[0] - a[0], point[1] - a[1]]);
+    let length = ab[0] * ab[0] + ab[1] * ab[1];
+    if length == 0.0 {
+        return *a;
+    }
+    // we return the extremities as is to avoid any rounding error
+    let t = (ap[0] * ab[0] + ap[1] * ab[1]) / length;
+    if t <= 0.0 {
+        *a
+    } else if t >= 1.0 {
+        *b
+    } else {
+        [a[0] + t * ab[0], a[1] + t * ab[1]]
+    }
+}
+
+fn segments_intersect(a: &[f64; 2], b: &[f64; 2], c: &[f64; 2], d: &[f64; 2]) -> bool {
+    fn orientation(p: &[f64; 2], q: &[f64; 2], r: &[f64; 2]) -> f64 {
+        (q[0] - p[0]) * (r[1] - p[1]) - (q[1] - p[1]) * (r[0] - p[0])
+    }
+
+    let (d1, d2) = (orientation(c, d, a), orientation(c, d, b));
+    let (d3, d4) = (orientation(a, b, c), orientation(a, b, d));
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0))
+        && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0))
+    {
+        return true;
+    }
+
+    // the segments are touching each other
+    closest_point_on_segment(a, c, d) == *a
+        || closest_point_on_segment(b, c, d) == *b
+        || closest_point_on_segment(c, a, b) == *c
+        || closest_point_on_segment(d, a, b) == *d
+}
+
+fn to_lat_lng([lng, lat]: [f64; 2]) -> Result<[f64; 2], String> {
+    if !(-90.0..=90.0).contains(&lat) {
+        Err(format!("the latitude `{lat}` must be contained between -90 and 90 degrees"))
+    } else if !(-180.0..=180.0).contains(&lng) {
+        Err(format!("the longitude `{lng}` must be contained between -180 and 180 degrees"))
+    } else {
+        Ok([lat, lng])
+    }
+}
+
+fn to_line(line: Vec<[f64; 2]>) -> Result<Vec<[f64; 2]>, String> {
+    if line.len() < 2 {
+        return Err(String::from("a line string must contain at least two positions"));
+    }
+    line.into_iter().map(to_lat_lng).collect()
+}
+
+fn to_polygon(rings: Vec<Vec<[f64; 2]>>) -> Result<Vec<Vec<[f64; 2]>>, String> {
+    if rings.is_empty() {
+        return Err(String::from("a polygon must contain at least one ring"));
+    }
+    rings
+        .into_iter()
+        .map(|ring| {
+            if ring.len() < 3 {
+                return Err(String::from("a polygon ring must contain at least three positions"));
+            }
+            ring.into_iter().map(to_lat_lng).collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse_geojson() {
+        let geometry =
+            Geometry::from_geojson(json!({ "type": "Point", "coordinates": [3.0, 50.0] })).unwrap();
+        assert_eq!(geometry.points, vec![[50.0, 3.0]]);
+
+        let error = Geometry::from_geojson(json!({ "type": "Point", "coordinates": [3.0, 91.0] }))
+            .unwrap_err();
+        assert_eq!(error, "the latitude `91` must be contained between -90 and 90 degrees");
+
+        let error =
+            Geometry::from_geojson(json!({ "type": "LineString", "coordinates": [[3.0, 50.0]] }))
+                .unwrap_err();
+        assert_eq!(error, "a line string must contain at least two positions");
+
+        let error = Geometry::from_geojson(json!({ "type": "Circle", "coordinates": [3.0, 50.0] }))
+            .unwrap_err();
+        assert!(error.starts_with("unknown variant `Circle`"), "{error}");
+    }
+
+    #[test]
+    fn polygon_contains_point() {
+        let square = json!({
+            "type": "Polygon",
+            "coordinates": [
+                [[0.0, 0.0], [10.0, 0.0], [10.0, 10.0], [0.0, 10.0], [0.0, 0.0]],
+                [[4.0, 4.0], [6.0, 4.0], [6.0, 6.0], [4.0, 6.0], [4.0, 4.0]],
+            ]
+        });
+        let geometry = Geometry::from_geojson(square).unwrap();
+
+        assert!(geometry.contains_point(&[1.0, 1.0]));
+        assert!(geometry.contains_point(&[0.0, 5.0]));
+        // inside the hole
+        assert!(!geometry.contains_point(&[5.0, 5.0]));
+        assert!(!geometry.contains_point(&[11.0, 5.0]));
+
+        assert_eq!(geometry.distance_to_point(&[1.0, 1.0]), 0.0);
+        assert!(geometry.distance_to_point(&[5.0, 5.0]) > 0.0);
+    }
+
+    #[test]
+    fn line_intersects_polygon() {
+        let line = json!({ "type": "LineString", "coordinates": [[-5.0, 5.0], [15.0, 5.0]] });
+        let geometry = Geometry::from_geojson(line).unwrap();
+
+        let square = [[0.0, 0.0], [0.0, 10.0], [10.0, 10.0], [10.0, 0.0]];
+        assert!(geometry.intersects_polygon(&square));
+
+        let far_away = [[20.0, 20.0], [20.0, 30.0], [30.0, 30.0], [30.0, 20.0]];
+        assert!(!geometry.intersects_polygon(&far_away));
+
+        let envelope = geometry.envelope();
+        assert_eq!(envelope.lower(), [5.0, -5.0]);
+        assert_eq!(envelope.upper(), [5.0, 15.0]);
+    }
+
+    #[test]
+    fn envelope_of_a_radius() {
+        let envelope = radius_envelope(&[0.0, 0.0], 111_000.0);
+        assert!(envelope.lower()[0] < -0.99 && envelope.upper()[0] > 0.99);
+        assert!(envelope.lower()[1] < -0.99 && envelope.upper()[1] > 0.99);
+
+        // close to the north pole we can't restrict the longitude
+        let envelope = radius_envelope(&[89.9, 0.0], 111_000.0);
+        assert_eq!(envelope.lower()[1], -180.0);
+        assert_eq!(envelope.upper(), [90.0, 180.0]);
+    }
+}
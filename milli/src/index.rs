@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs::File;
 use std::mem::size_of;
 use std::path::Path;
@@ -22,8 +22,9 @@ use crate::heed_codec::StrRefCodec;
 use crate::{
     default_criteria, BEU32StrCodec, BoRoaringBitmapCodec, CboRoaringBitmapCodec, Criterion,
     DocumentId, ExternalDocumentsIds, FacetDistribution, FieldDistribution, FieldId,
-    FieldIdWordCountCodec, GeoPoint, ObkvCodec, Result, RoaringBitmapCodec, RoaringBitmapLenCodec,
-    Search, StrBEU32Codec, U8StrStrCodec, BEU16, BEU32,
+    FieldIdWordCountCodec, GeoPoint, GeoShape, HnswNode, Locale, LocalizedAttributesRule,
+    ObkvCodec, OrderBy, PrefixSearch, ProximityPrecision, Result, RoaringBitmapCodec,
+    RoaringBitmapLenCodec, Search, StrBEU32Codec, U8StrStrCodec, BEU16, BEU32,
 };
 
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
@@ -42,6 +43,7 @@ pub mod main_key {
     pub const FIELDS_IDS_MAP_KEY: &str = "fields-ids-map";
     pub const GEO_FACETED_DOCUMENTS_IDS_KEY: &str = "geo-faceted-documents-ids";
     pub const GEO_RTREE_KEY: &str = "geo-rtree";
+    pub const GEO_SHAPES_RTREE_KEY: &str = "geo-shapes-rtree";
     pub const HARD_EXTERNAL_DOCUMENTS_IDS_KEY: &str = "hard-external-documents-ids";
     pub const NUMBER_FACETED_DOCUMENTS_IDS_PREFIX: &str = "number-faceted-documents-ids";
     pub const PRIMARY_KEY_KEY: &str = "primary-key";
@@ -49,19 +51,31 @@ pub mod main_key {
     pub const USER_DEFINED_SEARCHABLE_FIELDS_KEY: &str = "user-defined-searchable-fields";
     pub const SOFT_EXTERNAL_DOCUMENTS_IDS_KEY: &str = "soft-external-documents-ids";
     pub const STOP_WORDS_KEY: &str = "stop-words";
+    pub const DICTIONARY_KEY: &str = "dictionary";
+    pub const NON_SEPARATOR_TOKENS_KEY: &str = "non-separator-tokens";
+    pub const SEPARATOR_TOKENS_KEY: &str = "separator-tokens";
     pub const STRING_FACETED_DOCUMENTS_IDS_PREFIX: &str = "string-faceted-documents-ids";
     pub const SYNONYMS_KEY: &str = "synonyms";
+    pub const USER_DEFINED_SYNONYMS_KEY: &str = "user-defined-synonyms";
     pub const WORDS_FST_KEY: &str = "words-fst";
     pub const WORDS_PREFIXES_FST_KEY: &str = "words-prefixes-fst";
     pub const CREATED_AT_KEY: &str = "created-at";
     pub const UPDATED_AT_KEY: &str = "updated-at";
+    pub const VECTOR_HNSW_ENTRY_POINT_KEY: &str = "vector-hnsw-entry-point";
+    pub const VECTOR_DOCUMENTS_IDS_KEY: &str = "vector-documents-ids";
     pub const AUTHORIZE_TYPOS: &str = "authorize-typos";
     pub const ONE_TYPO_WORD_LEN: &str = "one-typo-word-len";
     pub const TWO_TYPOS_WORD_LEN: &str = "two-typos-word-len";
     pub const EXACT_WORDS: &str = "exact-words";
+    pub const USER_DEFINED_EXACT_WORDS: &str = "user-defined-exact-words";
     pub const EXACT_ATTRIBUTES: &str = "exact-attributes";
     pub const MAX_VALUES_PER_FACET: &str = "max-values-per-facet";
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
+    pub const SORT_FACET_VALUES_BY: &str = "sort-facet-values-by";
+    pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized-attributes-rules";
+    pub const PROXIMITY_PRECISION: &str = "proximity-precision";
+    pub const SEARCH_CUTOFF: &str = "search-cutoff";
+    pub const PREFIX_SEARCH: &str = "prefix-search";
 }
 
 pub mod db_name {
@@ -82,6 +96,8 @@ pub mod db_name {
     pub const FACET_ID_STRING_DOCIDS: &str = "facet-id-string-docids";
     pub const FIELD_ID_DOCID_FACET_F64S: &str = "field-id-docid-facet-f64s";
     pub const FIELD_ID_DOCID_FACET_STRINGS: &str = "field-id-docid-facet-strings";
+    pub const VECTOR_ID_DOCID: &str = "vector-id-docids";
+    pub const VECTOR_HNSW: &str = "vector-hnsw";
     pub const DOCUMENTS: &str = "documents";
 }
 
@@ -135,6 +151,11 @@ pub struct Index {
     /// Maps the document id, the facet field id and the strings.
     pub field_id_docid_facet_strings: Database<FieldDocIdFacetStringCodec, Str>,
 
+    /// Maps the vector id with the document id of the document it belongs to.
+    pub vector_id_docid: Database<OwnedType<BEU32>, OwnedType<BEU32>>,
+    /// Maps the vector id with its node in the HNSW: the vector and its neighbors.
+    pub vector_hnsw: Database<OwnedType<BEU32>, SerdeBincode<HnswNode>>,
+
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<OwnedType<BEU32>, ObkvCodec>,
 }
@@ -148,7 +169,7 @@ impl Index {
     ) -> Result<Index> {
         use db_name::*;
 
-        options.max_dbs(18);
+        options.max_dbs(20);
         unsafe { options.flag(Flags::MdbAlwaysFreePages) };
 
         let env = options.open(path)?;
@@ -173,6 +194,8 @@ impl Index {
         let field_id_docid_facet_f64s = env.create_database(Some(FIELD_ID_DOCID_FACET_F64S))?;
         let field_id_docid_facet_strings =
             env.create_database(Some(FIELD_ID_DOCID_FACET_STRINGS))?;
+        let vector_id_docid = env.create_database(Some(VECTOR_ID_DOCID))?;
+        let vector_hnsw = env.create_database(Some(VECTOR_HNSW))?;
         let documents = env.create_database(Some(DOCUMENTS))?;
 
         Index::set_creation_dates(&env, main, created_at, updated_at)?;
@@ -196,6 +219,8 @@ impl Index {
             facet_id_exists_docids,
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
+            vector_id_docid,
+            vector_hnsw,
             documents,
         })
     }
@@ -436,6 +461,91 @@ impl Index {
         }
     }
 
+    /* geo shapes rtree */
+
+    /// Writes the provided `rtree` which associates GeoJSON geometries to documents ids.
+    pub(crate) fn put_geo_shapes_rtree(
+        &self,
+        wtxn: &mut RwTxn,
+        rtree: &RTree<GeoShape>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<RTree<GeoShape>>>(
+            wtxn,
+            main_key::GEO_SHAPES_RTREE_KEY,
+            rtree,
+        )
+    }
+
+    /// Delete the `rtree` which associates GeoJSON geometries to documents ids.
+    pub(crate) fn delete_geo_shapes_rtree(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::GEO_SHAPES_RTREE_KEY)
+    }
+
+    /// Returns the `rtree` which associates GeoJSON geometries to documents ids.
+    pub fn geo_shapes_rtree(&self, rtxn: &RoTxn) -> Result<Option<RTree<GeoShape>>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeBincode<RTree<GeoShape>>>(rtxn, main_key::GEO_SHAPES_RTREE_KEY)?)
+    }
+
+    /* vector HNSW */
+
+    /// Writes the id of the vector from which the searches of the HNSW start.
+    pub(crate) fn put_vector_hnsw_entry_point(
+        &self,
+        wtxn: &mut RwTxn,
+        entry_point: u32,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<BEU32>>(
+            wtxn,
+            main_key::VECTOR_HNSW_ENTRY_POINT_KEY,
+            &BEU32::new(entry_point),
+        )
+    }
+
+    /// Delete the entry point of the HNSW.
+    pub(crate) fn delete_vector_hnsw_entry_point(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::VECTOR_HNSW_ENTRY_POINT_KEY)
+    }
+
+    /// Returns the id of the vector from which the searches of the HNSW start,
+    /// `None` when the HNSW is empty.
+    pub fn vector_hnsw_entry_point(&self, rtxn: &RoTxn) -> heed::Result<Option<u32>> {
+        Ok(self
+            .main
+            .get::<_, Str, OwnedType<BEU32>>(rtxn, main_key::VECTOR_HNSW_ENTRY_POINT_KEY)?
+            .map(|entry_point| entry_point.get()))
+    }
+
+    /// Writes the documents ids that have at least one vector in the `hnsw`.
+    pub(crate) fn put_vector_documents_ids(
+        &self,
+        wtxn: &mut RwTxn,
+        docids: &RoaringBitmap,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, RoaringBitmapCodec>(
+            wtxn,
+            main_key::VECTOR_DOCUMENTS_IDS_KEY,
+            docids,
+        )
+    }
+
+    /// Delete the documents ids that have at least one vector in the `hnsw`.
+    pub(crate) fn delete_vector_documents_ids(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::VECTOR_DOCUMENTS_IDS_KEY)
+    }
+
+    /// Retrieve all the documents ids that have at least one vector in the `hnsw`.
+    pub fn vector_documents_ids(&self, rtxn: &RoTxn) -> heed::Result<RoaringBitmap> {
+        match self
+            .main
+            .get::<_, Str, RoaringBitmapCodec>(rtxn, main_key::VECTOR_DOCUMENTS_IDS_KEY)?
+        {
+            Some(docids) => Ok(docids),
+            None => Ok(RoaringBitmap::new()),
+        }
+    }
+
     /* geo faceted */
 
     /// Writes the documents ids that are faceted with a _geo field.
@@ -917,20 +1027,96 @@ impl Index {
         }
     }
 
+    /* non separator tokens */
+
+    pub(crate) fn put_non_separator_tokens(
+        &self,
+        wtxn: &mut RwTxn,
+        set: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<_>>(wtxn, main_key::NON_SEPARATOR_TOKENS_KEY, set)
+    }
+
+    pub(crate) fn delete_non_separator_tokens(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::NON_SEPARATOR_TOKENS_KEY)
+    }
+
+    pub fn non_separator_tokens(&self, rtxn: &RoTxn) -> Result<Option<BTreeSet<String>>> {
+        Ok(self.main.get::<_, Str, SerdeBincode<BTreeSet<String>>>(
+            rtxn,
+            main_key::NON_SEPARATOR_TOKENS_KEY,
+        )?)
+    }
+
+    /* separator tokens */
+
+    pub(crate) fn put_separator_tokens(
+        &self,
+        wtxn: &mut RwTxn,
+        set: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<_>>(wtxn, main_key::SEPARATOR_TOKENS_KEY, set)
+    }
+
+    pub(crate) fn delete_separator_tokens(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::SEPARATOR_TOKENS_KEY)
+    }
+
+    pub fn separator_tokens(&self, rtxn: &RoTxn) -> Result<Option<BTreeSet<String>>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeBincode<BTreeSet<String>>>(rtxn, main_key::SEPARATOR_TOKENS_KEY)?)
+    }
+
+    /* dictionary */
+
+    pub(crate) fn put_dictionary(
+        &self,
+        wtxn: &mut RwTxn,
+        set: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<_>>(wtxn, main_key::DICTIONARY_KEY, set)
+    }
+
+    pub(crate) fn delete_dictionary(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::DICTIONARY_KEY)
+    }
+
+    pub fn dictionary(&self, rtxn: &RoTxn) -> Result<Option<BTreeSet<String>>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeBincode<BTreeSet<String>>>(rtxn, main_key::DICTIONARY_KEY)?)
+    }
+
     /* synonyms */
 
     pub(crate) fn put_synonyms(
         &self,
         wtxn: &mut RwTxn,
         synonyms: &HashMap<Vec<String>, Vec<Vec<String>>>,
+        user_defined_synonyms: &HashMap<String, Vec<String>>,
     ) -> heed::Result<()> {
-        self.main.put::<_, Str, SerdeBincode<_>>(wtxn, main_key::SYNONYMS_KEY, synonyms)
+        self.main.put::<_, Str, SerdeBincode<_>>(wtxn, main_key::SYNONYMS_KEY, synonyms)?;
+        self.main.put::<_, Str, SerdeBincode<_>>(
+            wtxn,
+            main_key::USER_DEFINED_SYNONYMS_KEY,
+            user_defined_synonyms,
+        )
     }
 
     pub(crate) fn delete_synonyms(&self, wtxn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(wtxn, main_key::USER_DEFINED_SYNONYMS_KEY)?;
         self.main.delete::<_, Str>(wtxn, main_key::SYNONYMS_KEY)
     }
 
+    /// Returns the synonyms as they were given by the user, before their normalization.
+    pub fn user_defined_synonyms(
+        &self,
+        rtxn: &RoTxn,
+    ) -> heed::Result<Option<HashMap<String, Vec<String>>>> {
+        self.main.get::<_, Str, SerdeBincode<_>>(rtxn, main_key::USER_DEFINED_SYNONYMS_KEY)
+    }
+
     pub fn synonyms(&self, rtxn: &RoTxn) -> heed::Result<HashMap<Vec<String>, Vec<Vec<String>>>> {
         Ok(self
             .main
@@ -1134,6 +1320,24 @@ impl Index {
         Ok(())
     }
 
+    pub(crate) fn put_user_defined_exact_words(
+        &self,
+        txn: &mut RwTxn,
+        words: &BTreeSet<String>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<_>>(txn, main_key::USER_DEFINED_EXACT_WORDS, words)
+    }
+
+    pub(crate) fn delete_user_defined_exact_words(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::USER_DEFINED_EXACT_WORDS)
+    }
+
+    /// Returns the words on which typo are not allowed as they were given by the user,
+    /// before their normalization.
+    pub fn user_defined_exact_words(&self, txn: &RoTxn) -> heed::Result<Option<BTreeSet<String>>> {
+        self.main.get::<_, Str, SerdeBincode<_>>(txn, main_key::USER_DEFINED_EXACT_WORDS)
+    }
+
     /// Returns the exact attributes: attributes for which typo is disallowed.
     pub fn exact_attributes<'t>(&self, txn: &'t RoTxn) -> Result<Vec<&'t str>> {
         Ok(self
@@ -1172,6 +1376,111 @@ impl Index {
         self.main.delete::<_, Str>(txn, main_key::MAX_VALUES_PER_FACET)
     }
 
+    pub fn sort_facet_values_by(&self, txn: &RoTxn) -> heed::Result<HashMap<String, OrderBy>> {
+        Ok(self
+            .main
+            .get::<_, Str, SerdeJson<HashMap<String, OrderBy>>>(
+                txn,
+                main_key::SORT_FACET_VALUES_BY,
+            )?
+            .unwrap_or_default())
+    }
+
+    pub(crate) fn put_sort_facet_values_by(
+        &self,
+        txn: &mut RwTxn,
+        val: &HashMap<String, OrderBy>,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(txn, main_key::SORT_FACET_VALUES_BY, val)
+    }
+
+    pub(crate) fn delete_sort_facet_values_by(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::SORT_FACET_VALUES_BY)
+    }
+
+    pub fn localized_attributes_rules(
+        &self,
+        txn: &RoTxn,
+    ) -> heed::Result<Option<Vec<LocalizedAttributesRule>>> {
+        self.main.get::<_, Str, SerdeJson<Vec<LocalizedAttributesRule>>>(
+            txn,
+            main_key::LOCALIZED_ATTRIBUTES_RULES,
+        )
+    }
+
+    pub(crate) fn put_localized_attributes_rules(
+        &self,
+        txn: &mut RwTxn,
+        val: &[LocalizedAttributesRule],
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeJson<_>>(txn, main_key::LOCALIZED_ATTRIBUTES_RULES, &val)
+    }
+
+    pub(crate) fn delete_localized_attributes_rules(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::LOCALIZED_ATTRIBUTES_RULES)
+    }
+
+    /// Returns the locales of all the localized attributes rules, without duplicates.
+    ///
+    /// They are used to tokenize the query when no locales are given at search time.
+    pub fn localized_attributes_locales(&self, txn: &RoTxn) -> heed::Result<Vec<Locale>> {
+        let mut locales = Vec::new();
+        for rule in self.localized_attributes_rules(txn)?.unwrap_or_default() {
+            for locale in rule.locales {
+                if !locales.contains(&locale) {
+                    locales.push(locale);
+                }
+            }
+        }
+        Ok(locales)
+    }
+
+    pub fn proximity_precision(&self, txn: &RoTxn) -> heed::Result<Option<ProximityPrecision>> {
+        self.main
+            .get::<_, Str, SerdeBincode<ProximityPrecision>>(txn, main_key::PROXIMITY_PRECISION)
+    }
+
+    pub(crate) fn put_proximity_precision(
+        &self,
+        txn: &mut RwTxn,
+        val: ProximityPrecision,
+    ) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<ProximityPrecision>>(
+            txn,
+            main_key::PROXIMITY_PRECISION,
+            &val,
+        )
+    }
+
+    pub(crate) fn delete_proximity_precision(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::PROXIMITY_PRECISION)
+    }
+
+    /// The maximum time, in milliseconds, a search can take before it is degraded.
+    pub fn search_cutoff(&self, txn: &RoTxn) -> heed::Result<Option<u64>> {
+        self.main.get::<_, Str, OwnedType<u64>>(txn, main_key::SEARCH_CUTOFF)
+    }
+
+    pub(crate) fn put_search_cutoff(&self, txn: &mut RwTxn, val: u64) -> heed::Result<()> {
+        self.main.put::<_, Str, OwnedType<u64>>(txn, main_key::SEARCH_CUTOFF, &val)
+    }
+
+    pub(crate) fn delete_search_cutoff(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::SEARCH_CUTOFF)
+    }
+
+    pub fn prefix_search(&self, txn: &RoTxn) -> heed::Result<Option<PrefixSearch>> {
+        self.main.get::<_, Str, SerdeBincode<PrefixSearch>>(txn, main_key::PREFIX_SEARCH)
+    }
+
+    pub(crate) fn put_prefix_search(&self, txn: &mut RwTxn, val: PrefixSearch) -> heed::Result<()> {
+        self.main.put::<_, Str, SerdeBincode<PrefixSearch>>(txn, main_key::PREFIX_SEARCH, &val)
+    }
+
+    pub(crate) fn delete_prefix_search(&self, txn: &mut RwTxn) -> heed::Result<bool> {
+        self.main.delete::<_, Str>(txn, main_key::PREFIX_SEARCH)
+    }
+
     pub fn pagination_max_total_hits(&self, txn: &RoTxn) -> heed::Result<Option<usize>> {
         self.main.get::<_, Str, OwnedType<usize>>(txn, main_key::PAGINATION_MAX_TOTAL_HITS)
     }
@@ -1193,6 +1502,7 @@ impl Index {
 pub(crate) mod tests {
     use std::collections::HashSet;
     use std::ops::Deref;
+    use std::str::FromStr;
 
     use big_s::S;
     use heed::{EnvOpenOptions, RwTxn};
@@ -1206,7 +1516,7 @@ pub(crate) mod tests {
         self, DeleteDocuments, DeletionStrategy, IndexDocuments, IndexDocumentsConfig,
         IndexDocumentsMethod, IndexerConfig, Settings,
     };
-    use crate::{db_snap, obkv_to_json, Filter, Index, Search, SearchResult};
+    use crate::{db_snap, obkv_to_json, AscDesc, Criterion, Filter, Index, Search, SearchResult};
 
     pub(crate) struct TempIndex {
         pub inner: Index,
@@ -1606,6 +1916,245 @@ pub(crate) mod tests {
         "###);
     }
 
+    #[test]
+    fn test_basic_geo_polygon() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "_geo": { "lat": 0, "lng": 0 } },
+                { "id": 1, "_geo": { "lat": 0, "lng": -175 } },
+                { "id": 2, "_geo": { "lat": 0, "lng": 175 } },
+                { "id": 3, "_geo": { "lat": 85, "lng": 0 } },
+                { "id": 4, "_geo": { "lat": -85, "lng": 0 } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let mut search = index.search(&rtxn);
+
+        // match a document in the middle of the polygon
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([[10, -10], [10, 10], [-10, 10], [-10, -10]])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0]>");
+
+        // match a document on a vertex of the polygon
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([[0, 0], [10, 10], [10, -10]])").unwrap().unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0]>");
+
+        // match a document with a triangle
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([[90, 0], [80, 10], [80, -10]])").unwrap().unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[3]>");
+
+        // select everything
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([[90, -180], [90, 180], [-90, 180], [-90, -180]])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0, 1, 2, 3, 4]>");
+
+        // a polygon that doesn't contain anything
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([[50, 50], [60, 60], [50, 60]])").unwrap().unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[]>");
+    }
+
+    #[test]
+    fn test_geojson_geometries() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo") });
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "_geo": { "lat": 0, "lng": 0 } },
+                { "id": 1, "_geo": { "type": "Polygon", "coordinates": [[[10, 10], [20, 10], [20, 20], [10, 20], [10, 10]]] } },
+                { "id": 2, "_geo": { "type": "LineString", "coordinates": [[-20, -20], [-10, -10]] } },
+                { "id": 3, "_geo": { "type": "MultiPoint", "coordinates": [[30, 30], [40, 40]] } },
+                { "id": 4, "_geo": { "type": "Point", "coordinates": [50, 50] } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let mut search = index.search(&rtxn);
+
+        // the polygon contains the point
+        let search_result = search
+            .filter(Filter::from_str("_geoRadius(15, 15, 0)").unwrap().unwrap())
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[1]>");
+
+        // the line contains the point
+        let search_result = search
+            .filter(Filter::from_str("_geoRadius(-15, -15, 1000)").unwrap().unwrap())
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[2]>");
+
+        // only the point is close enough
+        let search_result = search
+            .filter(Filter::from_str("_geoRadius(0, 0, 100)").unwrap().unwrap())
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0]>");
+
+        // one of the points is inside the polygon
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([[35, 25], [35, 35], [25, 35], [25, 25]])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[3]>");
+
+        // the polygons are intersecting each other
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([[0, 5], [5, 15], [15, 15], [15, 5]])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[1]>");
+
+        // the bounding box contains the point
+        let search_result = search
+            .filter(Filter::from_str("_geoBoundingBox([60, 45], [45, 55])").unwrap().unwrap())
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[4]>");
+
+        // select everything
+        let search_result = search
+            .filter(Filter::from_str("_geoBoundingBox([90, -180], [-90, 180])").unwrap().unwrap())
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0, 1, 2, 3, 4]>");
+
+        drop(rtxn);
+
+        // the deleted geometries must not be returned anymore
+        index.delete_document("1");
+
+        let rtxn = index.read_txn().unwrap();
+        let search_result = index
+            .search(&rtxn)
+            .filter(Filter::from_str("_geoRadius(15, 15, 0)").unwrap().unwrap())
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[]>");
+    }
+
+    #[test]
+    fn test_multiple_geo_points() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo") });
+                settings.set_sortable_fields(hashset! { S("_geo") });
+                settings.set_criteria(vec![Criterion::Sort]);
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "_geo": [{ "lat": 0, "lng": 0 }, { "lat": 10, "lng": 10 }] },
+                { "id": 1, "_geo": { "lat": 5, "lng": 5 } },
+                { "id": 2, "_geo": [{ "lat": 0, "lng": 10 }, { "lat": 10, "lng": 0 }] },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let mut search = index.search(&rtxn);
+
+        // only one of the points must be close enough
+        let search_result = search
+            .filter(Filter::from_str("_geoRadius(10, 10, 1000)").unwrap().unwrap())
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[0]>");
+
+        // the first document has a latitude and a longitude in the box but they belong to different points
+        let search_result = search
+            .filter(Filter::from_str("_geoBoundingBox([1, 9], [-1, 11])").unwrap().unwrap())
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[2]>");
+
+        let search_result = search
+            .filter(
+                Filter::from_str("_geoPolygon([[11, -1], [11, 1], [9, 1], [9, -1]])")
+                    .unwrap()
+                    .unwrap(),
+            )
+            .execute()
+            .unwrap();
+        insta::assert_debug_snapshot!(search_result.candidates, @"RoaringBitmap<[2]>");
+
+        // the documents are sorted by their nearest point
+        let mut search = index.search(&rtxn);
+        search.sort_criteria(vec![AscDesc::from_str("_geoPoint(10, 10):asc").unwrap()]);
+        let SearchResult { documents_ids, .. } = search.execute().unwrap();
+        insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2]");
+
+        search.sort_criteria(vec![AscDesc::from_str("_geoPoint(10, 10):desc").unwrap()]);
+        let SearchResult { documents_ids, .. } = search.execute().unwrap();
+        insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 1, 0]");
+    }
+
+    #[test]
+    fn too_many_geo_points() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo") });
+            })
+            .unwrap();
+
+        let points: Vec<_> = (0..=u16::MAX as usize + 1)
+            .map(|i| serde_json::json!({ "lat": 0, "lng": i % 180 }))
+            .collect();
+        let err = index.add_documents(documents!([{ "id": 0, "_geo": points }])).unwrap_err();
+        insta::assert_display_snapshot!(err, @"The `_geo` field in the document with the id: `0` contains 65537 points. A document cannot contain more than 65,536 points.");
+    }
+
     #[test]
     fn replace_documents_external_ids_and_soft_deletion_check() {
         use big_s::S;
@@ -2383,8 +2932,13 @@ pub(crate) mod tests {
 
         let rtxn = index.read_txn().unwrap();
         let search = Search::new(&rtxn, &index);
-        let SearchResult { matching_words: _, candidates: _, mut documents_ids } =
-            search.execute().unwrap();
+        let SearchResult {
+            matching_words: _,
+            candidates: _,
+            document_scores: _,
+            degraded: _,
+            mut documents_ids,
+        } = search.execute().unwrap();
         let primary_key_id = index.fields_ids_map(&rtxn).unwrap().id("primary_key").unwrap();
         documents_ids.sort_unstable();
         let docs = index.documents(&rtxn, documents_ids).unwrap();
@@ -8,11 +8,16 @@ mod error;
 mod external_documents_ids;
 pub mod facet;
 mod fields_ids_map;
+mod geo_shape;
 pub mod heed_codec;
 pub mod index;
+mod localized_attributes_rules;
 pub mod proximity;
+pub mod score_details;
 mod search;
+mod tokenizer_settings;
 pub mod update;
+mod vector;
 
 #[cfg(test)]
 #[macro_use]
@@ -35,17 +40,27 @@ pub use self::error::{
 };
 pub use self::external_documents_ids::ExternalDocumentsIds;
 pub use self::fields_ids_map::FieldsIdsMap;
+pub use self::geo_shape::Geometry;
 pub use self::heed_codec::{
     BEU32StrCodec, BoRoaringBitmapCodec, BoRoaringBitmapLenCodec, CboRoaringBitmapCodec,
     CboRoaringBitmapLenCodec, FieldIdWordCountCodec, ObkvCodec, RoaringBitmapCodec,
     RoaringBitmapLenCodec, StrBEU32Codec, U8StrStrCodec, UncheckedU8StrStrCodec,
 };
 pub use self::index::Index;
+pub use self::localized_attributes_rules::{
+    locales_allow_list, Locale, LocaleParseError, LocalizedAttributesRule,
+};
+pub use self::proximity::ProximityPrecision;
 pub use self::search::{
-    CriterionImplementationStrategy, FacetDistribution, Filter, FormatOptions, MatchBounds,
-    MatcherBuilder, MatchingWord, MatchingWords, Search, SearchResult, TermsMatchingStrategy,
+    sort_documents_ids, CriterionImplementationStrategy, FacetDistribution, FacetValueHit, Filter,
+    FormatOptions, MatchBounds, MatcherBuilder, MatchingWord, MatchingWords, OrderBy, PrefixSearch,
+    Search, SearchForFacetValues, SearchResult, TermsMatchingStrategy, TimeBudget,
     DEFAULT_VALUES_PER_FACET,
 };
+pub use self::tokenizer_settings::TokenizerSettings;
+pub use self::vector::{
+    dot_product_similarity, HnswNode, NDotProductPoint, VectorOrArrayOfVectors,
+};
 
 pub type Result<T> = std::result::Result<T, error::Error>;
 
@@ -71,6 +86,12 @@ pub type SmallVec8<T> = smallvec::SmallVec<[T; 8]>;
 /// expressed in term of latitude and longitude.
 pub type GeoPoint = rstar::primitives::GeomWithData<[f64; 3], (DocumentId, [f64; 2])>;
 
+/// A GeoShape is the envelope, expressed in terms of latitude and longitude, of the GeoJSON
+/// geometry of a document. Its metadata is a tuple composed of 1. the DocumentId of the
+/// associated document and 2. the geometry itself.
+pub type GeoShape =
+    rstar::primitives::GeomWithData<rstar::primitives::Rectangle<[f64; 2]>, (DocumentId, Geometry)>;
+
 /// The maximum length a LMDB key can be.
 ///
 /// Note that the actual allowed length is a little bit higher, but
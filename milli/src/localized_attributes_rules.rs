@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use charabia::{Language, Script};
+use deserr::DeserializeFromValue;
+use serde::{Deserialize, Serialize};
+
+macro_rules! make_locale {
+    ($(($locale:ident, $code:literal, $script:ident)),+ $(,)?) => {
+        /// A language the tokenizer can be forced to use, identified by its ISO 639-3 code.
+        #[derive(
+            Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, DeserializeFromValue,
+        )]
+        #[serde(rename_all = "lowercase")]
+        #[deserr(rename_all = lowercase)]
+        pub enum Locale {
+            $($locale),+
+        }
+
+        impl Locale {
+            pub const ALL: &'static [Locale] = &[$(Locale::$locale),+];
+
+            /// The ISO 639-3 code of the locale.
+            pub fn code(&self) -> &'static str {
+                match self {
+                    $(Locale::$locale => $code),+
+                }
+            }
+
+            pub fn language(&self) -> Language {
+                match self {
+                    $(Locale::$locale => Language::$locale),+
+                }
+            }
+
+            /// The script the language is written in.
+            pub fn script(&self) -> Script {
+                match self {
+                    $(Locale::$locale => Script::$script),+
+                }
+            }
+        }
+    };
+}
+
+make_locale!(
+    (Ara, "ara", Arabic),
+    (Cmn, "cmn", Cj),
+    (Dan, "dan", Latin),
+    (Deu, "deu", Latin),
+    (Ell, "ell", Greek),
+    (Eng, "eng", Latin),
+    (Fra, "fra", Latin),
+    (Heb, "heb", Hebrew),
+    (Hin, "hin", Devanagari),
+    (Ita, "ita", Latin),
+    (Jpn, "jpn", Cj),
+    (Kor, "kor", Hangul),
+    (Nld, "nld", Latin),
+    (Pol, "pol", Latin),
+    (Por, "por", Latin),
+    (Rus, "rus", Cyrillic),
+    (Spa, "spa", Latin),
+    (Swe, "swe", Latin),
+    (Tha, "tha", Thai),
+    (Tur, "tur", Latin),
+    (Ukr, "ukr", Cyrillic),
+    (Vie, "vie", Latin),
+);
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = LocaleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Locale::ALL
+            .iter()
+            .find(|locale| locale.code().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| LocaleParseError(s.to_owned()))
+    }
+}
+
+#[derive(Debug)]
+pub struct LocaleParseError(pub String);
+
+impl fmt::Display for LocaleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Unsupported locale `{}`, expected one of {}.",
+            self.0,
+            Locale::ALL.iter().map(|locale| format!("`{locale}`")).collect::<Vec<_>>().join(", ")
+        )
+    }
+}
+
+impl std::error::Error for LocaleParseError {}
+
+/// Forces the tokenizer to use the given locales on the attributes matching the patterns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, DeserializeFromValue)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct LocalizedAttributesRule {
+    /// The attributes the rule applies to, a pattern can start or end with a `*`.
+    pub attribute_patterns: Vec<String>,
+    pub locales: Vec<Locale>,
+}
+
+impl LocalizedAttributesRule {
+    /// Returns `true` if one of the patterns of the rule matches the attribute.
+    pub fn match_str(&self, attribute: &str) -> bool {
+        self.attribute_patterns.iter().any(|pattern| match_pattern(pattern, attribute))
+    }
+}
+
+fn match_pattern(pattern: &str, attribute: &str) -> bool {
+    if pattern == "*" {
+        true
+    } else if let Some(inner) = pattern.strip_prefix('*').and_then(|p| p.strip_suffix('*')) {
+        attribute.contains(inner)
+    } else if let Some(suffix) = pattern.strip_prefix('*') {
+        attribute.ends_with(suffix)
+    } else if let Some(prefix) = pattern.strip_suffix('*') {
+        attribute.starts_with(prefix)
+    } else {
+        pattern == attribute
+    }
+}
+
+/// Builds the list of languages the tokenizer is allowed to detect for each script.
+pub fn locales_allow_list(locales: &[Locale]) -> HashMap<Script, Vec<Language>> {
+    let mut allow_list: HashMap<Script, Vec<Language>> = HashMap::new();
+    for locale in locales {
+        let languages = allow_list.entry(locale.script()).or_default();
+        if !languages.contains(&locale.language()) {
+            languages.push(locale.language());
+        }
+    }
+    allow_list
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_attribute_patterns() {
+        let rule = LocalizedAttributesRule {
+            attribute_patterns: vec!["title_*".to_string(), "*_ja".to_string(), "desc".to_string()],
+            locales: vec![Locale::Jpn],
+        };
+        assert!(rule.match_str("title_en"));
+        assert!(rule.match_str("overview_ja"));
+        assert!(rule.match_str("desc"));
+        assert!(!rule.match_str("description"));
+        assert!(!rule.match_str("title"));
+
+        let rule = LocalizedAttributesRule {
+            attribute_patterns: vec!["*name*".to_string()],
+            locales: vec![Locale::Cmn],
+        };
+        assert!(rule.match_str("surname"));
+        assert!(rule.match_str("name"));
+        assert!(!rule.match_str("title"));
+    }
+
+    #[test]
+    fn parse_locale() {
+        assert_eq!("jpn".parse::<Locale>().unwrap(), Locale::Jpn);
+        assert_eq!("CMN".parse::<Locale>().unwrap(), Locale::Cmn);
+        assert!("japanese".parse::<Locale>().is_err());
+    }
+
+    #[test]
+    fn allow_list_groups_languages_by_script() {
+        let allow_list = locales_allow_list(&[Locale::Jpn, Locale::Cmn, Locale::Eng, Locale::Jpn]);
+        assert_eq!(allow_list.len(), 2);
+        assert_eq!(allow_list[&Script::Cj], vec![Language::Jpn, Language::Cmn]);
+        assert_eq!(allow_list[&Script::Latin], vec![Language::Eng]);
+    }
+}
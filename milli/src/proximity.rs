@@ -1,9 +1,22 @@
 use std::cmp;
 
+use serde::{Deserialize, Serialize};
+
 use crate::{relative_from_absolute_position, Position};
 
 pub const MAX_DISTANCE: u32 = 8;
 
+/// How precisely the proximity between two words is indexed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProximityPrecision {
+    /// The distance between the words is indexed in the word pair proximity databases.
+    #[default]
+    ByWord,
+    /// Only whether the words are in the same attribute is known, the word pair
+    /// proximity databases are not built.
+    ByAttribute,
+}
+
 pub fn index_proximity(lhs: u32, rhs: u32) -> u32 {
     if lhs <= rhs {
         cmp::min(rhs - lhs, MAX_DISTANCE)
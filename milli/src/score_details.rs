@@ -0,0 +1,229 @@
+use serde_json::{json, Map, Value};
+
+/// The details of the bucket a document fell into, for one ranking rule.
+///
+/// A list of `ScoreDetails`, one per ranking rule and in the order of the ranking rules,
+/// is associated with every document returned by a search.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScoreDetails {
+    Words(Words),
+    Typo(Typo),
+    Proximity(Rank),
+    Attribute(Rank),
+    Exactness(Rank),
+    Sort(Sort),
+    GeoSort(GeoSort),
+    Vector(Vector),
+}
+
+impl ScoreDetails {
+    /// The rank of the bucket, `None` for the ranking rules that do not participate in the score.
+    pub fn rank(&self) -> Option<Rank> {
+        match self {
+            ScoreDetails::Words(details) => Some(details.rank()),
+            ScoreDetails::Typo(details) => Some(details.rank()),
+            ScoreDetails::Proximity(rank) => Some(*rank),
+            ScoreDetails::Attribute(rank) => Some(*rank),
+            ScoreDetails::Exactness(rank) => Some(*rank),
+            ScoreDetails::Sort(_) => None,
+            ScoreDetails::GeoSort(_) => None,
+            ScoreDetails::Vector(details) => Some(details.rank()),
+        }
+    }
+
+    /// The normalized score between `0.0` and `1.0` of a document, given the details of all of its ranking rules.
+    ///
+    /// The score of a rule is only significant to break ties between documents that have the same score for
+    /// all of the previous rules, so the earlier rules weigh more in the global score.
+    pub fn global_score<'a>(details: impl Iterator<Item = &'a Self>) -> f64 {
+        Rank::global_score(details.filter_map(Self::rank))
+    }
+
+    /// Converts the details of all the ranking rules of a document into a JSON object,
+    /// keyed by ranking rule and in the order of the ranking rules.
+    pub fn to_json_map<'a>(details: impl Iterator<Item = &'a Self>) -> Map<String, Value> {
+        let mut map = Map::new();
+        for (order, details) in details.enumerate() {
+            match details {
+                ScoreDetails::Words(words) => {
+                    let words_details = json!({
+                        "order": order,
+                        "matchingWords": words.matching_words,
+                        "maxMatchingWords": words.max_matching_words,
+                        "score": words.rank().local_score(),
+                    });
+                    map.insert("words".into(), words_details);
+                }
+                ScoreDetails::Typo(typo) => {
+                    let typo_details = json!({
+                        "order": order,
+                        "typoCount": typo.typo_count,
+                        "maxTypoCount": typo.max_typo_count,
+                        "score": typo.rank().local_score(),
+                    });
+                    map.insert("typo".into(), typo_details);
+                }
+                ScoreDetails::Proximity(rank) => {
+                    let proximity_details = json!({
+                        "order": order,
+                        "score": rank.local_score(),
+                    });
+                    map.insert("proximity".into(), proximity_details);
+                }
+                ScoreDetails::Attribute(rank) => {
+                    let attribute_details = json!({
+                        "order": order,
+                        "score": rank.local_score(),
+                    });
+                    map.insert("attribute".into(), attribute_details);
+                }
+                ScoreDetails::Exactness(rank) => {
+                    let exactness_details = json!({
+                        "order": order,
+                        "score": rank.local_score(),
+                    });
+                    map.insert("exactness".into(), exactness_details);
+                }
+                ScoreDetails::Sort(details) => {
+                    let sort = format!(
+                        "{}:{}",
+                        details.field_name,
+                        if details.ascending { "asc" } else { "desc" }
+                    );
+                    map.insert(sort, json!({ "order": order }));
+                }
+                ScoreDetails::GeoSort(details) => {
+                    let [lat, lng] = details.target_point;
+                    let sort = format!(
+                        "_geoPoint({}, {}):{}",
+                        lat,
+                        lng,
+                        if details.ascending { "asc" } else { "desc" }
+                    );
+                    map.insert(sort, json!({ "order": order }));
+                }
+                ScoreDetails::Vector(vector) => {
+                    let vector_details = json!({
+                        "order": order,
+                        "similarity": vector.similarity,
+                        "score": vector.rank().local_score(),
+                    });
+                    map.insert("vectorSort".into(), vector_details);
+                }
+            }
+        }
+        map
+    }
+}
+
+/// The position of a bucket among the buckets a ranking rule can return.
+///
+/// `rank` goes from `1`, the worst bucket, to `max_rank`, the best bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rank {
+    pub rank: u32,
+    pub max_rank: u32,
+}
+
+impl Rank {
+    pub fn local_score(self) -> f64 {
+        self.rank as f64 / self.max_rank as f64
+    }
+
+    pub fn global_score(ranks: impl Iterator<Item = Rank>) -> f64 {
+        let mut score = 0.0;
+        let mut scale = 1.0;
+
+        for Rank { rank, max_rank } in ranks {
+            // the worst bucket of a rule is worth the best bucket of the following rules.
+            let max_rank = max_rank.max(1) as f64;
+            let rank = (rank as f64).clamp(1.0, max_rank);
+            scale /= max_rank;
+            score += (rank - 1.0) * scale;
+        }
+
+        // the best bucket of the last rule gives the maximum score.
+        score + scale
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Words {
+    pub matching_words: u32,
+    pub max_matching_words: u32,
+}
+
+impl Words {
+    pub fn rank(&self) -> Rank {
+        let max_rank = self.max_matching_words.max(1);
+        Rank { rank: self.matching_words.clamp(1, max_rank), max_rank }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Typo {
+    pub typo_count: u32,
+    pub max_typo_count: u32,
+}
+
+impl Typo {
+    pub fn rank(&self) -> Rank {
+        let typo_count = self.typo_count.min(self.max_typo_count);
+        Rank { rank: self.max_typo_count - typo_count + 1, max_rank: self.max_typo_count + 1 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sort {
+    pub field_name: String,
+    pub ascending: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoSort {
+    pub target_point: [f64; 2],
+    pub ascending: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Vector {
+    /// The dot product between the normalized query vector and the closest vector of the document.
+    pub similarity: f32,
+}
+
+impl Vector {
+    /// The similarity is bucketed so that it can be combined with the ranks of the other rules.
+    pub fn rank(&self) -> Rank {
+        const MAX_RANK: u32 = 10_000;
+        // the similarity of normalized vectors goes from `-1.0` to `1.0`.
+        let similarity = ((self.similarity as f64 + 1.0) / 2.0).clamp(0.0, 1.0);
+        Rank { rank: (similarity * (MAX_RANK - 1) as f64).round() as u32 + 1, max_rank: MAX_RANK }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn global_score_orders_like_the_buckets() {
+        let score = |ranks: &[(u32, u32)]| {
+            Rank::global_score(ranks.iter().map(|&(rank, max_rank)| Rank { rank, max_rank }))
+        };
+
+        assert_eq!(score(&[]), 1.0);
+        assert_eq!(score(&[(3, 3), (4, 4)]), 1.0);
+        assert_eq!(score(&[(1, 3), (1, 4)]), 1.0 / 12.0);
+        assert!(score(&[(2, 3), (1, 4)]) > score(&[(1, 3), (4, 4)]));
+        assert!(score(&[(2, 3), (2, 4)]) > score(&[(2, 3), (1, 4)]));
+    }
+
+    #[test]
+    fn vector_rank_follows_the_similarity() {
+        let rank = |similarity| Vector { similarity }.rank();
+
+        assert_eq!(rank(1.0).local_score(), 1.0);
+        assert_eq!(rank(-1.0).rank, 1);
+        assert!(rank(0.9).local_score() > rank(0.5).local_score());
+    }
+}
@@ -9,6 +9,7 @@ use super::{Criterion, CriterionParameters, CriterionResult};
 use crate::facet::FacetType;
 use crate::heed_codec::facet::FacetGroupKeyCodec;
 use crate::heed_codec::ByteSliceRefCodec;
+use crate::score_details::{self, ScoreDetails};
 use crate::search::criteria::{resolve_query_tree, CriteriaBuilder, InitialCandidates};
 use crate::search::facet::{ascending_facet_sort, descending_facet_sort};
 use crate::search::query_tree::Operation;
@@ -30,6 +31,7 @@ pub struct AscDesc<'t> {
     allowed_candidates: RoaringBitmap,
     initial_candidates: InitialCandidates,
     faceted_candidates: RoaringBitmap,
+    score_details: Vec<ScoreDetails>,
     implementation_strategy: CriterionImplementationStrategy,
     parent: Box<dyn Criterion + 't>,
 }
@@ -87,10 +89,21 @@ impl<'t> AscDesc<'t> {
             allowed_candidates: RoaringBitmap::new(),
             faceted_candidates,
             initial_candidates: InitialCandidates::Estimated(RoaringBitmap::new()),
+            score_details: Vec::new(),
             implementation_strategy,
             parent,
         })
     }
+
+    /// Appends the details of this criterion to the details of the parent bucket.
+    fn score_details(&self) -> Vec<ScoreDetails> {
+        let mut score_details = self.score_details.clone();
+        score_details.push(ScoreDetails::Sort(score_details::Sort {
+            field_name: self.field_name.clone(),
+            ascending: self.is_ascending,
+        }));
+        score_details
+    }
 }
 
 impl<'t> Criterion for AscDesc<'t> {
@@ -113,6 +126,7 @@ impl<'t> Criterion for AscDesc<'t> {
                         candidates: Some(take(&mut self.allowed_candidates)),
                         filtered_candidates: None,
                         initial_candidates: Some(self.initial_candidates.take()),
+                        score_details: self.score_details(),
                     }));
                 }
                 None => match self.parent.next(params)? {
@@ -121,8 +135,10 @@ impl<'t> Criterion for AscDesc<'t> {
                         candidates,
                         filtered_candidates,
                         initial_candidates,
+                        score_details,
                     }) => {
                         self.query_tree = query_tree;
+                        self.score_details = score_details;
                         let mut candidates = match (&self.query_tree, candidates) {
                             (_, Some(candidates)) => candidates,
                             (Some(qt), None) => {
@@ -170,6 +186,7 @@ impl<'t> Criterion for AscDesc<'t> {
                         candidates: Some(candidates),
                         filtered_candidates: None,
                         initial_candidates: Some(self.initial_candidates.take()),
+                        score_details: self.score_details(),
                     }));
                 }
             }
@@ -226,7 +243,7 @@ fn facet_ordered_set_based<'t>(
 ///
 /// It will either use an iterative or a recursive method on the whole facet database depending
 /// on the number of candidates to rank.
-fn facet_ordered<'t>(
+pub(crate) fn facet_ordered<'t>(
     index: &'t Index,
     rtxn: &'t heed::RoTxn,
     field_id: FieldId,
@@ -7,6 +7,7 @@ use std::mem::take;
 use roaring::RoaringBitmap;
 
 use super::{resolve_query_tree, Context, Criterion, CriterionParameters, CriterionResult};
+use crate::score_details::{Rank, ScoreDetails};
 use crate::search::criteria::{InitialCandidates, Query};
 use crate::search::query_tree::{Operation, QueryKind};
 use crate::search::{
@@ -29,6 +30,7 @@ pub struct Attribute<'t> {
     ctx: &'t dyn Context<'t>,
     state: Option<(Operation, FlattenedQueryTree, RoaringBitmap)>,
     initial_candidates: InitialCandidates,
+    parent_score_details: Vec<ScoreDetails>,
     parent: Box<dyn Criterion + 't>,
     linear_buckets: Option<btree_map::IntoIter<u64, RoaringBitmap>>,
     set_buckets: Option<BinaryHeap<Branch<'t>>>,
@@ -45,12 +47,37 @@ impl<'t> Attribute<'t> {
             ctx,
             state: None,
             initial_candidates: InitialCandidates::Estimated(RoaringBitmap::new()),
+            parent_score_details: Vec::new(),
             parent,
             linear_buckets: None,
             set_buckets: None,
             implementation_strategy,
         }
     }
+
+    /// Appends the details of the bucket with the given rank to the details of the parent bucket.
+    fn score_details(&self, bucket_rank: u64) -> Vec<ScoreDetails> {
+        let mut score_details = self.parent_score_details.clone();
+        score_details.push(ScoreDetails::Attribute(attribute_rank(bucket_rank)));
+        score_details
+    }
+}
+
+/// Converts the rank of a bucket, the mean of the words positions multiplied by `LCM_10_FIRST_NUMBERS`,
+/// into a `Rank` where the attribute the words are in weighs more than their position in the attribute.
+fn attribute_rank(bucket_rank: u64) -> Rank {
+    const MAX_RELATIVE_POSITION: u64 = 15;
+    const MAX_BUCKET: u64 = 255;
+
+    let mean_position = bucket_rank / LCM_10_FIRST_NUMBERS as u64;
+    let field_id = mean_position >> 16;
+    let relative_position = (mean_position & 0xFFFF).min(MAX_RELATIVE_POSITION);
+    let bucket = field_id
+        .saturating_mul(MAX_RELATIVE_POSITION + 1)
+        .saturating_add(relative_position)
+        .min(MAX_BUCKET);
+
+    Rank { rank: (MAX_BUCKET - bucket) as u32 + 1, max_rank: MAX_BUCKET as u32 + 1 }
 }
 
 impl<'t> Criterion for Attribute<'t> {
@@ -69,10 +96,24 @@ impl<'t> Criterion for Attribute<'t> {
                         candidates: Some(RoaringBitmap::new()),
                         filtered_candidates: None,
                         initial_candidates: Some(self.initial_candidates.take()),
+                        score_details: self.score_details(u64::MAX),
+                    }));
+                }
+                Some((query_tree, _, allowed_candidates))
+                    if params.time_budget.degrade_if_exceeded() =>
+                {
+                    // The search took too long: all the remaining candidates
+                    // are returned in a single, last bucket.
+                    return Ok(Some(CriterionResult {
+                        query_tree: Some(query_tree),
+                        candidates: Some(allowed_candidates),
+                        filtered_candidates: None,
+                        initial_candidates: Some(self.initial_candidates.take()),
+                        score_details: self.score_details(u64::MAX),
                     }));
                 }
                 Some((query_tree, flattened_query_tree, mut allowed_candidates)) => {
-                    let found_candidates = if matches!(
+                    let (bucket_rank, found_candidates) = if matches!(
                         self.implementation_strategy,
                         CriterionImplementationStrategy::OnlyIterative
                     ) || (matches!(
@@ -94,13 +135,14 @@ impl<'t> Criterion for Attribute<'t> {
                         };
 
                         match linear_buckets.next() {
-                            Some((_score, candidates)) => candidates,
+                            Some((rank, candidates)) => (rank, candidates),
                             None => {
                                 return Ok(Some(CriterionResult {
                                     query_tree: Some(query_tree),
                                     candidates: Some(RoaringBitmap::new()),
                                     filtered_candidates: None,
                                     initial_candidates: Some(self.initial_candidates.take()),
+                                    score_details: self.score_details(u64::MAX),
                                 }));
                             }
                         }
@@ -119,13 +161,14 @@ impl<'t> Criterion for Attribute<'t> {
                         };
 
                         match set_compute_candidates(set_buckets, &allowed_candidates)? {
-                            Some((_score, candidates)) => candidates,
+                            Some((rank, candidates)) => (rank as u64, candidates),
                             None => {
                                 return Ok(Some(CriterionResult {
                                     query_tree: Some(query_tree),
                                     candidates: Some(RoaringBitmap::new()),
                                     filtered_candidates: None,
                                     initial_candidates: Some(self.initial_candidates.take()),
+                                    score_details: self.score_details(u64::MAX),
                                 }));
                             }
                         }
@@ -141,6 +184,7 @@ impl<'t> Criterion for Attribute<'t> {
                         candidates: Some(found_candidates),
                         filtered_candidates: None,
                         initial_candidates: Some(self.initial_candidates.take()),
+                        score_details: self.score_details(bucket_rank),
                     }));
                 }
                 None => match self.parent.next(params)? {
@@ -149,7 +193,9 @@ impl<'t> Criterion for Attribute<'t> {
                         candidates,
                         filtered_candidates,
                         initial_candidates,
+                        score_details,
                     }) => {
+                        self.parent_score_details = score_details;
                         let mut candidates = match candidates {
                             Some(candidates) => candidates,
                             None => {
@@ -179,12 +225,14 @@ impl<'t> Criterion for Attribute<'t> {
                         candidates,
                         filtered_candidates,
                         initial_candidates,
+                        score_details,
                     }) => {
                         return Ok(Some(CriterionResult {
                             query_tree: None,
                             candidates,
                             filtered_candidates,
                             initial_candidates,
+                            score_details,
                         }));
                     }
                     None => return Ok(None),
@@ -6,6 +6,7 @@ use std::mem::take;
 use log::debug;
 use roaring::{MultiOps, RoaringBitmap};
 
+use crate::score_details::{Rank, ScoreDetails};
 use crate::search::criteria::{
     resolve_phrase, resolve_query_tree, Context, Criterion, CriterionParameters, CriterionResult,
     InitialCandidates,
@@ -18,6 +19,7 @@ pub struct Exactness<'t> {
     query_tree: Option<Operation>,
     state: Option<State>,
     initial_candidates: InitialCandidates,
+    score_details: Vec<ScoreDetails>,
     parent: Box<dyn Criterion + 't>,
     query: Vec<ExactQueryPart>,
     cache: Option<ExactWordsCombinationCache>,
@@ -39,6 +41,7 @@ impl<'t> Exactness<'t> {
             query_tree: None,
             state: None,
             initial_candidates: InitialCandidates::Estimated(RoaringBitmap::new()),
+            score_details: Vec::new(),
             parent,
             query,
             cache: None,
@@ -65,15 +68,20 @@ impl<'t> Criterion for Exactness<'t> {
                     // the primitive query, which does not change
                 }
                 Some(state) => {
+                    let rank = state.rank(self.query.len());
                     let (candidates, state) =
                         resolve_state(self.ctx, take(state), &self.query, &mut self.cache)?;
                     self.state = state;
 
+                    let mut score_details = self.score_details.clone();
+                    score_details.push(ScoreDetails::Exactness(rank));
+
                     return Ok(Some(CriterionResult {
                         query_tree: self.query_tree.clone(),
                         candidates: Some(candidates),
                         filtered_candidates: None,
                         initial_candidates: Some(self.initial_candidates.take()),
+                        score_details,
                     }));
                 }
                 None => match self.parent.next(params)? {
@@ -82,7 +90,9 @@ impl<'t> Criterion for Exactness<'t> {
                         candidates,
                         filtered_candidates,
                         initial_candidates,
+                        score_details,
                     }) => {
+                        self.score_details = score_details;
                         let mut candidates = match candidates {
                             Some(candidates) => candidates,
                             None => {
@@ -110,12 +120,14 @@ impl<'t> Criterion for Exactness<'t> {
                         candidates,
                         filtered_candidates,
                         initial_candidates,
+                        score_details,
                     }) => {
                         return Ok(Some(CriterionResult {
                             query_tree: None,
                             candidates,
                             filtered_candidates,
                             initial_candidates,
+                            score_details,
                         }));
                     }
                     None => return Ok(None),
@@ -133,6 +145,7 @@ enum State {
     AttributeStartsWith(RoaringBitmap),
     /// Rank the remaining documents by the number of exact words contained.
     ExactWords(RoaringBitmap),
+    /// The remaining documents, indexed by the number of exact words they contain.
     Remainings(Vec<RoaringBitmap>),
 }
 
@@ -147,12 +160,28 @@ impl State {
             | Self::AttributeStartsWith(candidates)
             | Self::ExactWords(candidates) => *candidates -= lhs,
             Self::Remainings(candidates_array) => {
+                // empty bitmaps are kept as the index is the number of exact words.
                 candidates_array.iter_mut().for_each(|candidates| *candidates -= lhs);
-                candidates_array.retain(|candidates| !candidates.is_empty());
             }
         }
     }
 
+    /// The rank of the bucket that will be returned when resolving this state.
+    fn rank(&self, query_len: usize) -> Rank {
+        let query_len = query_len as u32;
+        // one bucket for each number of exact words from 0 to the query length,
+        // plus the attribute starts with and the exact attribute buckets.
+        let max_rank = query_len + 3;
+        let rank = match self {
+            Self::ExactAttribute(_) => max_rank,
+            Self::AttributeStartsWith(_) => max_rank - 1,
+            Self::ExactWords(_) => query_len + 1,
+            // the last bitmap, the next to be popped, contains `len - 1` exact words.
+            Self::Remainings(candidates_array) => candidates_array.len().max(1) as u32,
+        };
+        Rank { rank, max_rank }
+    }
+
     fn is_empty(&self) -> bool {
         match self {
             Self::ExactAttribute(candidates)
@@ -529,8 +558,13 @@ mod tests {
 
         let rtxn = index.read_txn().unwrap();
 
-        let SearchResult { matching_words: _, candidates: _, documents_ids } =
-            index.search(&rtxn).query("cats are better than dogs").execute().unwrap();
+        let SearchResult {
+            matching_words: _,
+            candidates: _,
+            document_scores: _,
+            degraded: _,
+            documents_ids,
+        } = index.search(&rtxn).query("cats are better than dogs").execute().unwrap();
 
         insta::assert_snapshot!(format!("{documents_ids:?}"), @"[5, 4, 3, 2, 1]");
     }
@@ -2,10 +2,11 @@ use log::debug;
 use roaring::RoaringBitmap;
 
 use super::{resolve_query_tree, Context, Criterion, CriterionParameters, CriterionResult};
+use crate::score_details::ScoreDetails;
 use crate::search::criteria::InitialCandidates;
 use crate::search::query_tree::Operation;
 use crate::search::WordDerivationsCache;
-use crate::Result;
+use crate::{Result, TimeBudget};
 
 /// The result of a call to the fetcher.
 #[derive(Debug, Clone, PartialEq)]
@@ -16,6 +17,8 @@ pub struct FinalResult {
     pub candidates: RoaringBitmap,
     /// Candidates that comes from the current bucket of the initial criterion.
     pub initial_candidates: InitialCandidates,
+    /// The details of the buckets the candidates fell into, one per criterion.
+    pub score_details: Vec<ScoreDetails>,
 }
 
 pub struct Final<'t> {
@@ -36,13 +39,18 @@ impl<'t> Final<'t> {
     }
 
     #[logging_timer::time("Final::{}")]
-    pub fn next(&mut self, excluded_candidates: &RoaringBitmap) -> Result<Option<FinalResult>> {
+    pub fn next(
+        &mut self,
+        excluded_candidates: &RoaringBitmap,
+        time_budget: &TimeBudget,
+    ) -> Result<Option<FinalResult>> {
         debug!("Final iteration");
         let excluded_candidates = &self.returned_candidates | excluded_candidates;
         let mut criterion_parameters = CriterionParameters {
             wdcache: &mut self.wdcache,
             // returned_candidates is merged with excluded_candidates to avoid duplicas
             excluded_candidates: &excluded_candidates,
+            time_budget,
         };
 
         match self.parent.next(&mut criterion_parameters)? {
@@ -51,6 +59,7 @@ impl<'t> Final<'t> {
                 candidates,
                 filtered_candidates,
                 initial_candidates,
+                score_details,
             }) => {
                 let mut candidates = match (candidates, query_tree.as_ref()) {
                     (Some(candidates), _) => candidates,
@@ -69,7 +78,7 @@ impl<'t> Final<'t> {
 
                 self.returned_candidates |= &candidates;
 
-                Ok(Some(FinalResult { query_tree, candidates, initial_candidates }))
+                Ok(Some(FinalResult { query_tree, candidates, initial_candidates, score_details }))
             }
             None => Ok(None),
         }
@@ -4,6 +4,7 @@ use roaring::RoaringBitmap;
 use rstar::RTree;
 
 use super::{Criterion, CriterionParameters, CriterionResult};
+use crate::score_details::{self, ScoreDetails};
 use crate::search::criteria::{resolve_query_tree, CriteriaBuilder, InitialCandidates};
 use crate::{lat_lng_to_xyz, GeoPoint, Index, Result};
 
@@ -15,6 +16,7 @@ pub struct Geo<'t> {
     candidates: Box<dyn Iterator<Item = RoaringBitmap>>,
     allowed_candidates: RoaringBitmap,
     initial_candidates: InitialCandidates,
+    score_details: Vec<ScoreDetails>,
     rtree: Option<RTree<GeoPoint>>,
     point: [f64; 2],
 }
@@ -58,6 +60,7 @@ impl<'t> Geo<'t> {
             candidates,
             allowed_candidates,
             initial_candidates,
+            score_details: Vec::new(),
             rtree,
             point,
         })
@@ -73,11 +76,19 @@ impl Criterion for Geo<'_> {
                 Some(mut candidates) => {
                     candidates -= params.excluded_candidates;
                     self.allowed_candidates -= &candidates;
+
+                    let mut score_details = self.score_details.clone();
+                    score_details.push(ScoreDetails::GeoSort(score_details::GeoSort {
+                        target_point: self.point,
+                        ascending: self.ascending,
+                    }));
+
                     return Ok(Some(CriterionResult {
                         query_tree: None,
                         candidates: Some(candidates),
                         filtered_candidates: None,
                         initial_candidates: Some(self.initial_candidates.clone()),
+                        score_details,
                     }));
                 }
                 None => match self.parent.next(params)? {
@@ -86,7 +97,9 @@ impl Criterion for Geo<'_> {
                         candidates,
                         filtered_candidates,
                         initial_candidates,
+                        score_details,
                     }) => {
+                        self.score_details = score_details;
                         let mut candidates = match (&query_tree, candidates) {
                             (_, Some(candidates)) => candidates,
                             (Some(qt), None) => {
@@ -136,6 +149,8 @@ fn geo_point(
 ) -> Box<dyn Iterator<Item = RoaringBitmap>> {
     let point = lat_lng_to_xyz(&point);
 
+    // A document can be associated with several points, it is ranked by its nearest one
+    // as it is the first one the iterator returns.
     let mut results = Vec::new();
     for point in rtree.nearest_neighbor_iter(&point) {
         if candidates.remove(point.data.0) {
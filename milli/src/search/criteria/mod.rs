@@ -3,8 +3,10 @@ use std::collections::HashMap;
 use std::mem::take;
 use std::ops::{BitOr, BitOrAssign};
 
+use heed::Database;
 use roaring::RoaringBitmap;
 
+pub(crate) use self::asc_desc::facet_ordered;
 use self::asc_desc::AscDesc;
 use self::attribute::Attribute;
 use self::exactness::Exactness;
@@ -15,10 +17,14 @@ use self::typo::Typo;
 use self::words::Words;
 use super::query_tree::{Operation, PrimitiveQueryPart, Query, QueryKind};
 use super::CriterionImplementationStrategy;
+use crate::score_details::ScoreDetails;
 use crate::search::criteria::geo::Geo;
 use crate::search::{word_derivations, Distinct, WordDerivationsCache};
 use crate::update::{MAX_LENGTH_FOR_PREFIX_PROXIMITY_DB, MAX_PROXIMITY_FOR_PREFIX_PROXIMITY_DB};
-use crate::{AscDesc as AscDescName, DocumentId, FieldId, Index, Member, Result};
+use crate::{
+    absolute_from_relative_position, relative_from_absolute_position, AscDesc as AscDescName,
+    CboRoaringBitmapCodec, DocumentId, FieldId, Index, Member, Result, StrBEU32Codec, TimeBudget,
+};
 
 mod asc_desc;
 mod attribute;
@@ -46,12 +52,16 @@ pub struct CriterionResult {
     filtered_candidates: Option<RoaringBitmap>,
     /// Candidates that comes from the current bucket of the initial criterion.
     initial_candidates: Option<InitialCandidates>,
+    /// The details of the buckets the candidates fell into, one per previous criterion.
+    score_details: Vec<ScoreDetails>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug)]
 pub struct CriterionParameters<'a> {
     wdcache: &'a mut WordDerivationsCache,
     excluded_candidates: &'a RoaringBitmap,
+    /// Once exceeded, the criteria stop refining their buckets.
+    time_budget: &'a TimeBudget,
 }
 
 /// Either a set of candidates that defines the candidates
@@ -186,6 +196,25 @@ pub struct CriteriaBuilder<'t> {
     index: &'t Index,
     words_fst: fst::Set<Cow<'t, [u8]>>,
     words_prefixes_fst: fst::Set<Cow<'t, [u8]>>,
+    /// The fields the search is restricted to, `None` if all the searchable fields are used.
+    restricted_fields: Option<RestrictedFields>,
+}
+
+/// A subset of the searchable fields that a search is restricted to.
+struct RestrictedFields {
+    /// All the restricted fields, in the order of the searchable attributes.
+    fields_ids: Vec<FieldId>,
+    /// The restricted fields that are typo tolerant, stored in the `word_docids`.
+    tolerant_fields_ids: Vec<FieldId>,
+    /// The restricted fields that are exact attributes, stored in the `exact_word_docids`.
+    exact_fields_ids: Vec<FieldId>,
+}
+
+impl RestrictedFields {
+    fn contains(&self, position: u32) -> bool {
+        let (field_id, _) = relative_from_absolute_position(position);
+        self.fields_ids.contains(&field_id)
+    }
 }
 
 /// Return the docids for the following word pairs and proximities using [`Context::word_pair_proximity_docids`].
@@ -256,19 +285,47 @@ impl<'c> Context<'c> for CriteriaBuilder<'c> {
     }
 
     fn word_docids(&self, word: &str) -> heed::Result<Option<RoaringBitmap>> {
-        self.index.word_docids.get(self.rtxn, word)
+        match &self.restricted_fields {
+            Some(restricted) => self.word_docids_in_fields(
+                self.index.word_position_docids,
+                word,
+                &restricted.tolerant_fields_ids,
+            ),
+            None => self.index.word_docids.get(self.rtxn, word),
+        }
     }
 
     fn exact_word_docids(&self, word: &str) -> heed::Result<Option<RoaringBitmap>> {
-        self.index.exact_word_docids.get(self.rtxn, word)
+        match &self.restricted_fields {
+            Some(restricted) => self.word_docids_in_fields(
+                self.index.word_position_docids,
+                word,
+                &restricted.exact_fields_ids,
+            ),
+            None => self.index.exact_word_docids.get(self.rtxn, word),
+        }
     }
 
     fn word_prefix_docids(&self, word: &str) -> heed::Result<Option<RoaringBitmap>> {
-        self.index.word_prefix_docids.get(self.rtxn, word)
+        match &self.restricted_fields {
+            Some(restricted) => self.word_docids_in_fields(
+                self.index.word_prefix_position_docids,
+                word,
+                &restricted.tolerant_fields_ids,
+            ),
+            None => self.index.word_prefix_docids.get(self.rtxn, word),
+        }
     }
 
     fn exact_word_prefix_docids(&self, word: &str) -> heed::Result<Option<RoaringBitmap>> {
-        self.index.exact_word_prefix_docids.get(self.rtxn, word)
+        match &self.restricted_fields {
+            Some(restricted) => self.word_docids_in_fields(
+                self.index.word_prefix_position_docids,
+                word,
+                &restricted.exact_fields_ids,
+            ),
+            None => self.index.exact_word_prefix_docids.get(self.rtxn, word),
+        }
     }
 
     fn word_pair_proximity_docids(
@@ -277,7 +334,12 @@ impl<'c> Context<'c> for CriteriaBuilder<'c> {
         right: &str,
         proximity: u8,
     ) -> heed::Result<Option<RoaringBitmap>> {
-        self.index.word_pair_proximity_docids.get(self.rtxn, &(proximity, left, right))
+        let docids =
+            self.index.word_pair_proximity_docids.get(self.rtxn, &(proximity, left, right))?;
+        self.restrict_docids(
+            docids,
+            &[(self.index.word_position_docids, left), (self.index.word_position_docids, right)],
+        )
     }
 
     fn word_prefix_pair_proximity_docids(
@@ -286,7 +348,17 @@ impl<'c> Context<'c> for CriteriaBuilder<'c> {
         prefix: &str,
         proximity: u8,
     ) -> heed::Result<Option<RoaringBitmap>> {
-        self.index.word_prefix_pair_proximity_docids.get(self.rtxn, &(proximity, left, prefix))
+        let docids = self
+            .index
+            .word_prefix_pair_proximity_docids
+            .get(self.rtxn, &(proximity, left, prefix))?;
+        self.restrict_docids(
+            docids,
+            &[
+                (self.index.word_position_docids, left),
+                (self.index.word_prefix_position_docids, prefix),
+            ],
+        )
     }
     fn prefix_word_pair_proximity_docids(
         &self,
@@ -294,7 +366,17 @@ impl<'c> Context<'c> for CriteriaBuilder<'c> {
         right: &str,
         proximity: u8,
     ) -> heed::Result<Option<RoaringBitmap>> {
-        self.index.prefix_word_pair_proximity_docids.get(self.rtxn, &(proximity, prefix, right))
+        let docids = self
+            .index
+            .prefix_word_pair_proximity_docids
+            .get(self.rtxn, &(proximity, prefix, right))?;
+        self.restrict_docids(
+            docids,
+            &[
+                (self.index.word_prefix_position_docids, prefix),
+                (self.index.word_position_docids, right),
+            ],
+        )
     }
 
     fn words_fst<'t>(&self) -> &'t fst::Set<Cow<[u8]>> {
@@ -311,7 +393,13 @@ impl<'c> Context<'c> for CriteriaBuilder<'c> {
     ) -> heed::Result<HashMap<String, RoaringBitmap>> {
         let mut words_positions = HashMap::new();
         for result in self.index.docid_word_positions.prefix_iter(self.rtxn, &(docid, ""))? {
-            let ((_, word), positions) = result?;
+            let ((_, word), mut positions) = result?;
+            if let Some(restricted) = &self.restricted_fields {
+                positions = positions.into_iter().filter(|&pos| restricted.contains(pos)).collect();
+                if positions.is_empty() {
+                    continue;
+                }
+            }
             words_positions.insert(word.to_string(), positions);
         }
         Ok(words_positions)
@@ -323,6 +411,25 @@ impl<'c> Context<'c> for CriteriaBuilder<'c> {
         in_prefix_cache: bool,
     ) -> heed::Result<Box<dyn Iterator<Item = heed::Result<((&'c str, u32), RoaringBitmap)>> + 'c>>
     {
+        let db = match in_prefix_cache {
+            true => self.index.word_prefix_position_docids,
+            false => self.index.word_position_docids,
+        };
+
+        if let Some(restricted) = &self.restricted_fields {
+            // The positions of a field all come before the ones of the next field id,
+            // iterating over the fields by increasing id keeps the positions ordered.
+            let mut fields_ids = restricted.fields_ids.clone();
+            fields_ids.sort_unstable();
+            let mut iters = Vec::with_capacity(fields_ids.len());
+            for field_id in fields_ids {
+                let left = (word, absolute_from_relative_position(field_id, 0));
+                let right = (word, absolute_from_relative_position(field_id, u16::MAX));
+                iters.push(db.range(self.rtxn, &(left..=right))?);
+            }
+            return Ok(Box::new(iters.into_iter().flatten()));
+        }
+
         let range = {
             let left = u32::min_value();
             let right = u32::max_value();
@@ -330,10 +437,6 @@ impl<'c> Context<'c> for CriteriaBuilder<'c> {
             let right = (word, right);
             left..=right
         };
-        let db = match in_prefix_cache {
-            true => self.index.word_prefix_position_docids,
-            false => self.index.word_position_docids,
-        };
 
         Ok(Box::new(db.range(self.rtxn, &range)?))
     }
@@ -343,6 +446,10 @@ impl<'c> Context<'c> for CriteriaBuilder<'c> {
     }
 
     fn searchable_fields_ids(&self) -> Result<Vec<FieldId>> {
+        if let Some(restricted) = &self.restricted_fields {
+            return Ok(restricted.fields_ids.clone());
+        }
+
         match self.index.searchable_fields_ids(self.rtxn)? {
             Some(searchable_fields_ids) => Ok(searchable_fields_ids),
             None => Ok(self.index.fields_ids_map(self.rtxn)?.ids().collect()),
@@ -359,8 +466,13 @@ impl<'c> Context<'c> for CriteriaBuilder<'c> {
     }
 
     fn word_position_docids(&self, word: &str, pos: u32) -> heed::Result<Option<RoaringBitmap>> {
-        let key = (word, pos);
-        self.index.word_position_docids.get(self.rtxn, &key)
+        match &self.restricted_fields {
+            Some(restricted) if !restricted.contains(pos) => Ok(None),
+            _ => {
+                let key = (word, pos);
+                self.index.word_position_docids.get(self.rtxn, &key)
+            }
+        }
     }
 }
 
@@ -368,7 +480,61 @@ impl<'t> CriteriaBuilder<'t> {
     pub fn new(rtxn: &'t heed::RoTxn<'t>, index: &'t Index) -> Result<Self> {
         let words_fst = index.words_fst(rtxn)?;
         let words_prefixes_fst = index.words_prefixes_fst(rtxn)?;
-        Ok(Self { rtxn, index, words_fst, words_prefixes_fst })
+        Ok(Self { rtxn, index, words_fst, words_prefixes_fst, restricted_fields: None })
+    }
+
+    /// Restricts the search to a subset of the searchable fields, the documents are
+    /// only matched and ranked according to the words found in these fields.
+    pub fn restrict_searchable_fields(&mut self, fields_ids: Vec<FieldId>) -> Result<()> {
+        let exact_attributes = self.index.exact_attributes_ids(self.rtxn)?;
+        let (exact_fields_ids, tolerant_fields_ids) =
+            fields_ids.iter().copied().partition(|field_id| exact_attributes.contains(field_id));
+        self.restricted_fields =
+            Some(RestrictedFields { fields_ids, tolerant_fields_ids, exact_fields_ids });
+        Ok(())
+    }
+
+    /// Returns the documents containing the word in one of the given fields,
+    /// the database must be indexed by word and absolute position.
+    fn word_docids_in_fields(
+        &self,
+        db: Database<StrBEU32Codec, CboRoaringBitmapCodec>,
+        word: &str,
+        fields_ids: &[FieldId],
+    ) -> heed::Result<Option<RoaringBitmap>> {
+        let mut docids = RoaringBitmap::new();
+        for &field_id in fields_ids {
+            let left = (word, absolute_from_relative_position(field_id, 0));
+            let right = (word, absolute_from_relative_position(field_id, u16::MAX));
+            for result in db.range(self.rtxn, &(left..=right))? {
+                let (_, field_docids) = result?;
+                docids |= field_docids;
+            }
+        }
+        Ok(Some(docids).filter(|docids| !docids.is_empty()))
+    }
+
+    /// Keeps the documents that contain all the given words in the restricted fields,
+    /// the proximity databases do not know in which fields the word pairs were found.
+    fn restrict_docids(
+        &self,
+        docids: Option<RoaringBitmap>,
+        words: &[(Database<StrBEU32Codec, CboRoaringBitmapCodec>, &str)],
+    ) -> heed::Result<Option<RoaringBitmap>> {
+        match (docids, &self.restricted_fields) {
+            (Some(mut docids), Some(restricted)) => {
+                for &(db, word) in words {
+                    if docids.is_empty() {
+                        break;
+                    }
+                    let word_docids =
+                        self.word_docids_in_fields(db, word, &restricted.fields_ids)?;
+                    docids &= word_docids.unwrap_or_default();
+                }
+                Ok(Some(docids))
+            }
+            (docids, _) => Ok(docids),
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -428,7 +594,14 @@ impl<'t> CriteriaBuilder<'t> {
                     None => criterion,
                 },
                 Name::Proximity => {
-                    Box::new(Proximity::new(self, criterion, implementation_strategy))
+                    let proximity_precision =
+                        self.index.proximity_precision(self.rtxn)?.unwrap_or_default();
+                    Box::new(Proximity::new(
+                        self,
+                        criterion,
+                        implementation_strategy,
+                        proximity_precision,
+                    ))
                 }
                 Name::Attribute => {
                     Box::new(Attribute::new(self, criterion, implementation_strategy))
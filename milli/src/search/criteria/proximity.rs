@@ -9,10 +9,13 @@ use super::{
     query_docids, query_pair_proximity_docids, resolve_phrase, resolve_query_tree, Context,
     Criterion, CriterionParameters, CriterionResult,
 };
+use crate::score_details::{Rank, ScoreDetails};
 use crate::search::criteria::InitialCandidates;
 use crate::search::query_tree::{maximum_proximity, Operation, Query, QueryKind};
-use crate::search::{build_dfa, CriterionImplementationStrategy, WordDerivationsCache};
-use crate::{Position, Result};
+use crate::search::{
+    build_dfa, word_derivations, CriterionImplementationStrategy, WordDerivationsCache,
+};
+use crate::{relative_from_absolute_position, FieldId, Position, ProximityPrecision, Result};
 
 type Cache = HashMap<(Operation, u8), Vec<(Query, Query, RoaringBitmap)>>;
 
@@ -30,10 +33,12 @@ pub struct Proximity<'t> {
     state: Option<(u8, Operation, RoaringBitmap)>,
     proximity: u8,
     initial_candidates: InitialCandidates,
+    score_details: Vec<ScoreDetails>,
     parent: Box<dyn Criterion + 't>,
     candidates_cache: Cache,
     plane_sweep_cache: Option<btree_map::IntoIter<u8, RoaringBitmap>>,
     implementation_strategy: CriterionImplementationStrategy,
+    proximity_precision: ProximityPrecision,
 }
 
 impl<'t> Proximity<'t> {
@@ -41,16 +46,19 @@ impl<'t> Proximity<'t> {
         ctx: &'t dyn Context<'t>,
         parent: Box<dyn Criterion + 't>,
         implementation_strategy: CriterionImplementationStrategy,
+        proximity_precision: ProximityPrecision,
     ) -> Self {
         Proximity {
             ctx,
             state: None,
             proximity: 0,
             initial_candidates: InitialCandidates::Estimated(RoaringBitmap::new()),
+            score_details: Vec::new(),
             parent,
             candidates_cache: Cache::new(),
             plane_sweep_cache: None,
             implementation_strategy,
+            proximity_precision,
         }
     }
 }
@@ -77,15 +85,29 @@ impl<'t> Criterion for Proximity<'t> {
                 {
                     self.state = None; // reset state
                 }
-                Some((_, query_tree, allowed_candidates)) => {
-                    let mut new_candidates = if matches!(
+                Some((max_prox, query_tree, allowed_candidates)) => {
+                    let max_prox = *max_prox;
+                    let mut new_candidates = if params.time_budget.degrade_if_exceeded() {
+                        // The search took too long: all the remaining candidates
+                        // are returned in a single, last bucket.
+                        self.proximity = max_prox;
+                        allowed_candidates.clone()
+                    } else if self.proximity_precision == ProximityPrecision::ByAttribute {
+                        // Without the word pair proximities we only know if the words are in
+                        // the same attribute: these documents come first, then all the others.
+                        if self.proximity == 0 && max_prox > 0 {
+                            resolve_same_attribute_candidates(self.ctx, query_tree, params.wdcache)?
+                        } else {
+                            self.proximity = max_prox;
+                            allowed_candidates.clone()
+                        }
+                    } else if matches!(
                         self.implementation_strategy,
                         CriterionImplementationStrategy::OnlyIterative
                     ) || (matches!(
                         self.implementation_strategy,
                         CriterionImplementationStrategy::Dynamic
-                    ) && allowed_candidates.len()
-                        <= CANDIDATES_THRESHOLD
+                    ) && allowed_candidates.len() <= CANDIDATES_THRESHOLD
                         && self.proximity > PROXIMITY_THRESHOLD)
                     {
                         if let Some(cache) = self.plane_sweep_cache.as_mut() {
@@ -122,6 +144,13 @@ impl<'t> Criterion for Proximity<'t> {
 
                     new_candidates &= &*allowed_candidates;
                     *allowed_candidates -= &new_candidates;
+
+                    let mut score_details = self.score_details.clone();
+                    score_details.push(ScoreDetails::Proximity(Rank {
+                        rank: (max_prox - self.proximity.min(max_prox)) as u32 + 1,
+                        max_rank: max_prox as u32 + 1,
+                    }));
+
                     self.proximity += 1;
 
                     return Ok(Some(CriterionResult {
@@ -129,6 +158,7 @@ impl<'t> Criterion for Proximity<'t> {
                         candidates: Some(new_candidates),
                         filtered_candidates: None,
                         initial_candidates: Some(self.initial_candidates.take()),
+                        score_details,
                     }));
                 }
                 None => match self.parent.next(params)? {
@@ -137,7 +167,9 @@ impl<'t> Criterion for Proximity<'t> {
                         candidates,
                         filtered_candidates,
                         initial_candidates,
+                        score_details,
                     }) => {
+                        self.score_details = score_details;
                         let mut candidates = match candidates {
                             Some(candidates) => candidates,
                             None => {
@@ -167,12 +199,14 @@ impl<'t> Criterion for Proximity<'t> {
                         candidates,
                         filtered_candidates,
                         initial_candidates,
+                        score_details,
                     }) => {
                         return Ok(Some(CriterionResult {
                             query_tree: None,
                             candidates,
                             filtered_candidates,
                             initial_candidates,
+                            score_details,
                         }));
                     }
                     None => return Ok(None),
@@ -346,6 +380,114 @@ fn resolve_candidates(
     Ok(candidates)
 }
 
+/// Returns the documents containing all the words of the query tree in the same attribute.
+fn resolve_same_attribute_candidates(
+    ctx: &dyn Context,
+    query_tree: &Operation,
+    wdcache: &mut WordDerivationsCache,
+) -> Result<RoaringBitmap> {
+    type FieldsDocids = HashMap<FieldId, RoaringBitmap>;
+
+    fn intersection(left: FieldsDocids, mut right: FieldsDocids) -> FieldsDocids {
+        left.into_iter()
+            .filter_map(|(field_id, mut docids)| {
+                docids &= right.remove(&field_id)?;
+                (!docids.is_empty()).then_some((field_id, docids))
+            })
+            .collect()
+    }
+
+    fn word_fields_docids(
+        ctx: &dyn Context,
+        word: &str,
+        in_prefix_cache: bool,
+        output: &mut FieldsDocids,
+    ) -> Result<()> {
+        for result in ctx.word_position_iterator(word, in_prefix_cache)? {
+            let ((_, position), docids) = result?;
+            let (field_id, _) = relative_from_absolute_position(position);
+            *output.entry(field_id).or_default() |= docids;
+        }
+        Ok(())
+    }
+
+    fn resolve_operation(
+        ctx: &dyn Context,
+        query_tree: &Operation,
+        wdcache: &mut WordDerivationsCache,
+    ) -> Result<FieldsDocids> {
+        use Operation::{And, Or, Phrase};
+
+        let result = match query_tree {
+            And(ops) => {
+                let mut output: Option<FieldsDocids> = None;
+                for op in ops {
+                    let fields_docids = resolve_operation(ctx, op, wdcache)?;
+                    output = Some(match output {
+                        Some(output) => intersection(output, fields_docids),
+                        None => fields_docids,
+                    });
+                }
+                output.unwrap_or_default()
+            }
+            Phrase(words) => {
+                let mut output: Option<FieldsDocids> = None;
+                for word in words.iter().flatten() {
+                    let mut fields_docids = FieldsDocids::new();
+                    word_fields_docids(ctx, word, false, &mut fields_docids)?;
+                    output = Some(match output {
+                        Some(output) => intersection(output, fields_docids),
+                        None => fields_docids,
+                    });
+                }
+                output.unwrap_or_default()
+            }
+            Or(_, ops) => {
+                let mut output = FieldsDocids::new();
+                for op in ops {
+                    for (field_id, docids) in resolve_operation(ctx, op, wdcache)? {
+                        *output.entry(field_id).or_default() |= docids;
+                    }
+                }
+                output
+            }
+            Operation::Query(query) => {
+                let mut output = FieldsDocids::new();
+                let in_prefix_cache = query.prefix && ctx.in_prefix_cache(query.kind.word());
+                match &query.kind {
+                    QueryKind::Exact { word, .. } => {
+                        if !query.prefix || in_prefix_cache {
+                            word_fields_docids(ctx, word, in_prefix_cache, &mut output)?;
+                        } else {
+                            for (word, _) in
+                                word_derivations(word, true, 0, ctx.words_fst(), wdcache)?
+                            {
+                                word_fields_docids(ctx, word, false, &mut output)?;
+                            }
+                        }
+                    }
+                    QueryKind::Tolerant { typo, word } => {
+                        for (word, _) in
+                            word_derivations(word, query.prefix, *typo, ctx.words_fst(), wdcache)?
+                        {
+                            word_fields_docids(ctx, word, false, &mut output)?;
+                        }
+                    }
+                }
+                output
+            }
+        };
+
+        Ok(result)
+    }
+
+    let mut candidates = RoaringBitmap::new();
+    for docids in resolve_operation(ctx, query_tree, wdcache)?.into_values() {
+        candidates |= docids;
+    }
+    Ok(candidates)
+}
+
 fn resolve_plane_sweep_candidates(
     ctx: &dyn Context,
     query_tree: &Operation,
@@ -663,7 +805,13 @@ mod tests {
 
         let rtxn = index.read_txn().unwrap();
 
-        let SearchResult { matching_words: _, candidates: _, documents_ids } = index
+        let SearchResult {
+            matching_words: _,
+            candidates: _,
+            document_scores: _,
+            degraded: _,
+            documents_ids,
+        } = index
             .search(&rtxn)
             .query("zero c")
             .criterion_implementation_strategy(CriterionImplementationStrategy::OnlySetBased)
@@ -671,7 +819,13 @@ mod tests {
             .unwrap();
         insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 3, 4, 1, 5, 0]");
 
-        let SearchResult { matching_words: _, candidates: _, documents_ids } = index
+        let SearchResult {
+            matching_words: _,
+            candidates: _,
+            document_scores: _,
+            degraded: _,
+            documents_ids,
+        } = index
             .search(&rtxn)
             .query("zero co")
             .criterion_implementation_strategy(CriterionImplementationStrategy::OnlySetBased)
@@ -679,7 +833,13 @@ mod tests {
             .unwrap();
         insta::assert_snapshot!(format!("{documents_ids:?}"), @"[2, 3, 4, 1, 5, 0]");
 
-        let SearchResult { matching_words: _, candidates: _, documents_ids } = index
+        let SearchResult {
+            matching_words: _,
+            candidates: _,
+            document_scores: _,
+            degraded: _,
+            documents_ids,
+        } = index
             .search(&rtxn)
             .query("zero con")
             .criterion_implementation_strategy(CriterionImplementationStrategy::OnlySetBased)
@@ -690,7 +850,13 @@ mod tests {
         // all of its word derivations
         insta::assert_snapshot!(format!("{documents_ids:?}"), @"[0, 1, 2, 3, 4, 5]");
 
-        let SearchResult { matching_words: _, candidates: _, documents_ids } = index
+        let SearchResult {
+            matching_words: _,
+            candidates: _,
+            document_scores: _,
+            degraded: _,
+            documents_ids,
+        } = index
             .search(&rtxn)
             .criterion_implementation_strategy(CriterionImplementationStrategy::OnlySetBased)
             .query("zero conf")
@@ -700,7 +866,13 @@ mod tests {
         // that contain `conf` exactly, and not as a prefix.
         insta::assert_snapshot!(format!("{documents_ids:?}"), @"[4, 5, 0, 1, 2, 3]");
 
-        let SearchResult { matching_words: _, candidates: _, documents_ids } = index
+        let SearchResult {
+            matching_words: _,
+            candidates: _,
+            document_scores: _,
+            degraded: _,
+            documents_ids,
+        } = index
             .search(&rtxn)
             .criterion_implementation_strategy(CriterionImplementationStrategy::OnlySetBased)
             .query("zero config")
@@ -9,6 +9,7 @@ use super::{
     query_docids, resolve_query_tree, Candidates, Context, Criterion, CriterionParameters,
     CriterionResult,
 };
+use crate::score_details::{self, ScoreDetails};
 use crate::search::criteria::{resolve_phrase, InitialCandidates};
 use crate::search::query_tree::{maximum_typo, Operation, Query, QueryKind};
 use crate::search::{word_derivations, WordDerivationsCache};
@@ -23,6 +24,7 @@ pub struct Typo<'t> {
     state: Option<(u8, Operation, Candidates)>,
     typos: u8,
     initial_candidates: Option<InitialCandidates>,
+    score_details: Vec<ScoreDetails>,
     parent: Box<dyn Criterion + 't>,
     candidates_cache: HashMap<(Operation, u8), RoaringBitmap>,
 }
@@ -34,6 +36,7 @@ impl<'t> Typo<'t> {
             state: None,
             typos: 0,
             initial_candidates: None,
+            score_details: Vec::new(),
             parent,
             candidates_cache: HashMap::new(),
         }
@@ -66,7 +69,8 @@ impl<'t> Criterion for Typo<'t> {
                 Some((_, _, Allowed(allowed_candidates))) if allowed_candidates.is_empty() => {
                     self.state = None; // reset state
                 }
-                Some((_, query_tree, candidates_authorization)) => {
+                Some((max_typos, query_tree, candidates_authorization)) => {
+                    let max_typos = *max_typos;
                     let fst = self.ctx.words_fst();
                     let new_query_tree = match self.typos {
                         typos if typos < MAX_TYPOS_PER_WORD => alterate_query_tree(
@@ -125,6 +129,12 @@ impl<'t> Criterion for Typo<'t> {
                         None => InitialCandidates::Estimated(candidates.clone()),
                     };
 
+                    let mut score_details = self.score_details.clone();
+                    score_details.push(ScoreDetails::Typo(score_details::Typo {
+                        typo_count: self.typos as u32,
+                        max_typo_count: max_typos as u32,
+                    }));
+
                     self.typos += 1;
 
                     return Ok(Some(CriterionResult {
@@ -132,6 +142,7 @@ impl<'t> Criterion for Typo<'t> {
                         candidates: Some(candidates),
                         filtered_candidates: None,
                         initial_candidates: Some(initial_candidates),
+                        score_details,
                     }));
                 }
                 None => match self.parent.next(params)? {
@@ -140,7 +151,9 @@ impl<'t> Criterion for Typo<'t> {
                         candidates,
                         filtered_candidates,
                         initial_candidates,
+                        score_details,
                     }) => {
+                        self.score_details = score_details;
                         self.initial_candidates =
                             match (self.initial_candidates.take(), initial_candidates) {
                                 (Some(self_ic), Some(parent_ic)) => Some(self_ic | parent_ic),
@@ -163,12 +176,14 @@ impl<'t> Criterion for Typo<'t> {
                         candidates,
                         filtered_candidates,
                         initial_candidates,
+                        score_details,
                     }) => {
                         return Ok(Some(CriterionResult {
                             query_tree: None,
                             candidates,
                             filtered_candidates,
                             initial_candidates,
+                            score_details,
                         }));
                     }
                     None => return Ok(None),
@@ -330,6 +345,7 @@ mod test {
     use super::super::test::TestContext;
     use super::*;
     use crate::search::NoopDistinct;
+    use crate::TimeBudget;
 
     fn display_criteria(mut criteria: Typo, mut parameters: CriterionParameters) -> String {
         let mut result = String::new();
@@ -348,6 +364,7 @@ mod test {
         let criterion_parameters = CriterionParameters {
             wdcache: &mut WordDerivationsCache::new(),
             excluded_candidates: &RoaringBitmap::new(),
+            time_budget: &TimeBudget::max(),
         };
 
         let parent =
@@ -356,7 +373,7 @@ mod test {
 
         let result = display_criteria(criteria, criterion_parameters);
         insta::assert_snapshot!(result, @r###"
-        CriterionResult { query_tree: None, candidates: None, filtered_candidates: None, initial_candidates: None }
+        CriterionResult { query_tree: None, candidates: None, filtered_candidates: None, initial_candidates: None, score_details: [] }
 
         "###);
     }
@@ -387,6 +404,7 @@ mod test {
         let criterion_parameters = CriterionParameters {
             wdcache: &mut WordDerivationsCache::new(),
             excluded_candidates: &RoaringBitmap::new(),
+            time_budget: &TimeBudget::max(),
         };
         let parent =
             Initial::<NoopDistinct>::new(&context, Some(query_tree), facet_candidates, false, None);
@@ -399,7 +417,7 @@ mod test {
             Exact { word: "split" }
             Exact { word: "this" }
             Exact { word: "world" }
-        ), candidates: Some(RoaringBitmap<[]>), filtered_candidates: None, initial_candidates: Some(Estimated(RoaringBitmap<[]>)) }
+        ), candidates: Some(RoaringBitmap<[]>), filtered_candidates: None, initial_candidates: Some(Estimated(RoaringBitmap<[]>)), score_details: [Typo(Typo { typo_count: 0, max_typo_count: 1 })] }
 
         CriterionResult { query_tree: Some(OR
           AND
@@ -408,7 +426,7 @@ mod test {
             OR
               Exact { word: "word" }
               Exact { word: "world" }
-        ), candidates: Some(RoaringBitmap<[]>), filtered_candidates: None, initial_candidates: Some(Estimated(RoaringBitmap<[]>)) }
+        ), candidates: Some(RoaringBitmap<[]>), filtered_candidates: None, initial_candidates: Some(Estimated(RoaringBitmap<[]>)), score_details: [Typo(Typo { typo_count: 1, max_typo_count: 1 })] }
 
         "###);
     }
@@ -422,6 +440,7 @@ mod test {
         let criterion_parameters = CriterionParameters {
             wdcache: &mut WordDerivationsCache::new(),
             excluded_candidates: &RoaringBitmap::new(),
+            time_budget: &TimeBudget::max(),
         };
         let parent =
             Initial::<NoopDistinct>::new(&context, query_tree, Some(facet_candidates), false, None);
@@ -429,7 +448,7 @@ mod test {
 
         let result = display_criteria(criteria, criterion_parameters);
         insta::assert_snapshot!(result, @r###"
-        CriterionResult { query_tree: None, candidates: None, filtered_candidates: Some(RoaringBitmap<8000 values between 986424 and 4294786076>), initial_candidates: None }
+        CriterionResult { query_tree: None, candidates: None, filtered_candidates: Some(RoaringBitmap<8000 values between 986424 and 4294786076>), initial_candidates: None, score_details: [] }
 
         "###);
     }
@@ -460,6 +479,7 @@ mod test {
         let criterion_parameters = CriterionParameters {
             wdcache: &mut WordDerivationsCache::new(),
             excluded_candidates: &RoaringBitmap::new(),
+            time_budget: &TimeBudget::max(),
         };
         let parent = Initial::<NoopDistinct>::new(
             &context,
@@ -477,7 +497,7 @@ mod test {
             Exact { word: "split" }
             Exact { word: "this" }
             Exact { word: "world" }
-        ), candidates: Some(RoaringBitmap<[]>), filtered_candidates: None, initial_candidates: Some(Estimated(RoaringBitmap<[]>)) }
+        ), candidates: Some(RoaringBitmap<[]>), filtered_candidates: None, initial_candidates: Some(Estimated(RoaringBitmap<[]>)), score_details: [Typo(Typo { typo_count: 0, max_typo_count: 1 })] }
 
         CriterionResult { query_tree: Some(OR
           AND
@@ -486,7 +506,7 @@ mod test {
             OR
               Exact { word: "word" }
               Exact { word: "world" }
-        ), candidates: Some(RoaringBitmap<[]>), filtered_candidates: None, initial_candidates: Some(Estimated(RoaringBitmap<[]>)) }
+        ), candidates: Some(RoaringBitmap<[]>), filtered_candidates: None, initial_candidates: Some(Estimated(RoaringBitmap<[]>)), score_details: [Typo(Typo { typo_count: 1, max_typo_count: 1 })] }
 
         "###);
     }
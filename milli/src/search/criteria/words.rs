@@ -2,6 +2,7 @@ use log::debug;
 use roaring::RoaringBitmap;
 
 use super::{resolve_query_tree, Context, Criterion, CriterionParameters, CriterionResult};
+use crate::score_details::{self, ScoreDetails};
 use crate::search::criteria::InitialCandidates;
 use crate::search::query_tree::Operation;
 use crate::Result;
@@ -9,9 +10,12 @@ use crate::Result;
 pub struct Words<'t> {
     ctx: &'t dyn Context<'t>,
     query_trees: Vec<Operation>,
+    /// The number of words matched by the best query tree.
+    max_matching_words: u32,
     candidates: Option<RoaringBitmap>,
     initial_candidates: Option<InitialCandidates>,
     filtered_candidates: Option<RoaringBitmap>,
+    score_details: Vec<ScoreDetails>,
     parent: Box<dyn Criterion + 't>,
 }
 
@@ -20,10 +24,12 @@ impl<'t> Words<'t> {
         Words {
             ctx,
             query_trees: Vec::default(),
+            max_matching_words: 0,
             candidates: None,
             initial_candidates: None,
             parent,
             filtered_candidates: None,
+            score_details: Vec::new(),
         }
     }
 }
@@ -54,11 +60,18 @@ impl<'t> Criterion for Words<'t> {
 
                     let initial_candidates = self.initial_candidates.clone();
 
+                    let mut score_details = self.score_details.clone();
+                    score_details.push(ScoreDetails::Words(score_details::Words {
+                        matching_words: count_words(&query_tree),
+                        max_matching_words: self.max_matching_words,
+                    }));
+
                     return Ok(Some(CriterionResult {
                         query_tree: Some(query_tree),
                         candidates,
                         filtered_candidates: self.filtered_candidates.clone(),
                         initial_candidates,
+                        score_details,
                     }));
                 }
                 None => match self.parent.next(params)? {
@@ -67,10 +80,14 @@ impl<'t> Criterion for Words<'t> {
                         candidates,
                         filtered_candidates,
                         initial_candidates,
+                        score_details,
                     }) => {
                         self.query_trees = explode_query_tree(query_tree);
+                        // the best query tree is the last one, it is the first to be popped.
+                        self.max_matching_words = self.query_trees.last().map_or(0, count_words);
                         self.candidates = candidates;
                         self.filtered_candidates = filtered_candidates;
+                        self.score_details = score_details;
 
                         self.initial_candidates =
                             match (self.initial_candidates.take(), initial_candidates) {
@@ -83,12 +100,14 @@ impl<'t> Criterion for Words<'t> {
                         candidates,
                         filtered_candidates,
                         initial_candidates,
+                        score_details,
                     }) => {
                         return Ok(Some(CriterionResult {
                             query_tree: None,
                             candidates,
                             filtered_candidates,
                             initial_candidates,
+                            score_details,
                         }));
                     }
                     None => return Ok(None),
@@ -104,3 +123,14 @@ fn explode_query_tree(query_tree: Operation) -> Vec<Operation> {
         otherwise => vec![otherwise],
     }
 }
+
+/// Counts the number of query words matched by a query tree,
+/// the best alternative is counted for an `Or` operation.
+fn count_words(query_tree: &Operation) -> u32 {
+    match query_tree {
+        Operation::And(ops) => ops.iter().map(count_words).sum(),
+        Operation::Or(_, ops) => ops.iter().map(count_words).max().unwrap_or(0),
+        Operation::Phrase(words) => words.iter().flatten().count() as u32,
+        Operation::Query(_) => 1,
+    }
+}
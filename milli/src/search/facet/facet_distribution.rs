@@ -1,10 +1,12 @@
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::ControlFlow;
 use std::{fmt, mem};
 
 use heed::types::ByteSlice;
 use heed::BytesDecode;
+use indexmap::IndexMap;
 use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
 
 use crate::error::UserError;
 use crate::facet::FacetType;
@@ -13,7 +15,7 @@ use crate::heed_codec::facet::{
     OrderedF64Codec,
 };
 use crate::heed_codec::{ByteSliceRefCodec, StrRefCodec};
-use crate::search::facet::facet_distribution_iter;
+use crate::search::facet::{facet_distribution_iter, facet_number_min_max};
 use crate::{FieldId, Index, Result};
 
 /// The default number of values by facets that will
@@ -24,10 +26,21 @@ pub const DEFAULT_VALUES_PER_FACET: usize = 100;
 /// the system to choose between one algorithm or another.
 const CANDIDATES_THRESHOLD: u64 = 3000;
 
+/// The order in which the values of a facet are returned.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum OrderBy {
+    /// By the lexicographic order of the values.
+    #[default]
+    Lexicographic,
+    /// By the number of documents containing the values, in decreasing order.
+    Count,
+}
+
 pub struct FacetDistribution<'a> {
     facets: Option<HashSet<String>>,
     candidates: Option<RoaringBitmap>,
     max_values_per_facet: usize,
+    order_by: HashMap<String, OrderBy>,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
 }
@@ -38,6 +51,7 @@ impl<'a> FacetDistribution<'a> {
             facets: None,
             candidates: None,
             max_values_per_facet: DEFAULT_VALUES_PER_FACET,
+            order_by: HashMap::new(),
             rtxn,
             index,
         }
@@ -53,11 +67,22 @@ impl<'a> FacetDistribution<'a> {
         self
     }
 
+    /// Defines the order of the values of each facet, the `*` entry applies
+    /// to the facets that are not explicitly listed.
+    pub fn order_by(&mut self, order_by: HashMap<String, OrderBy>) -> &mut Self {
+        self.order_by = order_by;
+        self
+    }
+
     pub fn candidates(&mut self, candidates: RoaringBitmap) -> &mut Self {
         self.candidates = Some(candidates);
         self
     }
 
+    fn order_of(&self, name: &str) -> OrderBy {
+        self.order_by.get(name).or_else(|| self.order_by.get("*")).copied().unwrap_or_default()
+    }
+
     /// There is a small amount of candidates OR we ask for facet string values so we
     /// decide to iterate over the facet values of each one of them, one by one.
     fn facet_distribution_from_documents(
@@ -65,6 +90,7 @@ impl<'a> FacetDistribution<'a> {
         field_id: FieldId,
         facet_type: FacetType,
         candidates: &RoaringBitmap,
+        max_values: usize,
         distribution: &mut BTreeMap<String, u64>,
     ) -> heed::Result<()> {
         match facet_type {
@@ -85,8 +111,7 @@ impl<'a> FacetDistribution<'a> {
                         let ((_, _, value), ()) = result?;
                         *distribution.entry(value.to_string()).or_insert(0) += 1;
 
-                        if distribution.len() - distribution_prelength == self.max_values_per_facet
-                        {
+                        if distribution.len() - distribution_prelength == max_values {
                             break;
                         }
                     }
@@ -112,7 +137,7 @@ impl<'a> FacetDistribution<'a> {
                             .or_insert_with(|| (original_value, 0));
                         *count += 1;
 
-                        if normalized_distribution.len() == self.max_values_per_facet {
+                        if normalized_distribution.len() == max_values {
                             break 'outer;
                         }
                     }
@@ -134,6 +159,7 @@ impl<'a> FacetDistribution<'a> {
         &self,
         field_id: FieldId,
         candidates: &RoaringBitmap,
+        max_values: usize,
         distribution: &mut BTreeMap<String, u64>,
     ) -> heed::Result<()> {
         facet_distribution_iter::iterate_over_facet_distribution(
@@ -146,7 +172,7 @@ impl<'a> FacetDistribution<'a> {
             |facet_key, nbr_docids, _| {
                 let facet_key = OrderedF64Codec::bytes_decode(facet_key).unwrap();
                 distribution.insert(facet_key.to_string(), nbr_docids);
-                if distribution.len() == self.max_values_per_facet {
+                if distribution.len() == max_values {
                     Ok(ControlFlow::Break(()))
                 } else {
                     Ok(ControlFlow::Continue(()))
@@ -159,6 +185,7 @@ impl<'a> FacetDistribution<'a> {
         &self,
         field_id: FieldId,
         candidates: &RoaringBitmap,
+        max_values: usize,
         distribution: &mut BTreeMap<String, u64>,
     ) -> heed::Result<()> {
         facet_distribution_iter::iterate_over_facet_distribution(
@@ -180,7 +207,7 @@ impl<'a> FacetDistribution<'a> {
                     .to_owned();
 
                 distribution.insert(original_string, nbr_docids);
-                if distribution.len() == self.max_values_per_facet {
+                if distribution.len() == max_values {
                     Ok(ControlFlow::Break(()))
                 } else {
                     Ok(ControlFlow::Continue(()))
@@ -194,6 +221,7 @@ impl<'a> FacetDistribution<'a> {
     fn facet_values_from_raw_facet_database(
         &self,
         field_id: FieldId,
+        max_values: usize,
     ) -> heed::Result<BTreeMap<String, u64>> {
         let mut distribution = BTreeMap::new();
 
@@ -210,7 +238,7 @@ impl<'a> FacetDistribution<'a> {
         for result in iter {
             let (key, value) = result?;
             distribution.insert(key.left_bound.to_string(), value.bitmap.len());
-            if distribution.len() == self.max_values_per_facet {
+            if distribution.len() == max_values {
                 break;
             }
         }
@@ -231,7 +259,7 @@ impl<'a> FacetDistribution<'a> {
                 self.index.field_id_docid_facet_strings.get(self.rtxn, &key)?.unwrap().to_owned();
 
             distribution.insert(original_string, value.bitmap.len());
-            if distribution.len() == self.max_values_per_facet {
+            if distribution.len() == max_values {
                 break;
             }
         }
@@ -239,7 +267,33 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
-    fn facet_values(&self, field_id: FieldId) -> heed::Result<BTreeMap<String, u64>> {
+    fn facet_values(
+        &self,
+        field_id: FieldId,
+        order_by: OrderBy,
+    ) -> heed::Result<IndexMap<String, u64>> {
+        // To find the most frequent values we must first retrieve all of them.
+        let max_values = match order_by {
+            OrderBy::Lexicographic => self.max_values_per_facet,
+            OrderBy::Count => usize::MAX,
+        };
+
+        let distribution = self.facet_values_in_lexicographic_order(field_id, max_values)?;
+        let mut distribution: Vec<_> = distribution.into_iter().collect();
+        if order_by == OrderBy::Count {
+            // The sort is stable, values with the same count stay in lexicographic order.
+            distribution.sort_by(|(_, left), (_, right)| right.cmp(left));
+        }
+        distribution.truncate(self.max_values_per_facet);
+
+        Ok(distribution.into_iter().collect())
+    }
+
+    fn facet_values_in_lexicographic_order(
+        &self,
+        field_id: FieldId,
+        max_values: usize,
+    ) -> heed::Result<BTreeMap<String, u64>> {
         use FacetType::{Number, String};
 
         match self.candidates {
@@ -252,34 +306,38 @@ impl<'a> FacetDistribution<'a> {
                         field_id,
                         Number,
                         candidates,
+                        max_values,
                         &mut distribution,
                     )?;
                     self.facet_distribution_from_documents(
                         field_id,
                         String,
                         candidates,
+                        max_values,
                         &mut distribution,
                     )?;
                 } else {
                     self.facet_numbers_distribution_from_facet_levels(
                         field_id,
                         candidates,
+                        max_values,
                         &mut distribution,
                     )?;
                     self.facet_strings_distribution_from_facet_levels(
                         field_id,
                         candidates,
+                        max_values,
                         &mut distribution,
                     )?;
                 }
                 Ok(distribution)
             }
-            None => self.facet_values_from_raw_facet_database(field_id),
+            None => self.facet_values_from_raw_facet_database(field_id, max_values),
         }
     }
 
-    pub fn execute(&self) -> Result<BTreeMap<String, BTreeMap<String, u64>>> {
-        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+    /// Returns the names of the requested facets, ensuring they are all filterable.
+    fn faceted_fields(&self) -> Result<HashSet<String>> {
         let filterable_fields = self.index.filterable_fields(self.rtxn)?;
 
         let fields = match self.facets {
@@ -301,10 +359,45 @@ impl<'a> FacetDistribution<'a> {
             None => filterable_fields,
         };
 
+        Ok(fields)
+    }
+
+    /// Returns the minimum and maximum numeric values of every requested facet
+    /// among the candidates. The facets without any numeric value are omitted.
+    pub fn compute_stats(&self) -> Result<BTreeMap<String, (f64, f64)>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let fields = self.faceted_fields()?;
+
+        let all_documents;
+        let candidates = match &self.candidates {
+            Some(candidates) => candidates,
+            None => {
+                all_documents = self.index.documents_ids(self.rtxn)?;
+                &all_documents
+            }
+        };
+
+        let mut stats = BTreeMap::new();
+        for (fid, name) in fields_ids_map.iter() {
+            if crate::is_faceted(name, &fields) {
+                if let Some(min_max) = facet_number_min_max(self.index, self.rtxn, fid, candidates)?
+                {
+                    stats.insert(name.to_string(), min_max);
+                }
+            }
+        }
+
+        Ok(stats)
+    }
+
+    pub fn execute(&self) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let fields = self.faceted_fields()?;
+
         let mut distribution = BTreeMap::new();
         for (fid, name) in fields_ids_map.iter() {
             if crate::is_faceted(name, &fields) {
-                let values = self.facet_values(fid)?;
+                let values = self.facet_values(fid, self.order_of(name))?;
                 distribution.insert(name.to_string(), values);
             }
         }
@@ -315,13 +408,20 @@ impl<'a> FacetDistribution<'a> {
 
 impl fmt::Debug for FacetDistribution<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let FacetDistribution { facets, candidates, max_values_per_facet, rtxn: _, index: _ } =
-            self;
+        let FacetDistribution {
+            facets,
+            candidates,
+            max_values_per_facet,
+            order_by,
+            rtxn: _,
+            index: _,
+        } = self;
 
         f.debug_struct("FacetDistribution")
             .field("facets", facets)
             .field("candidates", candidates)
             .field("max_values_per_facet", max_values_per_facet)
+            .field("order_by", order_by)
             .finish()
     }
 }
@@ -329,11 +429,11 @@ impl fmt::Debug for FacetDistribution<'_> {
 #[cfg(test)]
 mod tests {
     use big_s::S;
-    use maplit::hashset;
+    use maplit::{hashmap, hashset};
 
     use crate::documents::documents_batch_reader_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution};
+    use crate::{milli_snap, FacetDistribution, OrderBy};
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -537,4 +637,139 @@ mod tests {
 
         milli_snap!(format!("{map:?}"), "candidates_0_5_000", @"825f23a4090d05756f46176987b7d992");
     }
+
+    #[test]
+    fn order_by_count() {
+        let mut index = TempIndex::new_with_map_size(4096 * 10_000);
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("colour") }))
+            .unwrap();
+
+        let facet_values = ["Red", "Red", "Red", "Blue", "Blue", "Green"];
+
+        let mut documents = vec![];
+        for i in 0..10_000 {
+            let document = serde_json::json!({
+                "colour": facet_values[i % 6],
+            })
+            .as_object()
+            .unwrap()
+            .clone();
+            documents.push(document);
+        }
+
+        let documents = documents_batch_reader_from_objects(documents);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("colour"))
+            .order_by(hashmap! { S("*") => OrderBy::Count })
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Red": 5001, "Blue": 3333, "Green": 1666}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("colour"))
+            .max_values_per_facet(1)
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Blue": 3333}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("colour"))
+            .max_values_per_facet(1)
+            .order_by(hashmap! { S("colour") => OrderBy::Count })
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Red": 5001}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("colour"))
+            .candidates((0..5_000).into_iter().collect())
+            .max_values_per_facet(2)
+            .order_by(hashmap! { S("*") => OrderBy::Count })
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Red": 2501, "Blue": 1666}}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("colour"))
+            .candidates((0..100).into_iter().collect())
+            .max_values_per_facet(2)
+            .order_by(hashmap! {
+                S("*") => OrderBy::Lexicographic,
+                S("colour") => OrderBy::Count,
+            })
+            .execute()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"colour": {"Red": 51, "Blue": 33}}"###);
+    }
+
+    #[test]
+    fn facet_stats() {
+        let mut index = TempIndex::new_with_map_size(4096 * 10_000);
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("colour"), S("number") })
+            })
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..10_000 {
+            let document = serde_json::json!({
+                "colour": format!("{i:x}"),
+                "number": [i, -i],
+            })
+            .as_object()
+            .unwrap()
+            .clone();
+            documents.push(document);
+        }
+
+        let documents = documents_batch_reader_from_objects(documents);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let map = FacetDistribution::new(&txn, &index).compute_stats().unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"number": (-9999.0, 9999.0)}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("number"))
+            .candidates((1000..5000).into_iter().collect())
+            .compute_stats()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"number": (-4999.0, 4999.0)}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("number"))
+            .candidates([42, 7, 1337].iter().copied().collect())
+            .compute_stats()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @r###"{"number": (-1337.0, 1337.0)}"###);
+
+        let map = FacetDistribution::new(&txn, &index)
+            .facets(std::iter::once("colour"))
+            .candidates((0..100).into_iter().collect())
+            .compute_stats()
+            .unwrap();
+
+        milli_snap!(format!("{map:?}"), @"{}");
+    }
 }
@@ -5,9 +5,12 @@ use std::ops::Bound::{self, Excluded, Included};
 use either::Either;
 pub use filter_parser::{Condition, Error as FPError, FilterCondition, Span, Token};
 use roaring::RoaringBitmap;
+use rstar::AABB;
+use serde_json::Value;
 
 use super::facet_range_search;
 use crate::error::{Error, UserError};
+use crate::geo_shape::{point_in_polygon, radius_envelope};
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec,
 };
@@ -63,7 +66,7 @@ impl<'a> Display for FilterError<'a> {
                 "`{}` is a reserved keyword and thus can't be used as a filter expression.",
                 keyword
             ),
-            Self::BadGeo(keyword) => write!(f, "`{}` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` field coordinates.", keyword),
+            Self::BadGeo(keyword) => write!(f, "`{}` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])`, or `_geoPolygon([[latitude, longitude], ...])` built-in rules to filter on `_geo` field coordinates.", keyword),
             Self::BadGeoBoundingBoxTopIsBelowBottom(top, bottom) => write!(f, "The top latitude `{top}` is below the bottom latitude `{bottom}`."),
             Self::BadGeoLat(lat) => write!(f, "Bad latitude `{}`. Latitude must be contained between -90 and 90 degrees. ", lat),
             Self::BadGeoLng(lng) => write!(f, "Bad longitude `{}`. Longitude must be contained between -180 and 180 degrees. ", lng),
@@ -84,6 +87,49 @@ impl<'a> From<Filter<'a>> for FilterCondition<'a> {
 }
 
 impl<'a> Filter<'a> {
+    /// Parses a filter given as a JSON string or as an array of strings and arrays of strings.
+    pub fn from_json(facets: &'a Value) -> Result<Option<Self>> {
+        match facets {
+            Value::String(expr) => Filter::from_str(expr),
+            Value::Array(arr) => Self::parse_filter_array(arr),
+            v => Err(UserError::InvalidFilterExpression(&["String", "Array"], v.clone()).into()),
+        }
+    }
+
+    fn parse_filter_array(arr: &'a [Value]) -> Result<Option<Self>> {
+        let mut ands = Vec::new();
+        for value in arr {
+            match value {
+                Value::String(s) => ands.push(Either::Right(s.as_str())),
+                Value::Array(arr) => {
+                    let mut ors = Vec::new();
+                    for value in arr {
+                        match value {
+                            Value::String(s) => ors.push(s.as_str()),
+                            v => {
+                                return Err(UserError::InvalidFilterExpression(
+                                    &["String"],
+                                    v.clone(),
+                                )
+                                .into())
+                            }
+                        }
+                    }
+                    ands.push(Either::Left(ors));
+                }
+                v => {
+                    return Err(UserError::InvalidFilterExpression(
+                        &["String", "[String]"],
+                        v.clone(),
+                    )
+                    .into())
+                }
+            }
+        }
+
+        Filter::from_array(ands)
+    }
+
     pub fn from_array<I, J>(array: I) -> Result<Option<Self>>
     where
         I: IntoIterator<Item = Either<J, &'a str>>,
@@ -363,21 +409,32 @@ impl<'a> Filter<'a> {
                         )?;
                     }
                     let radius = radius.parse_finite_float()?;
-                    let rtree = match index.geo_rtree(rtxn)? {
-                        Some(rtree) => rtree,
-                        None => return Ok(RoaringBitmap::new()),
-                    };
+                    let mut result = RoaringBitmap::new();
+
+                    if let Some(rtree) = index.geo_rtree(rtxn)? {
+                        let xyz_base_point = lat_lng_to_xyz(&base_point);
+
+                        result |= rtree
+                            .nearest_neighbor_iter(&xyz_base_point)
+                            .take_while(|point| {
+                                distance_between_two_points(&base_point, &point.data.1)
+                                    <= radius + f64::EPSILON
+                            })
+                            .map(|point| point.data.0)
+                            .collect::<RoaringBitmap>();
+                    }
 
-                    let xyz_base_point = lat_lng_to_xyz(&base_point);
+                    if let Some(rtree) = index.geo_shapes_rtree(rtxn)? {
+                        let envelope = radius_envelope(&base_point, radius);
 
-                    let result = rtree
-                        .nearest_neighbor_iter(&xyz_base_point)
-                        .take_while(|point| {
-                            distance_between_two_points(&base_point, &point.data.1)
-                                <= radius + f64::EPSILON
-                        })
-                        .map(|point| point.data.0)
-                        .collect();
+                        result |= rtree
+                            .locate_in_envelope_intersecting(&envelope)
+                            .filter(|shape| {
+                                shape.data.1.distance_to_point(&base_point) <= radius + f64::EPSILON
+                            })
+                            .map(|shape| shape.data.0)
+                            .collect::<RoaringBitmap>();
+                    }
 
                     Ok(result)
                 } else {
@@ -504,7 +561,45 @@ impl<'a> Filter<'a> {
                         )?
                     };
 
-                    Ok(selected_lat & selected_lng)
+                    let [top, left] = top_left;
+                    let [bottom, right] = bottom_right;
+
+                    // A document with several points could match with the latitude of one point and
+                    // the longitude of another one, we make sure that one of its points is inside the box.
+                    let candidates = selected_lat & selected_lng;
+                    let selected_points = match index.geo_rtree(rtxn)? {
+                        Some(rtree) if !candidates.is_empty() => rtree
+                            .iter()
+                            .filter(|point| candidates.contains(point.data.0))
+                            .filter(|point| {
+                                let [lat, lng] = point.data.1;
+                                let lng_inside = if left > right {
+                                    lng >= left || lng <= right
+                                } else {
+                                    (left..=right).contains(&lng)
+                                };
+                                (bottom..=top).contains(&lat) && lng_inside
+                            })
+                            .map(|point| point.data.0)
+                            .collect(),
+                        _ => candidates,
+                    };
+
+                    // The documents with a GeoJSON geometry are not part of the `_geo.lat` and `_geo.lng`
+                    // facets, we check their intersection with the bounding box itself.
+                    let rectangles = if left > right {
+                        vec![[[bottom, left], [top, 180.0]], [[bottom, -180.0], [top, right]]]
+                    } else {
+                        vec![[[bottom, left], [top, right]]]
+                    };
+
+                    let mut selected_shapes = RoaringBitmap::new();
+                    for [[bottom, left], [top, right]] in rectangles {
+                        let polygon = [[bottom, left], [top, left], [top, right], [bottom, right]];
+                        selected_shapes |= geo_shapes_intersecting_polygon(rtxn, index, &polygon)?;
+                    }
+
+                    Ok(selected_points | selected_shapes)
                 } else {
                     Err(top_left_point[0].as_external_error(FilterError::AttributeNotFilterable {
                         attribute: "_geo",
@@ -512,10 +607,63 @@ impl<'a> Filter<'a> {
                     }))?
                 }
             }
+            FilterCondition::GeoPolygon { points } => {
+                if filterable_fields.contains("_geo") {
+                    let mut polygon = Vec::with_capacity(points.len());
+                    for [lat, lng] in points {
+                        let point = [lat.parse_finite_float()?, lng.parse_finite_float()?];
+                        if !(-90.0..=90.0).contains(&point[0]) {
+                            return Err(lat.as_external_error(FilterError::BadGeoLat(point[0])))?;
+                        }
+                        if !(-180.0..=180.0).contains(&point[1]) {
+                            return Err(lng.as_external_error(FilterError::BadGeoLng(point[1])))?;
+                        }
+                        polygon.push(point);
+                    }
+
+                    let mut result = RoaringBitmap::new();
+
+                    if let Some(rtree) = index.geo_rtree(rtxn)? {
+                        result |= rtree
+                            .iter()
+                            .filter(|point| point_in_polygon(&point.data.1, &polygon))
+                            .map(|point| point.data.0)
+                            .collect::<RoaringBitmap>();
+                    }
+
+                    result |= geo_shapes_intersecting_polygon(rtxn, index, &polygon)?;
+
+                    Ok(result)
+                } else {
+                    Err(points[0][0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: "_geo",
+                        filterable_fields: filterable_fields.clone(),
+                    }))?
+                }
+            }
         }
     }
 }
 
+/// Returns the documents whose GeoJSON geometry shares at least one point with the polygon.
+fn geo_shapes_intersecting_polygon(
+    rtxn: &heed::RoTxn,
+    index: &Index,
+    polygon: &[[f64; 2]],
+) -> Result<RoaringBitmap> {
+    let rtree = match index.geo_shapes_rtree(rtxn)? {
+        Some(rtree) => rtree,
+        None => return Ok(RoaringBitmap::new()),
+    };
+
+    let envelope = AABB::from_points(polygon);
+    Ok(rtree
+        .locate_in_envelope_intersecting(&envelope)
+        .filter(|shape| shape.data.1.intersects_polygon(polygon))
+        .map(|shape| shape.data.0)
+        .collect())
+}
+
 impl<'a> From<FilterCondition<'a>> for Filter<'a> {
     fn from(fc: FilterCondition<'a>) -> Self {
         Self { condition: fc }
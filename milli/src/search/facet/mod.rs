@@ -2,11 +2,15 @@ pub use facet_sort_ascending::ascending_facet_sort;
 pub use facet_sort_descending::descending_facet_sort;
 use heed::types::{ByteSlice, DecodeIgnore};
 use heed::{BytesDecode, RoTxn};
+use roaring::RoaringBitmap;
 
-pub use self::facet_distribution::{FacetDistribution, DEFAULT_VALUES_PER_FACET};
+pub use self::facet_distribution::{FacetDistribution, OrderBy, DEFAULT_VALUES_PER_FACET};
 pub use self::filter::Filter;
-use crate::heed_codec::facet::{FacetGroupKeyCodec, FacetGroupValueCodec};
+use crate::heed_codec::facet::{
+    FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue, FacetGroupValueCodec, OrderedF64Codec,
+};
 use crate::heed_codec::ByteSliceRefCodec;
+use crate::{FieldId, Index};
 mod facet_distribution;
 mod facet_distribution_iter;
 mod facet_range_search;
@@ -83,6 +87,90 @@ pub(crate) fn get_highest_level<'t>(
         .unwrap_or(0))
 }
 
+/// Get the minimum and maximum numeric facet values of the given candidates.
+///
+/// The facet levels are walked from the top, only the groups that contain some
+/// candidates are visited, so the candidates never have to be scanned one by one.
+pub(crate) fn facet_number_min_max<'t>(
+    index: &Index,
+    rtxn: &'t RoTxn<'t>,
+    field_id: FieldId,
+    candidates: &RoaringBitmap,
+) -> heed::Result<Option<(f64, f64)>> {
+    let db = index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<ByteSliceRefCodec>>();
+    let highest_level = get_highest_level(rtxn, db, field_id)?;
+    let first_bound = match get_first_facet_value::<ByteSliceRefCodec>(rtxn, db, field_id)? {
+        Some(first_bound) => first_bound,
+        None => return Ok(None),
+    };
+
+    let find_extremum = |find_max| {
+        facet_extremum(
+            rtxn,
+            db,
+            field_id,
+            highest_level,
+            first_bound,
+            usize::MAX,
+            candidates,
+            find_max,
+        )
+    };
+    match (find_extremum(false)?, find_extremum(true)?) {
+        (Some(min), Some(max)) => {
+            let min = OrderedF64Codec::bytes_decode(min).ok_or(heed::Error::Encoding)?;
+            let max = OrderedF64Codec::bytes_decode(max).ok_or(heed::Error::Encoding)?;
+            Ok(Some((min, max)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Returns the left bound of the first (or last if `find_max` is set) level 0
+/// facet value that contains one of the candidates, starting from the group of
+/// `group_size` entries at the given level and bound.
+#[allow(clippy::too_many_arguments)]
+fn facet_extremum<'t>(
+    rtxn: &'t RoTxn<'t>,
+    db: heed::Database<FacetGroupKeyCodec<ByteSliceRefCodec>, FacetGroupValueCodec>,
+    field_id: FieldId,
+    level: u8,
+    left_bound: &'t [u8],
+    group_size: usize,
+    candidates: &RoaringBitmap,
+    find_max: bool,
+) -> heed::Result<Option<&'t [u8]>> {
+    let starting_key = FacetGroupKey { field_id, level, left_bound };
+    let mut groups = Vec::new();
+    for result in db.range(rtxn, &(starting_key..))?.take(group_size) {
+        let (key, value) = result?;
+        // The range is unbounded on the right and the group size for the highest level is MAX,
+        // so we need to check that we are not iterating over the next field id
+        if key.field_id != field_id {
+            break;
+        }
+        groups.push((key.left_bound, value));
+    }
+    if find_max {
+        groups.reverse();
+    }
+
+    match groups.into_iter().find(|(_, value)| !value.bitmap.is_disjoint(candidates)) {
+        Some((left_bound, _)) if level == 0 => Ok(Some(left_bound)),
+        Some((left_bound, FacetGroupValue { size, .. })) => facet_extremum(
+            rtxn,
+            db,
+            field_id,
+            level - 1,
+            left_bound,
+            size as usize,
+            candidates,
+            find_max,
+        ),
+        None => Ok(None),
+    }
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use rand::{Rng, SeedableRng};
@@ -17,6 +17,8 @@ type IsPrefix = bool;
 #[derive(Default)]
 pub struct MatchingWords {
     inner: Vec<(Vec<Rc<MatchingWord>>, Vec<PrimitiveWordId>)>,
+    /// The fields the words of the query are scoped to, if any.
+    scopes: BTreeMap<PrimitiveWordId, String>,
 }
 
 impl fmt::Debug for MatchingWords {
@@ -36,13 +38,33 @@ impl MatchingWords {
         // in order to highlight the longuest part of the matched word.
         matching_words.sort_unstable_by_key(|(mw, _)| Reverse((mw.len(), mw[0].word.len())));
 
-        Self { inner: matching_words }
+        Self { inner: matching_words, scopes: BTreeMap::new() }
+    }
+
+    /// Restricts the matches of the given query word to the given field.
+    pub(crate) fn scope(&mut self, id: PrimitiveWordId, field: String) {
+        self.scopes.insert(id, field);
     }
 
     /// Returns an iterator over terms that match or partially match the given token.
     pub fn match_token<'a, 'b>(&'a self, token: &'b Token<'b>) -> MatchesIter<'a, 'b> {
         MatchesIter { inner: Box::new(self.inner.iter()), token }
     }
+
+    /// Returns an iterator over terms that match or partially match the given token
+    /// found in the given field, ignoring the terms scoped to other fields.
+    pub fn match_token_in_field<'a, 'b>(
+        &'a self,
+        token: &'b Token<'b>,
+        field: &'a str,
+    ) -> MatchesIter<'a, 'b> {
+        let inner = self.inner.iter().filter(move |(_, ids)| {
+            ids.iter().all(|id| {
+                self.scopes.get(id).map_or(true, |scope| crate::is_faceted_by(field, scope))
+            })
+        });
+        MatchesIter { inner: Box::new(inner), token }
+    }
 }
 
 /// Iterator over terms that match the given token,
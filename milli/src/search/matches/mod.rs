@@ -5,6 +5,8 @@ use matching_words::{MatchType, PartialMatch, PrimitiveWordId};
 pub use matching_words::{MatchingWord, MatchingWords};
 use serde::Serialize;
 
+use crate::TokenizerSettings;
+
 pub mod matching_words;
 
 const DEFAULT_CROP_MARKER: &str = "…";
@@ -15,6 +17,7 @@ const DEFAULT_HIGHLIGHT_SUFFIX: &str = "</em>";
 pub struct MatcherBuilder<'a, A> {
     matching_words: MatchingWords,
     tokenizer: Tokenizer<'a, 'a, A>,
+    tokenizer_settings: TokenizerSettings,
     crop_marker: Option<String>,
     highlight_prefix: Option<String>,
     highlight_suffix: Option<String>,
@@ -25,12 +28,19 @@ impl<'a, A> MatcherBuilder<'a, A> {
         Self {
             matching_words,
             tokenizer,
+            tokenizer_settings: TokenizerSettings::default(),
             crop_marker: None,
             highlight_prefix: None,
             highlight_suffix: None,
         }
     }
 
+    /// Tokenizes the texts with the tokenization settings of the index.
+    pub fn tokenizer_settings(&mut self, tokenizer_settings: TokenizerSettings) -> &Self {
+        self.tokenizer_settings = tokenizer_settings;
+        self
+    }
+
     pub fn crop_marker(&mut self, marker: String) -> &Self {
         self.crop_marker = Some(marker);
         self
@@ -64,12 +74,20 @@ impl<'a, A> MatcherBuilder<'a, A> {
             text,
             matching_words: &self.matching_words,
             tokenizer: &self.tokenizer,
+            tokenizer_settings: &self.tokenizer_settings,
             crop_marker,
             highlight_prefix,
             highlight_suffix,
+            field: None,
             matches: None,
         }
     }
+
+    /// Builds a matcher for a text found in the given field,
+    /// the words of the query scoped to other fields do not match it.
+    pub fn build_in_field<'t, 'm>(&'m self, text: &'t str, field: &'m str) -> Matcher<'t, 'm, A> {
+        Matcher { field: Some(field), ..self.build(text) }
+    }
 }
 
 #[derive(Copy, Clone, Default)]
@@ -107,9 +125,12 @@ pub struct Matcher<'t, 'm, A> {
     text: &'t str,
     matching_words: &'m MatchingWords,
     tokenizer: &'m Tokenizer<'m, 'm, A>,
+    tokenizer_settings: &'m TokenizerSettings,
     crop_marker: &'m str,
     highlight_prefix: &'m str,
     highlight_suffix: &'m str,
+    // the field the text was found in, if known.
+    field: Option<&'m str>,
     matches: Option<(Vec<Token<'t>>, Vec<Match>)>,
 }
 
@@ -168,7 +189,7 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
             false
         }
 
-        let tokens: Vec<_> = self.tokenizer.tokenize(self.text).collect();
+        let tokens = self.tokenizer_settings.tokenize(self.tokenizer, self.text);
         let mut matches = Vec::new();
 
         let mut words_positions = tokens
@@ -186,7 +207,11 @@ impl<'t, A: AsRef<[u8]>> Matcher<'t, '_, A> {
             .filter(|(_, _, token)| !token.is_separator());
 
         while let Some((token_position, word_position, word)) = words_positions.next() {
-            for match_type in self.matching_words.match_token(word) {
+            let matches = match self.field {
+                Some(field) => self.matching_words.match_token_in_field(word, field),
+                None => self.matching_words.match_token(word),
+            };
+            for match_type in matches {
                 match match_type {
                     // we match, we save the current token as a match,
                     // then we continue the rest of the tokens.
@@ -6,25 +6,37 @@ use std::result::Result as StdResult;
 use std::str::Utf8Error;
 use std::time::Instant;
 
+use charabia::normalizer::{CharNormalizer, CompatibilityDecompositionNormalizer};
 use charabia::TokenizerBuilder;
 use distinct::{Distinct, DocIter, FacetDistinct, NoopDistinct};
 use fst::automaton::Str;
 use fst::{Automaton, IntoStreamer, Streamer};
-use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
+use heed::types::ByteSlice;
+use levenshtein_automata::{Distance, LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use log::debug;
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
+use serde::{Deserialize, Serialize};
 
-pub use self::facet::{FacetDistribution, Filter, DEFAULT_VALUES_PER_FACET};
+pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
 use self::fst_utils::{Complement, Intersection, StartsWith, Union};
 pub use self::matches::{
     FormatOptions, MatchBounds, Matcher, MatcherBuilder, MatchingWord, MatchingWords,
 };
-use self::query_tree::QueryTreeBuilder;
+use self::query_tree::{Operation, QueryOperators, QueryTreeBuilder};
+pub use self::sort::sort_documents_ids;
+pub use self::time_budget::TimeBudget;
+use self::vector::{documents_with_similarity, nearest_documents, NearestDocuments};
 use crate::error::UserError;
+use crate::heed_codec::facet::{FacetGroupKey, FacetGroupKeyCodec, FacetGroupValue};
+use crate::heed_codec::StrRefCodec;
+use crate::score_details::{self, ScoreDetails};
 use crate::search::criteria::r#final::{Final, FinalResult};
-use crate::search::criteria::InitialCandidates;
-use crate::{AscDesc, Criterion, DocumentId, Index, Member, Result};
+use crate::search::criteria::{Context, InitialCandidates};
+use crate::{
+    locales_allow_list, AscDesc, Criterion, DocumentId, FieldId, Index, Locale, Member, Result,
+    TokenizerSettings,
+};
 
 // Building these factories is not free.
 static LEVDIST0: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(0, true));
@@ -37,19 +49,28 @@ pub mod facet;
 mod fst_utils;
 mod matches;
 mod query_tree;
+mod sort;
+mod time_budget;
+mod vector;
 
 pub struct Search<'a> {
     query: Option<String>,
+    vector: Option<Vec<f32>>,
     // this should be linked to the String in the query
     filter: Option<Filter<'a>>,
     offset: usize,
     limit: usize,
     sort_criteria: Option<Vec<AscDesc>>,
+    searchable_attributes: Option<&'a [String]>,
     terms_matching_strategy: TermsMatchingStrategy,
     authorize_typos: bool,
     words_limit: usize,
     exhaustive_number_hits: bool,
     criterion_implementation_strategy: CriterionImplementationStrategy,
+    ranking_score_threshold: Option<f64>,
+    locales: Option<Vec<Locale>>,
+    time_budget: TimeBudget,
+    distinct: Option<String>,
     rtxn: &'a heed::RoTxn<'a>,
     index: &'a Index,
 }
@@ -58,15 +79,21 @@ impl<'a> Search<'a> {
     pub fn new(rtxn: &'a heed::RoTxn, index: &'a Index) -> Search<'a> {
         Search {
             query: None,
+            vector: None,
             filter: None,
             offset: 0,
             limit: 20,
             sort_criteria: None,
+            searchable_attributes: None,
             terms_matching_strategy: TermsMatchingStrategy::default(),
             authorize_typos: true,
             exhaustive_number_hits: false,
             words_limit: 10,
             criterion_implementation_strategy: CriterionImplementationStrategy::default(),
+            ranking_score_threshold: None,
+            locales: None,
+            time_budget: TimeBudget::max(),
+            distinct: None,
             rtxn,
             index,
         }
@@ -77,6 +104,13 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Ranks the documents by the similarity between their `_vectors` and the given vector,
+    /// the query is then only used to compute the matching words.
+    pub fn vector(&mut self, vector: impl Into<Vec<f32>>) -> &mut Search<'a> {
+        self.vector = Some(vector.into());
+        self
+    }
+
     pub fn offset(&mut self, offset: usize) -> &mut Search<'a> {
         self.offset = offset;
         self
@@ -92,6 +126,12 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Restricts the search to a subset of the searchable attributes.
+    pub fn searchable_attributes(&mut self, searchable: &'a [String]) -> &mut Search<'a> {
+        self.searchable_attributes = Some(searchable);
+        self
+    }
+
     pub fn terms_matching_strategy(&mut self, value: TermsMatchingStrategy) -> &mut Search<'a> {
         self.terms_matching_strategy = value;
         self
@@ -127,16 +167,122 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Only return the documents whose global ranking score is at least the given threshold.
+    pub fn ranking_score_threshold(&mut self, ranking_score_threshold: f64) -> &mut Search<'a> {
+        self.ranking_score_threshold = Some(ranking_score_threshold);
+        self
+    }
+
+    /// Forces the tokenizer to detect the query languages among the given locales.
+    pub fn locales(&mut self, locales: Vec<Locale>) -> &mut Search<'a> {
+        self.locales = Some(locales);
+        self
+    }
+
+    /// Stops refining the ranking of the documents once the budget is exceeded,
+    /// the result is then marked as degraded.
+    pub fn time_budget(&mut self, time_budget: TimeBudget) -> &mut Search<'a> {
+        self.time_budget = time_budget;
+        self
+    }
+
+    /// Overrides the distinct field of the index for this search only,
+    /// the field must be filterable.
+    pub fn distinct(&mut self, distinct: String) -> &mut Search<'a> {
+        self.distinct = Some(distinct);
+        self
+    }
+
     fn is_typo_authorized(&self) -> Result<bool> {
         let index_authorizes_typos = self.index.authorize_typos(self.rtxn)?;
         // only authorize typos if both the index and the query allow it.
         Ok(self.authorize_typos && index_authorizes_typos)
     }
 
+    /// Returns the ids of the searchable fields matching the given attributes,
+    /// ensuring that all of them are searchable.
+    fn searchable_fields_ids_for(&self, attributes: &[String]) -> Result<Vec<FieldId>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let unknown_field = match self.index.user_defined_searchable_fields(self.rtxn)? {
+            Some(searchable) => attributes
+                .iter()
+                .find(|attr| !crate::is_faceted(attr, &searchable))
+                .map(|field| (field, searchable.into_iter().map(String::from).collect())),
+            // when all the attributes are searchable, the attribute must match a known field.
+            None => attributes
+                .iter()
+                .find(|attr| !fields_ids_map.names().any(|name| crate::is_faceted_by(name, attr)))
+                .map(|field| (field, fields_ids_map.names().map(String::from).collect())),
+        };
+
+        if let Some((field, valid_fields)) = unknown_field {
+            return Err(UserError::InvalidSearchableAttribute {
+                field: field.to_string(),
+                valid_fields,
+            }
+            .into());
+        }
+
+        let searchable_fields_ids = match self.index.searchable_fields_ids(self.rtxn)? {
+            Some(searchable_fields_ids) => searchable_fields_ids,
+            None => fields_ids_map.ids().collect(),
+        };
+
+        Ok(searchable_fields_ids
+            .into_iter()
+            .filter(|&field_id| {
+                fields_ids_map
+                    .name(field_id)
+                    .map_or(false, |name| crate::is_faceted(name, attributes))
+            })
+            .collect())
+    }
+
+    /// Keeps the candidates containing the required words and phrases of the query, and
+    /// the field-scoped ones in their field, and removes the ones containing the negated ones.
+    fn apply_query_operators(
+        &self,
+        candidates: Option<RoaringBitmap>,
+        operators: &QueryOperators,
+    ) -> Result<Option<RoaringBitmap>> {
+        if operators.is_empty() {
+            return Ok(candidates);
+        }
+
+        let mut candidates = match candidates {
+            Some(candidates) => candidates,
+            None => self.index.documents_ids(self.rtxn)?,
+        };
+        let mut ctx = criteria::CriteriaBuilder::new(self.rtxn, self.index)?;
+        let mut wdcache = WordDerivationsCache::new();
+
+        // the operations in any field are resolved first, the context
+        // is then restricted to the field of each scoped operation.
+        for operation in &operators.required {
+            candidates &= criteria::resolve_query_tree(&ctx, operation, &mut wdcache)?;
+        }
+        for (_, operation) in operators.negative.iter().filter(|(field, _)| field.is_none()) {
+            candidates -= criteria::resolve_query_tree(&ctx, operation, &mut wdcache)?;
+        }
+
+        for (field, operation) in &operators.scoped {
+            ctx.restrict_searchable_fields(self.searchable_fields_ids_for(&[field.clone()])?)?;
+            candidates &= criteria::resolve_query_tree(&ctx, operation, &mut wdcache)?;
+        }
+        for (field, operation) in &operators.negative {
+            if let Some(field) = field {
+                ctx.restrict_searchable_fields(self.searchable_fields_ids_for(&[field.clone()])?)?;
+                candidates -= criteria::resolve_query_tree(&ctx, operation, &mut wdcache)?;
+            }
+        }
+
+        Ok(Some(candidates))
+    }
+
     pub fn execute(&self) -> Result<SearchResult> {
         // We create the query tree by spliting the query into tokens.
         let before = Instant::now();
-        let (query_tree, primitive_query, matching_words) = match self.query.as_ref() {
+        let (query_tree, primitive_query, matching_words, operators) = match self.query.as_ref() {
             Some(query) => {
                 let mut builder = QueryTreeBuilder::new(self.rtxn, self.index)?;
                 builder.terms_matching_strategy(self.terms_matching_strategy);
@@ -152,13 +298,25 @@ impl<'a> Search<'a> {
                     tokbuilder.stop_words(stop_words);
                 }
 
+                // without explicit locales we fallback on the ones of the localized attributes.
+                let locales = match &self.locales {
+                    Some(locales) => locales.clone(),
+                    None => self.index.localized_attributes_locales(self.rtxn)?,
+                };
+                let allow_list = locales_allow_list(&locales);
+                if !allow_list.is_empty() {
+                    tokbuilder.allow_list(&allow_list);
+                }
+
                 let tokenizer = tokbuilder.build();
-                let tokens = tokenizer.tokenize(query);
-                builder
-                    .build(tokens)?
-                    .map_or((None, None, None), |(qt, pq, mw)| (Some(qt), Some(pq), Some(mw)))
+                let tokenizer_settings = TokenizerSettings::from_index(self.index, self.rtxn)?;
+                let tokens = tokenizer_settings.tokenize(&tokenizer, query);
+                match builder.build(tokens.into_iter())? {
+                    (Some((qt, pq, mw)), operators) => (Some(qt), Some(pq), Some(mw), operators),
+                    (None, operators) => (None, None, None, operators),
+                }
             }
-            None => (None, None, None),
+            None => (None, None, None, QueryOperators::default()),
         };
 
         debug!("query tree: {:?} took {:.02?}", query_tree, before.elapsed());
@@ -172,6 +330,8 @@ impl<'a> Search<'a> {
 
         debug!("facet candidates: {:?} took {:.02?}", filtered_candidates, before.elapsed());
 
+        let filtered_candidates = self.apply_query_operators(filtered_candidates, &operators)?;
+
         // We check that we are allowed to use the sort criteria, we check
         // that they are declared in the sortable fields.
         if let Some(sort_criteria) = &self.sort_criteria {
@@ -199,14 +359,46 @@ impl<'a> Search<'a> {
         // error if we try to use it and that it doesn't.
         let sort_ranking_rule_missing = !self.index.criteria(self.rtxn)?.contains(&Criterion::Sort);
         let empty_sort_criteria = self.sort_criteria.as_ref().map_or(true, |s| s.is_empty());
+        if self.vector.is_some() && !empty_sort_criteria {
+            return Err(UserError::SortWithVector.into());
+        }
         if sort_ranking_rule_missing && !empty_sort_criteria {
             return Err(UserError::SortRankingRuleMissing.into());
         }
 
-        let criteria_builder = criteria::CriteriaBuilder::new(self.rtxn, self.index)?;
+        let mut criteria_builder = criteria::CriteriaBuilder::new(self.rtxn, self.index)?;
+        if let Some(searchable_attributes) = self.searchable_attributes {
+            let fields_ids = self.searchable_fields_ids_for(searchable_attributes)?;
+            criteria_builder.restrict_searchable_fields(fields_ids)?;
+        }
 
-        match self.index.distinct_field(self.rtxn)? {
+        let distinct_field = match self.distinct.as_deref() {
+            Some(distinct) => {
+                let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+                if !crate::is_faceted(distinct, &filterable_fields) {
+                    return Err(UserError::InvalidDistinctAttribute {
+                        field: distinct.to_string(),
+                        valid_fields: filterable_fields.into_iter().collect(),
+                    })?;
+                }
+                Some(distinct)
+            }
+            None => self.index.distinct_field(self.rtxn)?,
+        };
+
+        match distinct_field {
             None => {
+                if let Some(vector) = &self.vector {
+                    return self.vector_search(
+                        NoopDistinct,
+                        &criteria_builder,
+                        vector,
+                        query_tree.as_ref(),
+                        filtered_candidates,
+                        matching_words.unwrap_or_default(),
+                    );
+                }
+
                 let criteria = criteria_builder.build::<NoopDistinct>(
                     query_tree,
                     primitive_query,
@@ -224,6 +416,17 @@ impl<'a> Search<'a> {
                     Some(fid) => {
                         let distinct = FacetDistinct::new(fid, self.index, self.rtxn);
 
+                        if let Some(vector) = &self.vector {
+                            return self.vector_search(
+                                distinct,
+                                &criteria_builder,
+                                vector,
+                                query_tree.as_ref(),
+                                filtered_candidates,
+                                matching_words.unwrap_or_default(),
+                            );
+                        }
+
                         let criteria = criteria_builder.build(
                             query_tree,
                             primitive_query,
@@ -241,6 +444,85 @@ impl<'a> Search<'a> {
         }
     }
 
+    /// Ranks the documents matching the query by their similarity with the vector,
+    /// the query is only used to select the documents and must be resolved in the `ctx`.
+    ///
+    /// The ranking rules do not apply, the documents are only ranked by similarity.
+    /// When the time budget is exceeded, the results only come from the approximate
+    /// neighbors and the search is marked as degraded.
+    fn vector_search<D: Distinct>(
+        &self,
+        distinct: D,
+        ctx: &dyn Context,
+        vector: &[f32],
+        query_tree: Option<&Operation>,
+        filtered_candidates: Option<RoaringBitmap>,
+        matching_words: MatchingWords,
+    ) -> Result<SearchResult> {
+        let mut universe = match filtered_candidates {
+            Some(candidates) => candidates,
+            None => self.index.documents_ids(self.rtxn)?,
+        };
+        universe -= self.index.soft_deleted_documents_ids(self.rtxn)?;
+
+        if let Some(query_tree) = query_tree {
+            let mut wdcache = WordDerivationsCache::new();
+            universe &= criteria::resolve_query_tree(ctx, query_tree, &mut wdcache)?;
+        }
+
+        let NearestDocuments { candidates, documents } = nearest_documents(
+            self.index,
+            self.rtxn,
+            vector,
+            &universe,
+            distinct,
+            self.offset,
+            self.limit,
+            &self.time_budget,
+        )?;
+
+        let above_threshold = |similarity| match self.ranking_score_threshold {
+            Some(ranking_score_threshold) => {
+                let score_details = [ScoreDetails::Vector(score_details::Vector { similarity })];
+                ScoreDetails::global_score(score_details.iter()) >= ranking_score_threshold
+            }
+            None => true,
+        };
+
+        let mut documents_ids = Vec::new();
+        let mut document_scores = Vec::new();
+        for (docid, similarity) in documents {
+            // the documents are ordered by similarity, the next ones can only be worse.
+            if !above_threshold(similarity) {
+                break;
+            }
+            documents_ids.push(docid);
+            document_scores.push(vec![ScoreDetails::Vector(score_details::Vector { similarity })]);
+        }
+
+        // the candidates below the threshold are not counted, which requires comparing
+        // the target with all their vectors, as long as the time budget allows it.
+        let candidates = match self.ranking_score_threshold {
+            Some(_) => documents_with_similarity(
+                self.index,
+                self.rtxn,
+                vector,
+                &candidates,
+                above_threshold,
+                &self.time_budget,
+            )?,
+            None => candidates,
+        };
+
+        Ok(SearchResult {
+            matching_words,
+            candidates,
+            documents_ids,
+            document_scores,
+            degraded: self.time_budget.is_degraded(),
+        })
+    }
+
     fn perform_sort<D: Distinct>(
         &self,
         mut distinct: D,
@@ -251,17 +533,28 @@ impl<'a> Search<'a> {
         let mut initial_candidates = InitialCandidates::Estimated(RoaringBitmap::new());
         let mut excluded_candidates = self.index.soft_deleted_documents_ids(self.rtxn)?;
         let mut documents_ids = Vec::new();
+        let mut document_scores = Vec::new();
+        let mut below_threshold_candidates = RoaringBitmap::new();
 
-        while let Some(FinalResult { candidates, initial_candidates: ic, .. }) =
-            criteria.next(&excluded_candidates)?
+        while let Some(FinalResult { candidates, initial_candidates: ic, score_details, .. }) =
+            criteria.next(&excluded_candidates, &self.time_budget)?
         {
             debug!("Number of candidates found {}", candidates.len());
 
+            initial_candidates |= ic;
+
+            if let Some(ranking_score_threshold) = self.ranking_score_threshold {
+                // the buckets are not ordered by score when a sort rule comes before the
+                // relevancy rules, better buckets can still follow a skipped one.
+                if ScoreDetails::global_score(score_details.iter()) < ranking_score_threshold {
+                    below_threshold_candidates |= candidates;
+                    continue;
+                }
+            }
+
             let excluded = take(&mut excluded_candidates);
             let mut candidates = distinct.distinct(candidates, excluded);
 
-            initial_candidates |= ic;
-
             if offset != 0 {
                 let discarded = candidates.by_ref().take(offset).count();
                 offset = offset.saturating_sub(discarded);
@@ -269,21 +562,28 @@ impl<'a> Search<'a> {
 
             for candidate in candidates.by_ref().take(self.limit - documents_ids.len()) {
                 documents_ids.push(candidate?);
+                document_scores.push(score_details.clone());
             }
 
             excluded_candidates |= candidates.into_excluded();
 
+            // the remaining buckets are not visited, the candidates can then
+            // count documents that are below the threshold.
             if documents_ids.len() == self.limit {
                 break;
             }
         }
 
         initial_candidates.map_inplace(|c| c - excluded_candidates);
+        let mut candidates = initial_candidates.into_inner();
+        candidates -= below_threshold_candidates;
 
         Ok(SearchResult {
             matching_words,
-            candidates: initial_candidates.into_inner(),
+            candidates,
             documents_ids,
+            document_scores,
+            degraded: self.time_budget.is_degraded(),
         })
     }
 }
@@ -292,39 +592,193 @@ impl fmt::Debug for Search<'_> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let Search {
             query,
+            vector,
             filter,
             offset,
             limit,
             sort_criteria,
+            searchable_attributes,
             terms_matching_strategy,
             authorize_typos,
             words_limit,
             exhaustive_number_hits,
             criterion_implementation_strategy,
+            ranking_score_threshold,
+            locales,
+            time_budget,
+            distinct,
             rtxn: _,
             index: _,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
+            .field("vector", vector)
             .field("filter", filter)
             .field("offset", offset)
             .field("limit", limit)
             .field("sort_criteria", sort_criteria)
+            .field("searchable_attributes", searchable_attributes)
             .field("terms_matching_strategy", terms_matching_strategy)
             .field("authorize_typos", authorize_typos)
             .field("exhaustive_number_hits", exhaustive_number_hits)
             .field("criterion_implementation_strategy", criterion_implementation_strategy)
+            .field("ranking_score_threshold", ranking_score_threshold)
+            .field("locales", locales)
+            .field("time_budget", time_budget)
+            .field("distinct", distinct)
             .field("words_limit", words_limit)
             .finish()
     }
 }
 
+/// Searches for the values of a filterable attribute, optionally restricted
+/// to the documents matching a search query and filter.
+pub struct SearchForFacetValues<'a> {
+    query: Option<String>,
+    facet: String,
+    search_query: Search<'a>,
+    max_values: usize,
+}
+
+impl<'a> SearchForFacetValues<'a> {
+    pub fn new(facet: String, search_query: Search<'a>) -> SearchForFacetValues<'a> {
+        SearchForFacetValues {
+            query: None,
+            facet,
+            search_query,
+            max_values: DEFAULT_VALUES_PER_FACET,
+        }
+    }
+
+    pub fn query(&mut self, query: impl Into<String>) -> &mut SearchForFacetValues<'a> {
+        self.query = Some(query.into());
+        self
+    }
+
+    pub fn max_values(&mut self, max: usize) -> &mut SearchForFacetValues<'a> {
+        self.max_values = max;
+        self
+    }
+
+    fn max_typos_for(&self, query: &str) -> Result<u8> {
+        if !self.search_query.is_typo_authorized()? {
+            return Ok(0);
+        }
+
+        let rtxn = self.search_query.rtxn;
+        let count = query.chars().count().min(u8::MAX as usize) as u8;
+        if count < self.search_query.index.min_word_len_one_typo(rtxn)? {
+            Ok(0)
+        } else if count < self.search_query.index.min_word_len_two_typos(rtxn)? {
+            Ok(1)
+        } else {
+            Ok(2)
+        }
+    }
+
+    pub fn execute(&mut self) -> Result<Vec<FacetValueHit>> {
+        let index = self.search_query.index;
+        let rtxn = self.search_query.rtxn;
+
+        let filterable_fields = index.filterable_fields(rtxn)?;
+        if !filterable_fields.contains(&self.facet) {
+            return Err(UserError::InvalidFacetSearchFacetName {
+                field: self.facet.clone(),
+                valid_fields: filterable_fields.into_iter().collect(),
+            }
+            .into());
+        }
+
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let field_id = match fields_ids_map.id(&self.facet) {
+            Some(field_id) => field_id,
+            None => return Ok(Vec::new()),
+        };
+
+        // the facet values are only restricted to the documents matching
+        // the search when there is a query or a filter.
+        let is_placeholder = self.search_query.query.is_none();
+        let candidates = if is_placeholder && self.search_query.filter.is_none() {
+            None
+        } else {
+            self.search_query.exhaustive_number_hits(true).offset(0).limit(0);
+            Some(self.search_query.execute()?.candidates)
+        };
+        let soft_deleted = index.soft_deleted_documents_ids(rtxn)?;
+
+        // the facet values are normalized the same way at indexing time.
+        let query = self.query.as_deref().map(|query| {
+            CompatibilityDecompositionNormalizer.normalize_str(query.trim()).to_lowercase()
+        });
+        let dfa = match query {
+            Some(query) if !query.is_empty() => {
+                let typos = self.max_typos_for(&query)?;
+                Some(build_dfa(&query, typos, true))
+            }
+            _ => None,
+        };
+
+        let mut prefix = field_id.to_be_bytes().to_vec();
+        prefix.push(0); // the level 0 of the facet database
+        let iter = index
+            .facet_id_string_docids
+            .remap_key_type::<ByteSlice>()
+            .prefix_iter(rtxn, &prefix)?
+            .remap_key_type::<FacetGroupKeyCodec<StrRefCodec>>();
+
+        let mut results = Vec::new();
+        for result in iter {
+            let (FacetGroupKey { left_bound, .. }, FacetGroupValue { bitmap, .. }) = result?;
+
+            if let Some(dfa) = &dfa {
+                if !matches!(dfa.eval(left_bound), Distance::Exact(_)) {
+                    continue;
+                }
+            }
+
+            let mut docids = bitmap - &soft_deleted;
+            if let Some(candidates) = &candidates {
+                docids &= candidates;
+            }
+
+            let any_docid = match docids.min() {
+                Some(docid) => docid,
+                None => continue,
+            };
+
+            let key = (field_id, any_docid, left_bound);
+            let value = match index.field_id_docid_facet_strings.get(rtxn, &key)? {
+                Some(original) => original.to_owned(),
+                None => left_bound.to_owned(),
+            };
+            results.push(FacetValueHit { value, count: docids.len() });
+
+            if results.len() == self.max_values {
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, PartialEq)]
+pub struct FacetValueHit {
+    /// The original facet value
+    pub value: String,
+    /// The number of documents associated to this facet
+    pub count: u64,
+}
+
 #[derive(Default, Debug)]
 pub struct SearchResult {
     pub matching_words: MatchingWords,
     pub candidates: RoaringBitmap,
-    // TODO those documents ids should be associated with their criteria scores.
     pub documents_ids: Vec<DocumentId>,
+    /// The details of the buckets each document fell into, in the same order as `documents_ids`.
+    pub document_scores: Vec<Vec<ScoreDetails>>,
+    /// Whether the time budget was exceeded and the ranking of the documents left unrefined.
+    pub degraded: bool,
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -357,6 +811,17 @@ impl Default for TermsMatchingStrategy {
     }
 }
 
+/// Whether the last word of the query can be searched as a prefix.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrefixSearch {
+    /// The prefix databases are built at indexing time
+    /// and the last word of the query is searched as a prefix.
+    #[default]
+    IndexingTime,
+    /// The prefix databases are not built and all the words of the query are full words.
+    Disabled,
+}
+
 pub type WordDerivationsCache = HashMap<(String, bool, u8), Vec<(String, u8)>>;
 
 pub fn word_derivations<'c>(
@@ -443,6 +908,9 @@ pub fn build_dfa(word: &str, typos: u8, is_prefix: bool) -> DFA {
 
 #[cfg(test)]
 mod test {
+    use big_s::S;
+    use maplit::hashset;
+
     use super::*;
     use crate::index::tests::TempIndex;
 
@@ -471,6 +939,82 @@ mod test {
         assert!(!search.is_typo_authorized().unwrap());
     }
 
+    #[test]
+    fn negated_and_field_scoped_terms() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|settings| {
+                settings.set_searchable_fields(vec![S("title"), S("description")]);
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "iphone", "description": "a brand new phone" },
+                { "id": 1, "title": "iphone refurbished", "description": "a second hand phone" },
+                { "id": 2, "title": "phone case", "description": "fits your iphone" },
+                { "id": 3, "title": "iphone", "description": "refurbished like new" },
+            ]))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let search = |query: &str| {
+            let mut search = Search::new(&txn, &index);
+            search.query(query);
+            let mut ids = search.execute().unwrap().documents_ids;
+            ids.sort_unstable();
+            ids
+        };
+
+        assert_eq!(search("iphone"), vec![0, 1, 2, 3]);
+        assert_eq!(search("iphone -refurbished"), vec![0, 2]);
+        assert_eq!(search("-refurbished"), vec![0, 2]);
+        assert_eq!(search("iphone -\"like new\""), vec![0, 1, 2]);
+        assert_eq!(search("title:iphone"), vec![0, 1, 3]);
+        assert_eq!(search("title:iphone -description:refurbished"), vec![0, 1]);
+        assert_eq!(search("\"refurbished -like\""), vec![3]);
+        assert_eq!(search("iphone - refurbished"), vec![0, 1, 2, 3]);
+        assert_eq!(search("phone refurbished"), vec![0, 1, 2]);
+        assert_eq!(search("phone +refurbished"), vec![1]);
+    }
+
+    #[test]
+    fn ranking_score_threshold_with_a_sort_rule_first() {
+        let index = TempIndex::new();
+        index
+            .update_settings(|settings| {
+                settings.set_sortable_fields(hashset! { S("rank") });
+                settings.set_criteria(vec![Criterion::Sort, Criterion::Words]);
+            })
+            .unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "glass captain", "rank": 2 },
+                { "id": 1, "title": "glass", "rank": 1 },
+                { "id": 2, "title": "glass captain", "rank": 3 },
+            ]))
+            .unwrap();
+
+        let txn = index.read_txn().unwrap();
+        let search = |limit: usize| {
+            let mut search = Search::new(&txn, &index);
+            search.query("glass captain");
+            search.sort_criteria(vec!["rank:asc".parse().unwrap()]);
+            search.ranking_score_threshold(0.9);
+            search.limit(limit);
+            search.execute().unwrap()
+        };
+
+        // the first bucket is below the threshold, the buckets after it are still returned.
+        let result = search(20);
+        assert_eq!(result.documents_ids, vec![0, 2]);
+        assert_eq!(result.candidates, RoaringBitmap::from_iter([0, 2]));
+
+        // the buckets after a full page are not visited, the candidates are only estimated.
+        let result = search(1);
+        assert_eq!(result.documents_ids, vec![0]);
+        assert!(result.candidates.is_superset(&RoaringBitmap::from_iter([0, 2])));
+    }
+
     #[test]
     fn test_one_typos_tolerance() {
         let fst = fst::Set::from_iter(["zealand"].iter()).unwrap().map_data(Cow::Owned).unwrap();
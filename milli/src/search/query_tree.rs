@@ -6,13 +6,12 @@ use std::hash::Hash;
 use std::rc::Rc;
 use std::{fmt, mem};
 
-use charabia::normalizer::NormalizedTokenIter;
-use charabia::{SeparatorKind, TokenKind};
+use charabia::{SeparatorKind, Token, TokenKind};
 use roaring::RoaringBitmap;
 use slice_group_by::GroupBy;
 
 use crate::search::matches::matching_words::{MatchingWord, PrimitiveWordId};
-use crate::search::TermsMatchingStrategy;
+use crate::search::{PrefixSearch, TermsMatchingStrategy};
 use crate::{CboRoaringBitmapLenCodec, Index, MatchingWords, Result};
 
 type IsOptionalWord = bool;
@@ -99,6 +98,24 @@ impl Operation {
     }
 }
 
+/// The operators of the query that restrict the candidates without ranking them.
+#[derive(Debug, Default, Clone)]
+pub struct QueryOperators {
+    /// The documents matching these operations, in the given field if any,
+    /// are removed from the candidates.
+    pub negative: Vec<(Option<String>, Operation)>,
+    /// The candidates must match these operations in the given field.
+    pub scoped: Vec<(String, Operation)>,
+    /// The candidates must match these operations in any field.
+    pub required: Vec<Operation>,
+}
+
+impl QueryOperators {
+    pub fn is_empty(&self) -> bool {
+        self.negative.is_empty() && self.scoped.is_empty() && self.required.is_empty()
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub struct Query {
     pub prefix: IsPrefix,
@@ -180,6 +197,7 @@ pub struct QueryTreeBuilder<'a> {
     authorize_typos: bool,
     words_limit: Option<usize>,
     exact_words: Option<fst::Set<Cow<'a, [u8]>>>,
+    prefix_search: PrefixSearch,
 }
 
 impl<'a> Context for QueryTreeBuilder<'a> {
@@ -230,6 +248,7 @@ impl<'a> QueryTreeBuilder<'a> {
             authorize_typos: true,
             words_limit: None,
             exact_words: index.exact_words(rtxn)?,
+            prefix_search: index.prefix_search(rtxn)?.unwrap_or_default(),
         })
     }
 
@@ -268,11 +287,31 @@ impl<'a> QueryTreeBuilder<'a> {
     /// - if `authorize_typos` is set to `false` the query tree will be generated
     ///   forcing all query words to match documents without any typo
     ///   (the criterion `typo` will be ignored)
-    pub fn build<A: AsRef<[u8]>>(
+    ///
+    /// The negated and field-scoped words and phrases are also returned
+    /// as operators that restrict the candidates.
+    #[allow(clippy::type_complexity)]
+    pub fn build<'o>(
         &self,
-        query: NormalizedTokenIter<A>,
-    ) -> Result<Option<(Operation, PrimitiveQuery, MatchingWords)>> {
-        let primitive_query = create_primitive_query(query, self.words_limit);
+        query: impl Iterator<Item = Token<'o>>,
+    ) -> Result<(Option<(Operation, PrimitiveQuery, MatchingWords)>, QueryOperators)> {
+        let searchable_fields = self.searchable_fields()?;
+        let (mut primitive_query, primitive_operators) =
+            create_primitive_query(query, self.words_limit, &searchable_fields);
+        // without the prefix databases the last word of the query is a full word.
+        if self.prefix_search == PrefixSearch::Disabled {
+            for part in &mut primitive_query {
+                if let PrimitiveQueryPart::Word(_, is_prefix) = part {
+                    *is_prefix = false;
+                }
+            }
+        }
+        let operators = create_query_operators(
+            self,
+            self.authorize_typos,
+            &primitive_query,
+            &primitive_operators,
+        )?;
         if !primitive_query.is_empty() {
             let qt = create_query_tree(
                 self,
@@ -280,11 +319,26 @@ impl<'a> QueryTreeBuilder<'a> {
                 self.authorize_typos,
                 &primitive_query,
             )?;
-            let matching_words =
-                create_matching_words(self, self.authorize_typos, &primitive_query)?;
-            Ok(Some((qt, primitive_query, matching_words)))
+            let matching_words = create_matching_words(
+                self,
+                self.authorize_typos,
+                &primitive_query,
+                &primitive_operators.scoped,
+            )?;
+            Ok((Some((qt, primitive_query, matching_words)), operators))
         } else {
-            Ok(None)
+            Ok((None, operators))
+        }
+    }
+
+    /// The names of the fields that can be used to scope a word of the query.
+    fn searchable_fields(&self) -> Result<Vec<String>> {
+        match self.index.searchable_fields(self.rtxn)? {
+            Some(fields) => Ok(fields.into_iter().map(String::from).collect()),
+            None => {
+                let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+                Ok(fields_ids_map.names().map(String::from).collect())
+            }
         }
     }
 }
@@ -572,6 +626,7 @@ fn create_matching_words(
     ctx: &impl Context,
     authorize_typos: bool,
     query: &[PrimitiveQueryPart],
+    scoped: &[(String, usize)],
 ) -> Result<MatchingWords> {
     /// Matches on the `PrimitiveQueryPart` and create matchings words from it.
     fn resolve_primitive_part(
@@ -747,12 +802,65 @@ fn create_matching_words(
     let mut matching_word_cache = MatchingWordCache::default();
     let mut matching_words = Vec::new();
     ngrams(ctx, authorize_typos, query, &mut matching_words, &mut matching_word_cache, 0)?;
-    Ok(MatchingWords::new(matching_words))
+    let mut matching_words = MatchingWords::new(matching_words);
+
+    // the ids of a part start after the ones of all the parts preceding it.
+    for (field, index) in scoped {
+        let first_id: usize = query[..*index].iter().map(PrimitiveQueryPart::len).sum();
+        for id in first_id..first_id + query[*index].len() {
+            matching_words.scope(id as PrimitiveWordId, field.clone());
+        }
+    }
+
+    Ok(matching_words)
+}
+
+/// Creates the operations that restrict the candidates from the negated,
+/// field-scoped and required words and phrases of the query.
+fn create_query_operators(
+    ctx: &impl Context,
+    authorize_typos: bool,
+    primitive_query: &[PrimitiveQueryPart],
+    operators: &PrimitiveOperators,
+) -> Result<QueryOperators> {
+    let (word_len_one_typo, word_len_two_typo) = ctx.min_word_len_for_typo()?;
+    let exact_words = ctx.exact_words();
+    let config = TypoConfig { max_typos: 2, word_len_one_typo, word_len_two_typo, exact_words };
+
+    // the documents containing a negated word are excluded without typo tolerance,
+    // excluding documents that only contain a similar word would be surprising.
+    let negative = operators
+        .negative
+        .iter()
+        .map(|(scope, part)| {
+            let operation = match part {
+                PrimitiveQueryPart::Word(word, _) => {
+                    Operation::Query(Query { prefix: false, kind: QueryKind::exact(word.clone()) })
+                }
+                PrimitiveQueryPart::Phrase(words) => Operation::phrase(words.clone()),
+            };
+            (scope.clone(), operation)
+        })
+        .collect();
+
+    let operation = |index: usize| match &primitive_query[index] {
+        PrimitiveQueryPart::Word(word, prefix) => Operation::Query(Query {
+            prefix: *prefix,
+            kind: typos(word.clone(), authorize_typos, config.clone()),
+        }),
+        PrimitiveQueryPart::Phrase(words) => Operation::phrase(words.clone()),
+    };
+
+    let scoped =
+        operators.scoped.iter().map(|(field, index)| (field.clone(), operation(*index))).collect();
+    let required = operators.required.iter().map(|index| operation(*index)).collect();
+
+    Ok(QueryOperators { negative, scoped, required })
 }
 
 pub type PrimitiveQuery = Vec<PrimitiveQueryPart>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PrimitiveQueryPart {
     Phrase(Vec<Option<String>>),
     Word(String, IsPrefix),
@@ -775,30 +883,90 @@ impl PrimitiveQueryPart {
     }
 }
 
+/// The parts of the query that are prefixed by an operator.
+#[derive(Debug, Default, Clone)]
+struct PrimitiveOperators {
+    /// The parts prefixed by a `-` and their optional field scope,
+    /// they are not part of the primitive query.
+    negative: Vec<(Option<String>, PrimitiveQueryPart)>,
+    /// The field scopes of the parts of the primitive query, along with their index.
+    scoped: Vec<(String, usize)>,
+    /// The indexes of the parts of the primitive query prefixed by a `+`
+    /// that are not scoped to a field.
+    required: Vec<usize>,
+}
+
+/// The operators that prefix the next word or phrase of the query.
+#[derive(Debug, Default, Clone)]
+struct Modifiers {
+    negative: bool,
+    required: bool,
+    scope: Option<String>,
+}
+
+/// A word or a phrase of the query along with the operators that prefix it.
+struct ParsedPart {
+    part: PrimitiveQueryPart,
+    modifiers: Modifiers,
+}
+
+/// Returns the `-` or the `+` that ends the separator if it is not in the middle of a word,
+/// meaning that the next word or phrase must be excluded or required.
+fn prefix_operator(separator: &str, after_whitespace: bool) -> Option<char> {
+    let operator = separator.chars().last().filter(|c| matches!(c, '-' | '+'))?;
+    let before = separator[..separator.len() - operator.len_utf8()].chars().last();
+    before.map_or(after_whitespace, char::is_whitespace).then_some(operator)
+}
+
+/// Returns the part of the separator that is outside of the quotes and prefixes
+/// the next word or phrase, or `None` if the separator is inside a phrase.
+///
+/// `quoted` is whether the separator ends inside a phrase.
+fn unquoted_operator(separator: &str, quoted: bool) -> Option<&str> {
+    match separator.rfind('"') {
+        // the last quote opens a phrase, the operator is just before it.
+        Some(last) if quoted => separator[..last].rsplit('"').next(),
+        // the last quote closes a phrase, the operator is after it.
+        Some(last) => Some(&separator[last + 1..]),
+        None if quoted => None,
+        None => Some(separator),
+    }
+}
+
 /// Create primitive query from tokenized query string,
 /// the primitive query is an intermediate state to build the query tree.
-fn create_primitive_query<A>(
-    query: NormalizedTokenIter<A>,
+///
+/// The words and phrases prefixed by a `-` are excluded from the primitive query, the ones
+/// prefixed by a `+` are required and the ones prefixed by the name of a searchable field
+/// and a `:` are scoped to this field. The operators are ignored inside of a phrase.
+fn create_primitive_query<'o>(
+    query: impl Iterator<Item = Token<'o>>,
     words_limit: Option<usize>,
-) -> PrimitiveQuery
-where
-    A: AsRef<[u8]>,
-{
-    let mut primitive_query = Vec::new();
+    searchable_fields: &[String],
+) -> (PrimitiveQuery, PrimitiveOperators) {
+    let mut parsed: Vec<ParsedPart> = Vec::new();
     let mut phrase = Vec::new();
     let mut quoted = false;
+    let mut modifiers = Modifiers::default();
+    let mut phrase_modifiers = Modifiers::default();
+    // whether the previous token ends with a whitespace or is the start of the query.
+    let mut after_whitespace = true;
+    // whether the previous token is a word that was pushed as a part of the query.
+    let mut after_word = false;
 
     let parts_limit = words_limit.unwrap_or(usize::MAX);
 
     let mut peekable = query.peekable();
     while let Some(token) = peekable.next() {
         // early return if word limit is exceeded
-        if primitive_query.len() >= parts_limit {
-            return primitive_query;
+        if parsed.len() >= parts_limit {
+            break;
         }
 
         match token.kind {
             TokenKind::Word | TokenKind::StopWord => {
+                after_whitespace = false;
+                after_word = false;
                 // 1. if the word is quoted we push it in a phrase-buffer waiting for the ending quote,
                 // 2. if the word is not the last token of the query and is not a stop_word we push it as a non-prefix word,
                 // 3. if the word is the last token of the query we push it as a prefix word.
@@ -810,36 +978,89 @@ where
                     }
                 } else if peekable.peek().is_some() {
                     if let TokenKind::StopWord = token.kind {
+                        modifiers = Modifiers::default();
                     } else {
-                        primitive_query
-                            .push(PrimitiveQueryPart::Word(token.lemma().to_string(), false));
+                        let part = PrimitiveQueryPart::Word(token.lemma().to_string(), false);
+                        parsed.push(ParsedPart { part, modifiers: mem::take(&mut modifiers) });
+                        after_word = true;
                     }
                 } else {
-                    primitive_query.push(PrimitiveQueryPart::Word(token.lemma().to_string(), true));
+                    let part = PrimitiveQueryPart::Word(token.lemma().to_string(), true);
+                    parsed.push(ParsedPart { part, modifiers: mem::take(&mut modifiers) });
                 }
             }
             TokenKind::Separator(separator_kind) => {
-                let quote_count = token.lemma().chars().filter(|&s| s == '"').count();
+                let lemma = token.lemma();
+                let quote_count = lemma.chars().filter(|&s| s == '"').count();
                 // swap quoted state if we encounter a double quote
                 if quote_count % 2 != 0 {
                     quoted = !quoted;
                 }
+
+                if let Some(operator) = unquoted_operator(lemma, quoted) {
+                    // a word directly followed by a `:` is the scope of the next word or phrase
+                    // if it is the name of a searchable field.
+                    let scope = match parsed.last() {
+                        Some(ParsedPart { part: PrimitiveQueryPart::Word(word, _), modifiers })
+                            if after_word && operator == ":" && modifiers.scope.is_none() =>
+                        {
+                            searchable_fields.iter().find(|field| field.to_lowercase() == *word)
+                        }
+                        _ => None,
+                    };
+                    if let Some(field) = scope {
+                        let ParsedPart { modifiers: previous, .. } = parsed.pop().unwrap();
+                        modifiers = Modifiers { scope: Some(field.clone()), ..previous };
+                    } else if !operator.is_empty() {
+                        let prefix = prefix_operator(operator, after_whitespace);
+                        modifiers = Modifiers {
+                            negative: prefix == Some('-'),
+                            required: prefix == Some('+'),
+                            scope: None,
+                        };
+                    }
+                }
                 // if there is a quote or a hard separator we close the phrase.
                 if !phrase.is_empty() && (quote_count > 0 || separator_kind == SeparatorKind::Hard)
                 {
-                    primitive_query.push(PrimitiveQueryPart::Phrase(mem::take(&mut phrase)));
+                    let part = PrimitiveQueryPart::Phrase(mem::take(&mut phrase));
+                    parsed.push(ParsedPart { part, modifiers: mem::take(&mut phrase_modifiers) });
+                }
+                // the operators preceding an opening quote apply to the whole phrase.
+                if quoted && quote_count % 2 != 0 {
+                    phrase_modifiers = mem::take(&mut modifiers);
                 }
+
+                after_whitespace = lemma.ends_with(char::is_whitespace);
+                after_word = false;
             }
             _ => (),
         }
     }
 
     // If a quote is never closed, we consider all of the end of the query as a phrase.
-    if !phrase.is_empty() {
-        primitive_query.push(PrimitiveQueryPart::Phrase(mem::take(&mut phrase)));
+    if !phrase.is_empty() && parsed.len() < parts_limit {
+        let part = PrimitiveQueryPart::Phrase(mem::take(&mut phrase));
+        parsed.push(ParsedPart { part, modifiers: phrase_modifiers });
     }
 
-    primitive_query
+    let mut primitive_query = Vec::new();
+    let mut operators = PrimitiveOperators::default();
+    for ParsedPart { part, modifiers: Modifiers { negative, required, scope } } in parsed {
+        if negative {
+            operators.negative.push((scope, part));
+        } else {
+            // a field-scoped part is already required in its field.
+            match scope {
+                Some(scope) => operators.scoped.push((scope, primitive_query.len())),
+                None if required => operators.required.push(primitive_query.len()),
+                None => (),
+            }
+            primitive_query.push(part);
+        }
+    }
+
+    (primitive_query, operators)
 }
 
 /// Returns the maximum number of typos that this Operation allows.
@@ -870,6 +1091,7 @@ pub fn maximum_proximity(operation: &Operation) -> usize {
 mod test {
     use std::collections::HashMap;
 
+    use big_s::S;
     use charabia::Tokenize;
     use maplit::hashmap;
     use rand::rngs::StdRng;
@@ -887,14 +1109,14 @@ mod test {
     }
 
     impl TestContext {
-        fn build<A: AsRef<[u8]>>(
+        fn build<'o>(
             &self,
             terms_matching_strategy: TermsMatchingStrategy,
             authorize_typos: bool,
             words_limit: Option<usize>,
-            query: NormalizedTokenIter<A>,
+            query: impl Iterator<Item = Token<'o>>,
         ) -> Result<Option<(Operation, PrimitiveQuery)>> {
-            let primitive_query = create_primitive_query(query, words_limit);
+            let (primitive_query, _) = create_primitive_query(query, words_limit, &[]);
             if !primitive_query.is_empty() {
                 let qt = create_query_tree(
                     self,
@@ -1228,6 +1450,91 @@ mod test {
         "###);
     }
 
+    #[test]
+    fn negation() {
+        use PrimitiveQueryPart::{Phrase, Word};
+
+        let query = "hello -world -\"good morning\" t-shirt";
+        let (primitive_query, operators) = create_primitive_query(query.tokenize(), None, &[]);
+
+        assert_eq!(
+            primitive_query,
+            vec![Word(S("hello"), false), Word(S("t"), false), Word(S("shirt"), true)]
+        );
+        assert_eq!(
+            operators.negative,
+            vec![
+                (None, Word(S("world"), false)),
+                (None, Phrase(vec![Some(S("good")), Some(S("morning"))])),
+            ]
+        );
+        assert!(operators.scoped.is_empty());
+    }
+
+    #[test]
+    fn negation_only_prefixes_words() {
+        use PrimitiveQueryPart::{Phrase, Word};
+
+        // a `-` inside of a phrase or that is not stuck to the next word is not a negation.
+        let query = "\"like -new\" phone - case";
+        let (primitive_query, operators) = create_primitive_query(query.tokenize(), None, &[]);
+
+        assert_eq!(
+            primitive_query,
+            vec![
+                Phrase(vec![Some(S("like")), Some(S("new"))]),
+                Word(S("phone"), false),
+                Word(S("case"), true),
+            ]
+        );
+        assert!(operators.negative.is_empty());
+    }
+
+    #[test]
+    fn requirement() {
+        use PrimitiveQueryPart::{Phrase, Word};
+
+        let fields = [S("title")];
+        let query = "+iphone case +\"like new\" +title:blue c++";
+        let (primitive_query, operators) = create_primitive_query(query.tokenize(), None, &fields);
+
+        assert_eq!(
+            primitive_query,
+            vec![
+                Word(S("iphone"), false),
+                Word(S("case"), false),
+                Phrase(vec![Some(S("like")), Some(S("new"))]),
+                Word(S("blue"), false),
+                Word(S("c"), false),
+            ]
+        );
+        assert_eq!(operators.required, vec![0, 2]);
+        assert_eq!(operators.scoped, vec![(S("title"), 3)]);
+        assert!(operators.negative.is_empty());
+    }
+
+    #[test]
+    fn field_scope() {
+        use PrimitiveQueryPart::{Phrase, Word};
+
+        let fields = [S("title"), S("Description")];
+        let query = "title:iphone description:\"blue case\" brand:apple -title:refurbished";
+        let (primitive_query, operators) = create_primitive_query(query.tokenize(), None, &fields);
+
+        // `brand` is not a searchable field, it is kept as a word of the query.
+        assert_eq!(
+            primitive_query,
+            vec![
+                Word(S("iphone"), false),
+                Phrase(vec![Some(S("blue")), Some(S("case"))]),
+                Word(S("brand"), false),
+                Word(S("apple"), false),
+            ]
+        );
+        assert_eq!(operators.scoped, vec![(S("title"), 0), (S("Description"), 1)]);
+        assert_eq!(operators.negative, vec![(Some(S("title")), Word(S("refurbished"), true))]);
+    }
+
     #[test]
     fn optional_word() {
         let query = "hey my friend ";
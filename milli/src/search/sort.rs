@@ -0,0 +1,111 @@
+use roaring::RoaringBitmap;
+
+use super::criteria::facet_ordered;
+use super::CriterionImplementationStrategy;
+use crate::error::UserError;
+use crate::{AscDesc, DocumentId, Index, Member, Result};
+
+/// Returns a page of the given candidates ordered by the sort criteria.
+///
+/// Contrary to a placeholder search, the sort ranking rule does not need to
+/// be part of the ranking rules of the index, only the sortable attributes are
+/// checked. The documents that do not have a value for a criterion are returned
+/// after the ones that have one. The `_geoPoint` sort rule isn't supported.
+pub fn sort_documents_ids(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    sort_criteria: &[AscDesc],
+    candidates: RoaringBitmap,
+    offset: usize,
+    limit: usize,
+) -> Result<Vec<DocumentId>> {
+    let sortable_fields = index.sortable_fields(rtxn)?;
+    let fields_ids_map = index.fields_ids_map(rtxn)?;
+
+    let mut fields = Vec::with_capacity(sort_criteria.len());
+    for asc_desc in sort_criteria {
+        match asc_desc.member() {
+            Member::Field(field) if crate::is_faceted(field, &sortable_fields) => {
+                fields.push((fields_ids_map.id(field), matches!(asc_desc, AscDesc::Asc(_))))
+            }
+            Member::Field(field) => {
+                return Err(UserError::InvalidSortableAttribute {
+                    field: field.to_string(),
+                    valid_fields: sortable_fields.into_iter().collect(),
+                })?
+            }
+            Member::Geo(_) => return Err(UserError::InvalidDocumentsGeoSort)?,
+        }
+    }
+
+    let mut sorter = BucketSorter { index, rtxn, offset, limit, output: Vec::new() };
+    sorter.sort(&fields, candidates)?;
+    Ok(sorter.output)
+}
+
+struct BucketSorter<'t> {
+    index: &'t Index,
+    rtxn: &'t heed::RoTxn<'t>,
+    /// The number of documents that must still be skipped.
+    offset: usize,
+    limit: usize,
+    output: Vec<DocumentId>,
+}
+
+impl BucketSorter<'_> {
+    fn is_full(&self) -> bool {
+        self.output.len() >= self.limit
+    }
+
+    /// Recursively splits the candidates into buckets of documents sharing the same
+    /// facet value for the first field and sorts these buckets with the next fields.
+    fn sort(&mut self, fields: &[(Option<u16>, bool)], candidates: RoaringBitmap) -> Result<()> {
+        if self.is_full() || candidates.is_empty() {
+            return Ok(());
+        }
+
+        // There is no need to sort a bucket that is going to be skipped entirely.
+        if candidates.len() as usize <= self.offset {
+            self.offset -= candidates.len() as usize;
+            return Ok(());
+        }
+
+        let ((field_id, is_ascending), next_fields) = match fields.split_first() {
+            Some((&(Some(field_id), is_ascending), next_fields)) => {
+                ((field_id, is_ascending), next_fields)
+            }
+            // The field was never seen in any document, no document can be sorted by it.
+            Some((&(None, _), next_fields)) => return self.sort(next_fields, candidates),
+            None => {
+                let remaining = self.limit - self.output.len();
+                self.output.extend(candidates.iter().skip(self.offset).take(remaining));
+                self.offset = 0;
+                return Ok(());
+            }
+        };
+
+        let mut remaining = candidates.clone();
+        let iter = facet_ordered(
+            self.index,
+            self.rtxn,
+            field_id,
+            is_ascending,
+            candidates,
+            CriterionImplementationStrategy::Dynamic,
+        )?;
+
+        for bucket in iter {
+            // A document with multiple values can be part of many buckets,
+            // we only keep it in the first one.
+            let bucket = bucket? & &remaining;
+            remaining -= &bucket;
+            self.sort(next_fields, bucket)?;
+            if self.is_full() {
+                return Ok(());
+            }
+        }
+
+        // The documents without any value for this field come last.
+        self.sort(next_fields, remaining)
+    }
+}
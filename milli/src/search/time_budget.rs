@@ -0,0 +1,78 @@
+use std::cell::Cell;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// The time a search is allowed to take.
+///
+/// Once it is exceeded the criteria stop refining their buckets
+/// and the search result is marked as degraded.
+pub struct TimeBudget {
+    started_at: Instant,
+    budget: Duration,
+    degraded: Cell<bool>,
+}
+
+impl fmt::Debug for TimeBudget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimeBudget")
+            .field("budget", &self.budget)
+            .field("left", &self.budget.saturating_sub(self.started_at.elapsed()))
+            .field("degraded", &self.degraded.get())
+            .finish()
+    }
+}
+
+impl Default for TimeBudget {
+    fn default() -> Self {
+        Self::max()
+    }
+}
+
+impl TimeBudget {
+    /// Starts a budget of the given duration, counted from now.
+    pub fn new(budget: Duration) -> Self {
+        Self { started_at: Instant::now(), budget, degraded: Cell::new(false) }
+    }
+
+    /// A budget that is never exceeded.
+    pub fn max() -> Self {
+        Self::new(Duration::MAX)
+    }
+
+    pub fn exceeded(&self) -> bool {
+        self.started_at.elapsed() > self.budget
+    }
+
+    /// Returns `true` if the budget is exceeded and marks the search as degraded,
+    /// the caller must then stop refining its buckets.
+    pub(crate) fn degrade_if_exceeded(&self) -> bool {
+        let exceeded = self.exceeded();
+        if exceeded {
+            self.degraded.set(true);
+        }
+        exceeded
+    }
+
+    /// Whether a criterion stopped refining its buckets because the budget was exceeded.
+    pub fn is_degraded(&self) -> bool {
+        self.degraded.get()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn degrade_only_when_exceeded() {
+        let budget = TimeBudget::max();
+        assert!(!budget.degrade_if_exceeded());
+        assert!(!budget.is_degraded());
+
+        let budget = TimeBudget::new(Duration::ZERO);
+        std::thread::sleep(Duration::from_millis(1));
+        assert!(!budget.is_degraded());
+        assert!(budget.degrade_if_exceeded());
+        assert!(budget.is_degraded());
+    }
+}
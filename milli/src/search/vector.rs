@@ -0,0 +1,185 @@
+use std::mem::take;
+
+use roaring::RoaringBitmap;
+
+use super::distinct::{Distinct, DocIter};
+use crate::error::UserError;
+use crate::vector::hnsw::{self, PointId};
+use crate::{DocumentId, Index, NDotProductPoint, Result, TimeBudget, BEU32};
+
+/// The documents the closest to a target vector, along with their similarity.
+#[derive(Debug, Default)]
+pub(crate) struct NearestDocuments {
+    /// The documents of the universe that have at least one vector.
+    pub candidates: RoaringBitmap,
+    pub documents: Vec<(DocumentId, f32)>,
+}
+
+/// Returns a page of the documents of the universe ordered by the similarity
+/// between their closest vector and the target vector.
+///
+/// The approximate neighbors given by the HNSW are used first, all the vectors
+/// are compared when they are not enough to fill the page, for example when the
+/// universe is restricted by a filter. The comparison of all the vectors is given
+/// up when the time budget is exceeded, the page is then only filled with the
+/// approximate neighbors.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn nearest_documents<D: Distinct>(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    target: &[f32],
+    universe: &RoaringBitmap,
+    distinct: D,
+    offset: usize,
+    limit: usize,
+    time_budget: &TimeBudget,
+) -> Result<NearestDocuments> {
+    let expected = match hnsw::dimensions(index, rtxn)? {
+        Some(expected) => expected,
+        None => return Ok(NearestDocuments::default()),
+    };
+
+    if expected != target.len() {
+        return Err(UserError::InvalidVectorDimensions { expected, found: target.len() }.into());
+    }
+
+    // the vectors of the deleted documents are no longer associated with a document.
+    let docid_of = |pid: PointId| -> Result<Option<DocumentId>> {
+        let docid = index.vector_id_docid.get(rtxn, &BEU32::new(pid))?;
+        Ok(docid.map(|docid| docid.get()))
+    };
+
+    let candidates = index.vector_documents_ids(rtxn)? & universe;
+
+    let target = NDotProductPoint::new(target.to_vec());
+    let wanted = offset.saturating_add(limit).min(candidates.len() as usize);
+
+    let mut selection = Selection::new(&candidates, distinct);
+    let mut neighbors = Vec::new();
+    for (pid, distance) in hnsw::search(index, rtxn, &target, wanted, time_budget)? {
+        if neighbors.len() >= wanted {
+            break;
+        }
+        if let Some(docid) = docid_of(pid)? {
+            if selection.keep(docid)? {
+                neighbors.push((docid, distance));
+            }
+        }
+    }
+
+    if neighbors.len() < wanted && !time_budget.degrade_if_exceeded() {
+        let mut all = Vec::new();
+        let mut exceeded = false;
+        for result in index.vector_hnsw.iter(rtxn)? {
+            if time_budget.degrade_if_exceeded() {
+                exceeded = true;
+                break;
+            }
+            let (pid, node) = result?;
+            if let Some(docid) = docid_of(pid.get())? {
+                if candidates.contains(docid) {
+                    all.push((docid, target.distance(&node.point)));
+                }
+            }
+        }
+
+        if !exceeded {
+            all.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+            selection.reset();
+            neighbors.clear();
+            for (docid, distance) in all {
+                if neighbors.len() >= wanted {
+                    break;
+                }
+                if selection.keep(docid)? {
+                    neighbors.push((docid, distance));
+                }
+            }
+        }
+    }
+
+    let documents = neighbors
+        .into_iter()
+        .skip(offset)
+        .map(|(docid, distance)| (docid, 1.0 - distance))
+        .collect();
+
+    Ok(NearestDocuments { candidates, documents })
+}
+
+/// Returns the candidates that have at least one vector whose similarity
+/// with the target vector is accepted by the predicate.
+///
+/// All the vectors of the candidates are compared with the target vector. When the
+/// time budget is exceeded the comparison is given up, the candidates that were not
+/// compared yet are then all returned. The search is not marked as degraded since
+/// only the number of candidates is estimated, the page of documents is final.
+pub(crate) fn documents_with_similarity(
+    index: &Index,
+    rtxn: &heed::RoTxn,
+    target: &[f32],
+    candidates: &RoaringBitmap,
+    predicate: impl Fn(f32) -> bool,
+    time_budget: &TimeBudget,
+) -> Result<RoaringBitmap> {
+    let target = NDotProductPoint::new(target.to_vec());
+    let mut documents = RoaringBitmap::new();
+    let mut compared = RoaringBitmap::new();
+    for result in index.vector_id_docid.iter(rtxn)? {
+        if time_budget.exceeded() {
+            return Ok(documents | (candidates - compared));
+        }
+        let (pid, docid) = result?;
+        let docid = docid.get();
+        if !candidates.contains(docid) || documents.contains(docid) {
+            continue;
+        }
+        compared.insert(docid);
+        if let Some(node) = index.vector_hnsw.get(rtxn, &pid)? {
+            if predicate(1.0 - target.distance(&node.point)) {
+                documents.insert(docid);
+            }
+        }
+    }
+    Ok(documents)
+}
+
+/// Selects the documents in the order they are given, only keeping the candidates
+/// that were not already given and the first document of each distinct value.
+struct Selection<'a, D> {
+    candidates: &'a RoaringBitmap,
+    distinct: D,
+    seen: RoaringBitmap,
+    excluded: RoaringBitmap,
+}
+
+impl<'a, D: Distinct> Selection<'a, D> {
+    fn new(candidates: &'a RoaringBitmap, distinct: D) -> Self {
+        Selection {
+            candidates,
+            distinct,
+            seen: RoaringBitmap::new(),
+            excluded: RoaringBitmap::new(),
+        }
+    }
+
+    /// Whether the document must be kept, a document can have many vectors
+    /// and is ranked by its closest one.
+    fn keep(&mut self, docid: DocumentId) -> Result<bool> {
+        if !self.candidates.contains(docid) || !self.seen.insert(docid) {
+            return Ok(false);
+        }
+
+        let single = RoaringBitmap::from_iter([docid]);
+        let mut iter = self.distinct.distinct(single, take(&mut self.excluded));
+        let kept = iter.next().transpose()?.is_some();
+        self.excluded = iter.into_excluded();
+        Ok(kept)
+    }
+
+    fn reset(&mut self) {
+        self.seen.clear();
+        self.excluded.clear();
+    }
+}
@@ -0,0 +1,368 @@
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
+use charabia::{SeparatorKind, Token, TokenKind, Tokenizer};
+use heed::RoTxn;
+
+use crate::{Index, Result};
+
+/// The tokenization settings of an index.
+///
+/// The documents, the queries and the settings that are normalized must all be tokenized
+/// with the same settings. The tokenizer does not know about them, the tokens it gives are
+/// reworked by [`TokenizerSettings::tokenize`].
+///
+/// The separator tokens and the dictionary words are searched in the text with a single
+/// automaton and the non-separator tokens with another one, both built once for all the
+/// texts. They give the longest token found at the leftmost position.
+#[derive(Debug, Default, Clone)]
+pub struct TokenizerSettings {
+    specials: Option<Specials>,
+    non_separators: Option<AhoCorasick>,
+}
+
+/// The separator tokens and the dictionary words, searched in the lowercased text.
+#[derive(Debug, Clone)]
+struct Specials {
+    /// The dictionary words come first, a dictionary word is then
+    /// preferred over a separator token of the same length.
+    automaton: AhoCorasick,
+    dictionary_len: usize,
+    /// The separator tokens are case sensitive, unlike the dictionary words.
+    separators: BTreeSet<String>,
+}
+
+/// A part of the text that is not given to the tokenizer.
+enum Special {
+    Separator,
+    DictionaryWord,
+}
+
+impl TokenizerSettings {
+    /// Loads the tokenization settings of the index.
+    pub fn from_index(index: &Index, rtxn: &RoTxn) -> Result<Self> {
+        Ok(TokenizerSettings::new(
+            index.separator_tokens(rtxn)?,
+            index.non_separator_tokens(rtxn)?,
+            index.dictionary(rtxn)?,
+        ))
+    }
+
+    fn new(
+        separators: Option<BTreeSet<String>>,
+        non_separators: Option<BTreeSet<String>>,
+        dictionary: Option<BTreeSet<String>>,
+    ) -> Self {
+        let dictionary: Vec<_> = non_empty(dictionary).map(|word| word.to_lowercase()).collect();
+        let separators: BTreeSet<_> = non_empty(separators).collect();
+        let specials = if dictionary.is_empty() && separators.is_empty() {
+            None
+        } else {
+            let lowercased_separators = separators.iter().map(|separator| separator.to_lowercase());
+            let automaton =
+                leftmost_longest(dictionary.iter().cloned().chain(lowercased_separators));
+            Some(Specials { automaton, dictionary_len: dictionary.len(), separators })
+        };
+
+        let non_separators: Vec<_> = non_empty(non_separators).collect();
+        let non_separators = (!non_separators.is_empty()).then(|| leftmost_longest(non_separators));
+
+        TokenizerSettings { specials, non_separators }
+    }
+
+    /// Tokenizes the text the way the index does.
+    ///
+    /// The text is first split at the separator tokens and around the words of the dictionary,
+    /// which are kept as a single word, the rest of the text is tokenized by the tokenizer.
+    /// The non-separator tokens are then merged with the words they are stuck to.
+    pub fn tokenize<'o, A: AsRef<[u8]>>(
+        &self,
+        tokenizer: &Tokenizer<A>,
+        text: &'o str,
+    ) -> Vec<Token<'o>> {
+        let mut tokens = Vec::new();
+        // the start of the text that is not tokenized yet, in bytes and in chars.
+        let mut start = 0;
+        let mut char_start = 0;
+        for (range, special) in self.specials(text) {
+            tokens.extend(self.tokenize_plain(tokenizer, text, start..range.start, char_start));
+            char_start += text[start..range.start].chars().count();
+
+            let char_end = char_start + text[range.clone()].chars().count();
+            let token = match special {
+                Special::Separator => Token {
+                    kind: TokenKind::Separator(SeparatorKind::Soft),
+                    lemma: Cow::Borrowed(&text[range.clone()]),
+                    char_start,
+                    char_end,
+                    byte_start: range.start,
+                    byte_end: range.end,
+                    ..Default::default()
+                },
+                Special::DictionaryWord => {
+                    let mut token = tokenizer
+                        .tokenize(&text[range.clone()])
+                        .map(|token| shift(token, range.start, char_start))
+                        .reduce(|merged, token| merge(text, merged, token))
+                        .unwrap_or_default();
+                    token.kind = TokenKind::Word;
+                    token.char_start = char_start;
+                    token.char_end = char_end;
+                    token.byte_start = range.start;
+                    token.byte_end = range.end;
+                    token
+                }
+            };
+            tokens.push(token);
+
+            start = range.end;
+            char_start = char_end;
+        }
+        tokens.extend(self.tokenize_plain(tokenizer, text, start..text.len(), char_start));
+
+        tokens
+    }
+
+    /// Returns the ranges and the kinds of the separator tokens
+    /// and the dictionary words found in the text, in order.
+    fn specials(&self, text: &str) -> Vec<(Range<usize>, Special)> {
+        let specials = match &self.specials {
+            Some(specials) => specials,
+            None => return Vec::new(),
+        };
+
+        let (lowercased, origins) = lowercase(text);
+        let origin = |position: usize| match &origins {
+            Some(origins) => origins[position],
+            None => Some(position),
+        };
+
+        specials
+            .automaton
+            .find_iter(&lowercased)
+            .filter_map(|found| {
+                // the matches starting or ending inside the lowercase version of a char are ignored.
+                let range = origin(found.start())?..origin(found.end())?;
+                let matched = &text[range.clone()];
+                if found.pattern() < specials.dictionary_len {
+                    // a dictionary word must not be a part of a larger word.
+                    let before = text[..range.start].chars().next_back();
+                    let after = text[range.end..].chars().next();
+                    let glued = (starts_with_word_char(matched)
+                        && before.map_or(false, is_word_char))
+                        || (ends_with_word_char(matched) && after.map_or(false, is_word_char));
+                    (!glued).then_some((range, Special::DictionaryWord))
+                } else {
+                    specials.separators.contains(matched).then_some((range, Special::Separator))
+                }
+            })
+            .collect()
+    }
+
+    /// Tokenizes the range of the text with the tokenizer and merges
+    /// the non-separator tokens with the words they are stuck to.
+    fn tokenize_plain<'o, A: AsRef<[u8]>>(
+        &self,
+        tokenizer: &Tokenizer<A>,
+        text: &'o str,
+        range: Range<usize>,
+        char_start: usize,
+    ) -> Vec<Token<'o>> {
+        if range.is_empty() {
+            return Vec::new();
+        }
+
+        let tokens = tokenizer
+            .tokenize(&text[range.clone()])
+            .map(|token| shift(token, range.start, char_start));
+        let non_separators = match &self.non_separators {
+            Some(non_separators) => non_separators,
+            None => return tokens.collect(),
+        };
+
+        let glued_ranges: Vec<_> = non_separators
+            .find_iter(&text[range.clone()])
+            .map(|found| range.start + found.start()..range.start + found.end())
+            .collect();
+        let is_glued = |token: &Token| {
+            token.is_separator()
+                && glued_ranges
+                    .iter()
+                    .any(|glued| glued.start <= token.byte_start && token.byte_end <= glued.end)
+        };
+
+        let mut merged: Vec<Token> = Vec::new();
+        // whether the last merged token ends with a non-separator token.
+        let mut ends_glued = false;
+        for token in tokens {
+            let glued = is_glued(&token);
+            let joined = match merged.last() {
+                Some(last) if last.byte_end == token.byte_start => {
+                    (glued && is_word(last)) || (ends_glued && is_word(&token))
+                }
+                _ => false,
+            };
+
+            if joined {
+                let last = merged.pop().unwrap();
+                merged.push(merge(text, last, token));
+            } else if glued {
+                // a non-separator token on its own is a word.
+                merged.push(Token { kind: TokenKind::Word, ..token });
+            } else {
+                merged.push(token);
+            }
+            ends_glued = glued;
+        }
+
+        merged
+    }
+}
+
+/// The tokens of a setting, an empty token never matches.
+fn non_empty(tokens: Option<BTreeSet<String>>) -> impl Iterator<Item = String> {
+    tokens.into_iter().flatten().filter(|token| !token.is_empty())
+}
+
+/// Builds an automaton finding the longest of the patterns at the leftmost position.
+fn leftmost_longest(patterns: impl IntoIterator<Item = String>) -> AhoCorasick {
+    AhoCorasickBuilder::new().match_kind(MatchKind::LeftmostLongest).build(patterns)
+}
+
+/// Lowercases the text. When a char and its lowercase version don't have the same length,
+/// the position in the text of each byte of the lowercased text is also returned,
+/// the bytes that don't start the lowercase version of a char have none.
+fn lowercase(text: &str) -> (String, Option<Vec<Option<usize>>>) {
+    if text.is_ascii() {
+        return (text.to_ascii_lowercase(), None);
+    }
+
+    let mut lowercased = String::with_capacity(text.len());
+    let mut origins = Vec::with_capacity(text.len() + 1);
+    for (position, c) in text.char_indices() {
+        origins.push(Some(position));
+        lowercased.extend(c.to_lowercase());
+        origins.resize(lowercased.len(), None);
+    }
+    origins.push(Some(text.len()));
+    (lowercased, Some(origins))
+}
+
+/// Moves a token given by the tokenizer for a part of the text to its place in the whole text.
+fn shift(mut token: Token, byte_offset: usize, char_offset: usize) -> Token {
+    token.byte_start += byte_offset;
+    token.byte_end += byte_offset;
+    token.char_start += char_offset;
+    token.char_end += char_offset;
+    token
+}
+
+/// Merges two consecutive tokens of the text into a single word.
+fn merge<'o>(text: &'o str, left: Token<'o>, right: Token<'o>) -> Token<'o> {
+    let char_map = match (&left.char_map, &right.char_map) {
+        (None, None) => None,
+        _ => {
+            let mut char_map = char_map_or_identity(text, &left);
+            char_map.extend(char_map_or_identity(text, &right));
+            Some(char_map)
+        }
+    };
+
+    Token {
+        kind: TokenKind::Word,
+        lemma: Cow::Owned(format!("{}{}", left.lemma, right.lemma)),
+        char_end: right.char_end,
+        byte_end: right.byte_end,
+        char_map,
+        ..left
+    }
+}
+
+/// The char map of a token that was not normalized maps every char to itself.
+fn char_map_or_identity(text: &str, token: &Token) -> Vec<(u8, u8)> {
+    match &token.char_map {
+        Some(char_map) => char_map.clone(),
+        None => text[token.byte_start..token.byte_end]
+            .chars()
+            .map(|c| (c.len_utf8() as u8, c.len_utf8() as u8))
+            .collect(),
+    }
+}
+
+fn is_word(token: &Token) -> bool {
+    matches!(token.kind, TokenKind::Word | TokenKind::StopWord)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric()
+}
+
+fn starts_with_word_char(word: &str) -> bool {
+    word.chars().next().map_or(false, is_word_char)
+}
+
+fn ends_with_word_char(word: &str) -> bool {
+    word.chars().next_back().map_or(false, is_word_char)
+}
+
+#[cfg(test)]
+mod tests {
+    use big_s::S;
+    use charabia::TokenizerBuilder;
+    use maplit::btreeset;
+
+    use super::*;
+
+    fn words(settings: &TokenizerSettings, text: &str) -> Vec<String> {
+        let tokenizer = TokenizerBuilder::default().build();
+        settings
+            .tokenize(&tokenizer, text)
+            .into_iter()
+            .filter(is_word)
+            .map(|token| token.lemma().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn default_settings() {
+        let settings = TokenizerSettings::default();
+        assert_eq!(words(&settings, "I love C#"), vec!["i", "love", "c"]);
+    }
+
+    #[test]
+    fn separators_and_non_separators() {
+        let settings =
+            TokenizerSettings::new(Some(btreeset! { S("@@") }), Some(btreeset! { S("#") }), None);
+        assert_eq!(words(&settings, "C# or F#@@rust"), vec!["c#", "or", "f#", "rust"]);
+        assert_eq!(words(&settings, "a # b"), vec!["a", "#", "b"]);
+    }
+
+    #[test]
+    fn dictionary() {
+        let settings =
+            TokenizerSettings::new(None, None, Some(btreeset! { S("J. R. R. Tolkien"), S("ear") }));
+        let text = "by j. R. R. Tolkien, near";
+        assert_eq!(words(&settings, text), vec!["by", "j. r. r. tolkien", "near"]);
+
+        let tokenizer = TokenizerBuilder::default().build();
+        let tokens = settings.tokenize(&tokenizer, text);
+        let word = tokens.iter().find(|token| token.lemma() == "j. r. r. tolkien").unwrap();
+        assert_eq!(&text[word.byte_start..word.byte_end], "j. R. R. Tolkien");
+        assert_eq!((word.char_start, word.char_end), (3, 19));
+
+        // the lowercase version of `İ` is longer than it.
+        let text = "İ read J. R. R. Tolkien";
+        let tokens = settings.tokenize(&tokenizer, text);
+        let word = tokens.iter().find(|token| token.lemma() == "j. r. r. tolkien").unwrap();
+        assert_eq!(&text[word.byte_start..word.byte_end], "J. R. R. Tolkien");
+        assert_eq!((word.char_start, word.char_end), (7, 23));
+    }
+
+    #[test]
+    fn case_sensitive_separators() {
+        let settings = TokenizerSettings::new(Some(btreeset! { S("X") }), None, None);
+        assert_eq!(words(&settings, "aXb axb"), vec!["a", "b", "axb"]);
+    }
+}
@@ -35,6 +35,8 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
             facet_id_exists_docids,
             field_id_docid_facet_f64s,
             field_id_docid_facet_strings,
+            vector_id_docid,
+            vector_hnsw,
             documents,
         } = self.index;
 
@@ -52,7 +54,10 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
         self.index.put_soft_deleted_documents_ids(self.wtxn, &empty_roaring)?;
         self.index.put_field_distribution(self.wtxn, &FieldDistribution::default())?;
         self.index.delete_geo_rtree(self.wtxn)?;
+        self.index.delete_geo_shapes_rtree(self.wtxn)?;
         self.index.delete_geo_faceted_documents_ids(self.wtxn)?;
+        self.index.delete_vector_hnsw_entry_point(self.wtxn)?;
+        self.index.delete_vector_documents_ids(self.wtxn)?;
 
         // We clean all the faceted documents ids.
         for field_id in faceted_fields {
@@ -87,6 +92,8 @@ impl<'t, 'u, 'i> ClearDocuments<'t, 'u, 'i> {
         facet_id_string_docids.clear(self.wtxn)?;
         field_id_docid_facet_f64s.clear(self.wtxn)?;
         field_id_docid_facet_strings.clear(self.wtxn)?;
+        vector_id_docid.clear(self.wtxn)?;
+        vector_hnsw.clear(self.wtxn)?;
         documents.clear(self.wtxn)?;
 
         Ok(number_of_documents)
@@ -107,7 +114,7 @@ mod tests {
             .add_documents_using_wtxn(&mut wtxn, documents!([
                 { "id": 0, "name": "kevin", "age": 20 },
                 { "id": 1, "name": "kevina" },
-                { "id": 2, "name": "benoit", "country": "France", "_geo": { "lng": 42, "lat": 35 } }
+                { "id": 2, "name": "benoit", "country": "France", "_geo": { "lng": 42, "lat": 35 }, "_vectors": [0.5, 0.2] }
             ]))
             .unwrap();
 
@@ -118,8 +125,8 @@ mod tests {
 
         let rtxn = index.read_txn().unwrap();
 
-        // the value is 7 because there is `[id, name, age, country, _geo, _geo.lng, _geo.lat]`
-        assert_eq!(index.fields_ids_map(&rtxn).unwrap().len(), 7);
+        // the value is 8 because there is `[id, name, age, country, _geo, _vectors, _geo.lng, _geo.lat]`
+        assert_eq!(index.fields_ids_map(&rtxn).unwrap().len(), 8);
 
         assert!(index.words_fst(&rtxn).unwrap().is_empty());
         assert!(index.words_prefixes_fst(&rtxn).unwrap().is_empty());
@@ -127,7 +134,10 @@ mod tests {
         assert!(index.documents_ids(&rtxn).unwrap().is_empty());
         assert!(index.field_distribution(&rtxn).unwrap().is_empty());
         assert!(index.geo_rtree(&rtxn).unwrap().is_none());
+        assert!(index.geo_shapes_rtree(&rtxn).unwrap().is_none());
         assert!(index.geo_faceted_documents_ids(&rtxn).unwrap().is_empty());
+        assert!(index.vector_hnsw_entry_point(&rtxn).unwrap().is_none());
+        assert!(index.vector_documents_ids(&rtxn).unwrap().is_empty());
 
         assert!(index.word_docids.is_empty(&rtxn).unwrap());
         assert!(index.word_prefix_docids.is_empty(&rtxn).unwrap());
@@ -139,6 +149,8 @@ mod tests {
         assert!(index.facet_id_string_docids.is_empty(&rtxn).unwrap());
         assert!(index.field_id_docid_facet_f64s.is_empty(&rtxn).unwrap());
         assert!(index.field_id_docid_facet_strings.is_empty(&rtxn).unwrap());
+        assert!(index.vector_id_docid.is_empty(&rtxn).unwrap());
+        assert!(index.vector_hnsw.is_empty(&rtxn).unwrap());
         assert!(index.documents.is_empty(&rtxn).unwrap());
     }
 }
@@ -5,6 +5,7 @@ use fst::IntoStreamer;
 use heed::types::{ByteSlice, DecodeIgnore, Str};
 use heed::Database;
 use roaring::RoaringBitmap;
+use rstar::RTree;
 use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
@@ -14,6 +15,7 @@ use crate::error::InternalError;
 use crate::facet::FacetType;
 use crate::heed_codec::facet::FieldDocIdFacetCodec;
 use crate::heed_codec::CboRoaringBitmapCodec;
+use crate::vector::hnsw;
 use crate::{
     ExternalDocumentsIds, FieldId, FieldIdMapMissingEntry, Index, Result, RoaringBitmapCodec,
     SmallString32, BEU32,
@@ -244,6 +246,8 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             field_id_docid_facet_f64s: _,
             field_id_docid_facet_strings: _,
             facet_id_exists_docids,
+            vector_id_docid,
+            vector_hnsw: _,
             documents,
         } = self.index;
 
@@ -470,6 +474,38 @@ impl<'t, 'u, 'i> DeleteDocuments<'t, 'u, 'i> {
             self.index.put_geo_faceted_documents_ids(self.wtxn, &geo_faceted_doc_ids)?;
         }
 
+        if let Some(rtree) = self.index.geo_shapes_rtree(self.wtxn)? {
+            let shapes: Vec<_> = rtree
+                .iter()
+                .filter(|shape| !self.to_delete_docids.contains(shape.data.0))
+                .cloned()
+                .collect();
+
+            if shapes.len() != rtree.size() {
+                self.index.put_geo_shapes_rtree(self.wtxn, &RTree::bulk_load(shapes))?;
+            }
+        }
+
+        // We remove the vectors of the deleted documents from the HNSW.
+        let mut vector_documents_ids = self.index.vector_documents_ids(self.wtxn)?;
+        if !vector_documents_ids.is_disjoint(&self.to_delete_docids) {
+            let mut removed_points = RoaringBitmap::new();
+            let mut iter = vector_id_docid.iter_mut(self.wtxn)?;
+            while let Some(result) = iter.next() {
+                let (pid, docid) = result?;
+                if self.to_delete_docids.contains(docid.get()) {
+                    removed_points.insert(pid.get());
+                    // safety: we don't keep references from inside the LMDB database.
+                    unsafe { iter.del_current()? };
+                }
+            }
+            drop(iter);
+            hnsw::remove(self.index, self.wtxn, &removed_points)?;
+
+            vector_documents_ids -= &self.to_delete_docids;
+            self.index.put_vector_documents_ids(self.wtxn, &vector_documents_ids)?;
+        }
+
         for facet_type in [FacetType::Number, FacetType::String] {
             let mut affected_facet_values = HashMap::new();
             for field_id in self.index.faceted_fields_ids(self.wtxn)? {
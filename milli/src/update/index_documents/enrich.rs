@@ -7,8 +7,9 @@ use serde_json::Value;
 
 use crate::documents::{DocumentsBatchIndex, DocumentsBatchReader, EnrichedDocumentsBatchReader};
 use crate::error::{GeoError, InternalError, UserError};
+use crate::geo_shape::is_geojson;
 use crate::update::index_documents::{obkv_to_object, writer_into_reader};
-use crate::{FieldId, Index, Object, Result};
+use crate::{FieldId, Geometry, Index, Object, Result};
 
 /// The symbol used to define levels in a nested primary key.
 const PRIMARY_KEY_SPLIT_SYMBOL: char = '.';
@@ -16,6 +17,9 @@ const PRIMARY_KEY_SPLIT_SYMBOL: char = '.';
 /// The default primary that is used when not specified.
 const DEFAULT_PRIMARY_KEY: &str = "id";
 
+/// The maximum number of points of a document, the index of a point is stored on a `u16`.
+pub const MAX_GEO_POINTS_PER_DOCUMENT: usize = u16::MAX as usize + 1;
+
 /// This function validates and enrich the documents by checking that:
 ///  - we can infer a primary key,
 ///  - all the documents id exist and are extracted,
@@ -371,30 +375,46 @@ pub fn extract_finite_float_from_value(value: Value) -> StdResult<f64, Value> {
 }
 
 pub fn validate_geo_from_json(id: &DocumentId, bytes: &[u8]) -> Result<StdResult<(), GeoError>> {
-    use GeoError::*;
     let debug_id = || {
         serde_json::from_slice(id.value().as_bytes()).unwrap_or_else(|_| Value::from(id.debug()))
     };
     match serde_json::from_slice(bytes).map_err(InternalError::SerdeJson)? {
+        value if is_geojson(&value) => match Geometry::from_geojson(value) {
+            Ok(_) => Ok(Ok(())),
+            Err(error) => Ok(Err(GeoError::BadGeoJson { document_id: debug_id(), error })),
+        },
+        // a document can be associated with several points
+        Value::Array(points) if points.len() > MAX_GEO_POINTS_PER_DOCUMENT => {
+            Ok(Err(GeoError::TooManyPoints { document_id: debug_id(), count: points.len() }))
+        }
+        Value::Array(points) => {
+            Ok(points.into_iter().try_for_each(|point| validate_geo_point(point, &debug_id)))
+        }
+        value => Ok(validate_geo_point(value, &debug_id)),
+    }
+}
+
+fn validate_geo_point(value: Value, debug_id: &impl Fn() -> Value) -> StdResult<(), GeoError> {
+    use GeoError::*;
+    match value {
         Value::Object(mut object) => match (object.remove("lat"), object.remove("lng")) {
             (Some(lat), Some(lng)) => {
                 match (extract_finite_float_from_value(lat), extract_finite_float_from_value(lng)) {
-                    (Ok(_), Ok(_)) if !object.is_empty() => Ok(Err(UnexpectedExtraFields {
-                        document_id: debug_id(),
-                        value: object.into(),
-                    })),
-                    (Ok(_), Ok(_)) => Ok(Ok(())),
-                    (Err(value), Ok(_)) => Ok(Err(BadLatitude { document_id: debug_id(), value })),
-                    (Ok(_), Err(value)) => Ok(Err(BadLongitude { document_id: debug_id(), value })),
+                    (Ok(_), Ok(_)) if !object.is_empty() => {
+                        Err(UnexpectedExtraFields { document_id: debug_id(), value: object.into() })
+                    }
+                    (Ok(_), Ok(_)) => Ok(()),
+                    (Err(value), Ok(_)) => Err(BadLatitude { document_id: debug_id(), value }),
+                    (Ok(_), Err(value)) => Err(BadLongitude { document_id: debug_id(), value }),
                     (Err(lat), Err(lng)) => {
-                        Ok(Err(BadLatitudeAndLongitude { document_id: debug_id(), lat, lng }))
+                        Err(BadLatitudeAndLongitude { document_id: debug_id(), lat, lng })
                     }
                 }
             }
-            (None, Some(_)) => Ok(Err(MissingLatitude { document_id: debug_id() })),
-            (Some(_), None) => Ok(Err(MissingLongitude { document_id: debug_id() })),
-            (None, None) => Ok(Err(MissingLatitudeAndLongitude { document_id: debug_id() })),
+            (None, Some(_)) => Err(MissingLatitude { document_id: debug_id() }),
+            (Some(_), None) => Err(MissingLongitude { document_id: debug_id() }),
+            (None, None) => Err(MissingLatitudeAndLongitude { document_id: debug_id() }),
         },
-        value => Ok(Err(NotAnObject { document_id: debug_id(), value })),
+        value => Err(NotAnObject { document_id: debug_id(), value }),
     }
 }
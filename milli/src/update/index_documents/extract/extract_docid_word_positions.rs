@@ -1,16 +1,17 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::fs::File;
 use std::{io, mem, str};
 
-use charabia::{SeparatorKind, Token, TokenKind, TokenizerBuilder};
+use charabia::{Language, Script, SeparatorKind, Token, TokenKind, Tokenizer, TokenizerBuilder};
 use roaring::RoaringBitmap;
 use serde_json::Value;
 
 use super::helpers::{concat_u32s_array, create_sorter, sorter_into_reader, GrenadParameters};
 use crate::error::{InternalError, SerializationError};
 use crate::{
-    absolute_from_relative_position, FieldId, Result, MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH,
+    absolute_from_relative_position, FieldId, Result, TokenizerSettings,
+    MAX_POSITION_PER_ATTRIBUTE, MAX_WORD_LENGTH,
 };
 
 /// Extracts the word and positions where this word appear and
@@ -24,6 +25,8 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     indexer: GrenadParameters,
     searchable_fields: &Option<HashSet<FieldId>>,
     stop_words: Option<&fst::Set<&[u8]>>,
+    tokenizer_settings: &TokenizerSettings,
+    localized_fields: &HashMap<FieldId, HashMap<Script, Vec<Language>>>,
     max_positions_per_attributes: Option<u32>,
 ) -> Result<(RoaringBitmap, grenad::Reader<File>)> {
     let max_positions_per_attributes = max_positions_per_attributes
@@ -42,11 +45,14 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
 
     let mut key_buffer = Vec::new();
     let mut field_buffer = String::new();
-    let mut builder = TokenizerBuilder::new();
-    if let Some(stop_words) = stop_words {
-        builder.stop_words(stop_words);
-    }
-    let tokenizer = builder.build();
+    let tokenizer = build_tokenizer(stop_words, None);
+
+    // the fields matching a localized attributes rule get their own tokenizer
+    // restricted to the languages of the rule.
+    let localized_tokenizers: HashMap<_, _> = localized_fields
+        .iter()
+        .map(|(field_id, allow_list)| (*field_id, build_tokenizer(stop_words, Some(allow_list))))
+        .collect();
 
     let mut cursor = obkv_documents.into_cursor()?;
     while let Some((key, value)) = cursor.move_on_next()? {
@@ -66,7 +72,9 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
                     serde_json::from_slice(field_bytes).map_err(InternalError::SerdeJson)?;
                 field_buffer.clear();
                 if let Some(field) = json_to_string(&value, &mut field_buffer) {
-                    let tokens = process_tokens(tokenizer.tokenize(field))
+                    let tokenizer = localized_tokenizers.get(&field_id).unwrap_or(&tokenizer);
+                    let tokens = tokenizer_settings.tokenize(tokenizer, field);
+                    let tokens = process_tokens(tokens.into_iter())
                         .take_while(|(p, _)| (*p as u32) < max_positions_per_attributes);
 
                     for (index, token) in tokens {
@@ -91,6 +99,23 @@ pub fn extract_docid_word_positions<R: io::Read + io::Seek>(
     sorter_into_reader(docid_word_positions_sorter, indexer).map(|reader| (documents_ids, reader))
 }
 
+/// Creates a tokenizer that ignores the stop words and only detects the allowed languages.
+///
+/// The tokenization settings of the index are applied on top of the tokens it gives.
+fn build_tokenizer<'a>(
+    stop_words: Option<&'a fst::Set<&'a [u8]>>,
+    allow_list: Option<&'a HashMap<Script, Vec<Language>>>,
+) -> Tokenizer<'a, 'a, &'a [u8]> {
+    let mut builder = TokenizerBuilder::new();
+    if let Some(stop_words) = stop_words {
+        builder.stop_words(stop_words);
+    }
+    if let Some(allow_list) = allow_list {
+        builder.allow_list(allow_list);
+    }
+    builder.build()
+}
+
 /// Transform a JSON value into a string that can be indexed.
 fn json_to_string<'a>(value: &'a Value, buffer: &'a mut String) -> Option<&'a str> {
     fn inner(value: &Value, output: &mut String) -> bool {
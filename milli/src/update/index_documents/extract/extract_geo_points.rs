@@ -6,12 +6,15 @@ use serde_json::Value;
 
 use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
 use crate::error::GeoError;
-use crate::update::index_documents::extract_finite_float_from_value;
+use crate::update::index_documents::{
+    extract_finite_float_from_value, MAX_GEO_POINTS_PER_DOCUMENT,
+};
 use crate::{FieldId, InternalError, Result};
 
 /// Extracts the geographical coordinates contained in each document under the `_geo` field.
 ///
-/// Returns the generated grenad reader containing the docid as key associated to the (latitude, longitude)
+/// Returns the generated grenad reader containing the docid and the index of the point
+/// in the document as key associated to the (latitude, longitude)
 #[logging_timer::time]
 pub fn extract_geo_points<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
@@ -40,20 +43,38 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
         let lng = obkv.get(lng_fid);
 
         if let Some((lat, lng)) = lat.zip(lng) {
-            // then we extract the values
-            let lat = extract_finite_float_from_value(
-                serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lat| GeoError::BadLatitude { document_id: document_id(), value: lat })?;
+            let lat = serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?;
+            let lng = serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?;
 
-            let lng = extract_finite_float_from_value(
-                serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lng| GeoError::BadLongitude { document_id: document_id(), value: lng })?;
+            // when a document contains several points their latitudes and
+            // longitudes are flattened into two arrays of the same length.
+            let points = match (lat, lng) {
+                (Value::Array(lats), Value::Array(lngs)) => lats.into_iter().zip(lngs).collect(),
+                (lat, lng) => vec![(lat, lng)],
+            };
 
-            #[allow(clippy::drop_non_drop)]
-            let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
-            writer.insert(docid_bytes, bytes)?;
+            if points.len() > MAX_GEO_POINTS_PER_DOCUMENT {
+                let count = points.len();
+                return Err(GeoError::TooManyPoints { document_id: document_id(), count })?;
+            }
+
+            for (i, (lat, lng)) in points.into_iter().enumerate() {
+                // then we extract the values
+                let lat = extract_finite_float_from_value(lat).map_err(|lat| {
+                    GeoError::BadLatitude { document_id: document_id(), value: lat }
+                })?;
+
+                let lng = extract_finite_float_from_value(lng).map_err(|lng| {
+                    GeoError::BadLongitude { document_id: document_id(), value: lng }
+                })?;
+
+                let index = u16::try_from(i).unwrap();
+                let mut key = docid_bytes.to_vec();
+                key.extend_from_slice(&index.to_be_bytes());
+                #[allow(clippy::drop_non_drop)]
+                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
+                writer.insert(key, bytes)?;
+            }
         } else if lat.is_none() && lng.is_some() {
             return Err(GeoError::MissingLatitude { document_id: document_id() })?;
         } else if lat.is_some() && lng.is_none() {
@@ -0,0 +1,50 @@
+use std::fs::File;
+use std::io;
+
+use serde_json::Value;
+
+use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
+use crate::error::GeoError;
+use crate::geo_shape::is_geojson;
+use crate::{FieldId, Geometry, InternalError, Result};
+
+/// Extracts the GeoJSON geometries contained in each document under the `_geo` field.
+///
+/// Returns the generated grenad reader containing the docid as key associated to the geometry
+#[logging_timer::time]
+pub fn extract_geo_shapes<R: io::Read + io::Seek>(
+    obkv_documents: grenad::Reader<R>,
+    indexer: GrenadParameters,
+    primary_key_id: FieldId,
+    geo_fid: FieldId,
+) -> Result<grenad::Reader<File>> {
+    let mut writer = create_writer(
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+
+    let mut cursor = obkv_documents.into_cursor()?;
+    while let Some((docid_bytes, value)) = cursor.move_on_next()? {
+        let obkv = obkv::KvReader::new(value);
+        // since we only needs the primary key when we throw an error we create this getter to
+        // lazily get it when needed
+        let document_id = || -> Value {
+            let document_id = obkv.get(primary_key_id).unwrap();
+            serde_json::from_slice(document_id).unwrap()
+        };
+
+        // the `_geo.lat` and `_geo.lng` points are extracted from the flattened documents
+        if let Some(value) = obkv.get(geo_fid) {
+            let value: Value = serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+            if is_geojson(&value) {
+                let geometry = Geometry::from_geojson(value)
+                    .map_err(|error| GeoError::BadGeoJson { document_id: document_id(), error })?;
+                let bytes = serde_json::to_vec(&geometry).map_err(InternalError::SerdeJson)?;
+                writer.insert(docid_bytes, bytes)?;
+            }
+        }
+    }
+
+    writer_into_reader(writer)
+}
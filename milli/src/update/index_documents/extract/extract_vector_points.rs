@@ -0,0 +1,62 @@
+use std::fs::File;
+use std::io;
+
+use serde_json::Value;
+
+use super::helpers::{create_writer, writer_into_reader, GrenadParameters};
+use crate::error::UserError;
+use crate::{FieldId, InternalError, Result, VectorOrArrayOfVectors};
+
+/// Extracts the embedding vectors contained in each document under the `_vectors` field.
+///
+/// Returns the generated grenad reader containing the docid and the index of the vector
+/// in the document as key associated to the vector as native-endian `f32`s.
+#[logging_timer::time]
+pub fn extract_vector_points<R: io::Read + io::Seek>(
+    obkv_documents: grenad::Reader<R>,
+    indexer: GrenadParameters,
+    primary_key_id: FieldId,
+    vectors_fid: FieldId,
+) -> Result<grenad::Reader<File>> {
+    let mut writer = create_writer(
+        indexer.chunk_compression_type,
+        indexer.chunk_compression_level,
+        tempfile::tempfile()?,
+    );
+
+    let mut cursor = obkv_documents.into_cursor()?;
+    while let Some((docid_bytes, value)) = cursor.move_on_next()? {
+        let obkv = obkv::KvReader::new(value);
+        // since we only needs the primary key when we throw an error we create this getter to
+        // lazily get it when needed
+        let document_id = || -> Value {
+            let document_id = obkv.get(primary_key_id).unwrap();
+            serde_json::from_slice(document_id).unwrap()
+        };
+
+        // first we retrieve the _vectors field
+        if let Some(vectors) = obkv.get(vectors_fid) {
+            // then we extract the vectors, a `null` value means there is no vector
+            let vectors = match serde_json::from_slice::<Option<VectorOrArrayOfVectors>>(vectors) {
+                Ok(vectors) => vectors.map_or_else(Vec::new, |v| v.into_array_of_vectors()),
+                Err(_) => {
+                    return Err(UserError::InvalidVectorsType {
+                        document_id: document_id(),
+                        value: serde_json::from_slice(vectors).map_err(InternalError::SerdeJson)?,
+                    }
+                    .into())
+                }
+            };
+
+            for (i, vector) in vectors.into_iter().enumerate().take(u16::MAX as usize) {
+                let index = u16::try_from(i).unwrap();
+                let mut key = docid_bytes.to_vec();
+                key.extend_from_slice(&index.to_be_bytes());
+                let bytes: Vec<u8> = vector.iter().flat_map(|f| f.to_ne_bytes()).collect();
+                writer.insert(key, bytes)?;
+            }
+        }
+    }
+
+    writer_into_reader(writer)
+}
@@ -4,13 +4,16 @@ mod extract_facet_string_docids;
 mod extract_fid_docid_facet_values;
 mod extract_fid_word_count_docids;
 mod extract_geo_points;
+mod extract_geo_shapes;
+mod extract_vector_points;
 mod extract_word_docids;
 mod extract_word_pair_proximity_docids;
 mod extract_word_position_docids;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
 
+use charabia::{Language, Script};
 use crossbeam_channel::Sender;
 use log::debug;
 use rayon::prelude::*;
@@ -21,6 +24,8 @@ use self::extract_facet_string_docids::extract_facet_string_docids;
 use self::extract_fid_docid_facet_values::extract_fid_docid_facet_values;
 use self::extract_fid_word_count_docids::extract_fid_word_count_docids;
 use self::extract_geo_points::extract_geo_points;
+use self::extract_geo_shapes::extract_geo_shapes;
+use self::extract_vector_points::extract_vector_points;
 use self::extract_word_docids::extract_word_docids;
 use self::extract_word_pair_proximity_docids::extract_word_pair_proximity_docids;
 use self::extract_word_position_docids::extract_word_position_docids;
@@ -29,7 +34,7 @@ use super::helpers::{
     GrenadParameters, MergeFn, MergeableReader,
 };
 use super::{helpers, TypedChunk};
-use crate::{FieldId, Result};
+use crate::{FieldId, ProximityPrecision, Result, TokenizerSettings};
 
 /// Extract data for each databases from obkv documents in parallel.
 /// Send data in grenad file over provided Sender.
@@ -42,15 +47,27 @@ pub(crate) fn data_from_obkv_documents(
     searchable_fields: Option<HashSet<FieldId>>,
     faceted_fields: HashSet<FieldId>,
     primary_key_id: FieldId,
+    geo_field_id: Option<FieldId>,
     geo_fields_ids: Option<(FieldId, FieldId)>,
+    vectors_field_id: Option<FieldId>,
     stop_words: Option<fst::Set<&[u8]>>,
+    tokenizer_settings: &TokenizerSettings,
+    localized_fields: &HashMap<FieldId, HashMap<Script, Vec<Language>>>,
     max_positions_per_attributes: Option<u32>,
     exact_attributes: HashSet<FieldId>,
+    proximity_precision: ProximityPrecision,
 ) -> Result<()> {
     original_obkv_chunks
         .par_bridge()
         .map(|original_documents_chunk| {
-            send_original_documents_data(original_documents_chunk, lmdb_writer_sx.clone())
+            send_original_documents_data(
+                original_documents_chunk,
+                indexer,
+                lmdb_writer_sx.clone(),
+                primary_key_id,
+                geo_field_id,
+                vectors_field_id,
+            )
         })
         .collect::<Result<()>>()?;
 
@@ -67,6 +84,8 @@ pub(crate) fn data_from_obkv_documents(
                 primary_key_id,
                 geo_fields_ids,
                 &stop_words,
+                tokenizer_settings,
+                localized_fields,
                 max_positions_per_attributes,
             )
         })
@@ -96,15 +115,18 @@ pub(crate) fn data_from_obkv_documents(
         });
     }
 
-    spawn_extraction_task::<_, _, Vec<grenad::Reader<File>>>(
-        docid_word_positions_chunks.clone(),
-        indexer,
-        lmdb_writer_sx.clone(),
-        extract_word_pair_proximity_docids,
-        merge_cbo_roaring_bitmaps,
-        TypedChunk::WordPairProximityDocids,
-        "word-pair-proximity-docids",
-    );
+    // the word pair proximities are only indexed when computed between words.
+    if proximity_precision == ProximityPrecision::ByWord {
+        spawn_extraction_task::<_, _, Vec<grenad::Reader<File>>>(
+            docid_word_positions_chunks.clone(),
+            indexer,
+            lmdb_writer_sx.clone(),
+            extract_word_pair_proximity_docids,
+            merge_cbo_roaring_bitmaps,
+            TypedChunk::WordPairProximityDocids,
+            "word-pair-proximity-docids",
+        );
+    }
 
     spawn_extraction_task::<_, _, Vec<grenad::Reader<File>>>(
         docid_word_positions_chunks.clone(),
@@ -201,13 +223,54 @@ fn spawn_extraction_task<FE, FS, M>(
 
 /// Extract chunked data and send it into lmdb_writer_sx sender:
 /// - documents
+/// - vector_points
 fn send_original_documents_data(
     original_documents_chunk: Result<grenad::Reader<File>>,
+    indexer: GrenadParameters,
     lmdb_writer_sx: Sender<Result<TypedChunk>>,
+    primary_key_id: FieldId,
+    geo_field_id: Option<FieldId>,
+    vectors_field_id: Option<FieldId>,
 ) -> Result<()> {
     let original_documents_chunk =
         original_documents_chunk.and_then(|c| unsafe { as_cloneable_grenad(&c) })?;
 
+    // the GeoJSON geometries are extracted from the original documents as flattening
+    // would merge the coordinates of the different positions into a single array.
+    if let Some(geo_field_id) = geo_field_id {
+        let documents_chunk_cloned = original_documents_chunk.clone();
+        let lmdb_writer_sx_cloned = lmdb_writer_sx.clone();
+        rayon::spawn(move || {
+            let result =
+                extract_geo_shapes(documents_chunk_cloned, indexer, primary_key_id, geo_field_id);
+            let _ = match result {
+                Ok(geo_shapes) => lmdb_writer_sx_cloned.send(Ok(TypedChunk::GeoShapes(geo_shapes))),
+                Err(error) => lmdb_writer_sx_cloned.send(Err(error)),
+            };
+        });
+    }
+
+    // the vectors are extracted from the original documents as flattening
+    // would merge the arrays of vectors into a single array.
+    if let Some(vectors_field_id) = vectors_field_id {
+        let documents_chunk_cloned = original_documents_chunk.clone();
+        let lmdb_writer_sx_cloned = lmdb_writer_sx.clone();
+        rayon::spawn(move || {
+            let result = extract_vector_points(
+                documents_chunk_cloned,
+                indexer,
+                primary_key_id,
+                vectors_field_id,
+            );
+            let _ = match result {
+                Ok(vector_points) => {
+                    lmdb_writer_sx_cloned.send(Ok(TypedChunk::VectorPoints(vector_points)))
+                }
+                Err(error) => lmdb_writer_sx_cloned.send(Err(error)),
+            };
+        });
+    }
+
     // TODO: create a custom internal error
     lmdb_writer_sx.send(Ok(TypedChunk::Documents(original_documents_chunk))).unwrap();
     Ok(())
@@ -230,6 +293,8 @@ fn send_and_extract_flattened_documents_data(
     primary_key_id: FieldId,
     geo_fields_ids: Option<(FieldId, FieldId)>,
     stop_words: &Option<fst::Set<&[u8]>>,
+    tokenizer_settings: &TokenizerSettings,
+    localized_fields: &HashMap<FieldId, HashMap<Script, Vec<Language>>>,
     max_positions_per_attributes: Option<u32>,
 ) -> Result<(
     grenad::Reader<CursorClonableMmap>,
@@ -262,6 +327,8 @@ fn send_and_extract_flattened_documents_data(
                     indexer,
                     searchable_fields,
                     stop_words.as_ref(),
+                    tokenizer_settings,
+                    localized_fields,
                     max_positions_per_attributes,
                 )?;
 
@@ -4,7 +4,7 @@ mod helpers;
 mod transform;
 mod typed_chunk;
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::io::{Cursor, Read, Seek};
 use std::iter::FromIterator;
 use std::num::NonZeroU32;
@@ -22,7 +22,7 @@ use typed_chunk::{write_typed_chunk_into_index, TypedChunk};
 use self::enrich::enrich_documents_batch;
 pub use self::enrich::{
     extract_finite_float_from_value, validate_document_id, validate_document_id_value,
-    validate_geo_from_json, DocumentId,
+    validate_geo_from_json, DocumentId, MAX_GEO_POINTS_PER_DOCUMENT,
 };
 pub use self::helpers::{
     as_cloneable_grenad, create_sorter, create_writer, fst_stream_into_hashset,
@@ -38,9 +38,11 @@ use crate::update::{
     self, DeletionStrategy, IndexerConfig, PrefixWordPairsProximityDocids, UpdateIndexingStep,
     WordPrefixDocids, WordPrefixPositionDocids, WordsPrefixesFst,
 };
-use crate::{Index, Result, RoaringBitmapCodec};
+use crate::{
+    locales_allow_list, Index, PrefixSearch, Result, RoaringBitmapCodec, TokenizerSettings,
+};
 
-static MERGED_DATABASE_COUNT: usize = 7;
+pub(crate) static MERGED_DATABASE_COUNT: usize = 7;
 static PREFIX_DATABASE_COUNT: usize = 5;
 static TOTAL_POSTING_DATABASE_COUNT: usize = MERGED_DATABASE_COUNT + PREFIX_DATABASE_COUNT;
 
@@ -253,34 +255,59 @@ where
         // get the primary key field id
         let primary_key_id = fields_ids_map.id(&primary_key).unwrap();
 
-        // get searchable fields for word databases
+        // get the fid of the `_vectors` field.
+        let vectors_field_id = self.index.fields_ids_map(self.wtxn)?.id("_vectors");
+
+        // get searchable fields for word databases, the vectors are never tokenized.
         let searchable_fields =
-            self.index.searchable_fields_ids(self.wtxn)?.map(HashSet::from_iter);
+            match (self.index.searchable_fields_ids(self.wtxn)?, vectors_field_id) {
+                (Some(fields_ids), vectors_field_id) => Some(
+                    fields_ids.into_iter().filter(|fid| Some(*fid) != vectors_field_id).collect(),
+                ),
+                (None, Some(vectors_field_id)) => {
+                    Some(fields_ids_map.ids().filter(|fid| *fid != vectors_field_id).collect())
+                }
+                (None, None) => None,
+            };
         // get filterable fields for facet databases
         let faceted_fields = self.index.faceted_fields_ids(self.wtxn)?;
-        // get the fid of the `_geo.lat` and `_geo.lng` fields.
-        let geo_fields_ids = match self.index.fields_ids_map(self.wtxn)?.id("_geo") {
+        // get the fid of the `_geo` field if it is faceted.
+        let geo_field_id = match self.index.fields_ids_map(self.wtxn)?.id("_geo") {
             Some(gfid) => {
                 let is_sortable = self.index.sortable_fields_ids(self.wtxn)?.contains(&gfid);
                 let is_filterable = self.index.filterable_fields_ids(self.wtxn)?.contains(&gfid);
-                // if `_geo` is faceted then we get the `lat` and `lng`
-                if is_sortable || is_filterable {
-                    let field_ids = self
-                        .index
-                        .fields_ids_map(self.wtxn)?
-                        .insert("_geo.lat")
-                        .zip(self.index.fields_ids_map(self.wtxn)?.insert("_geo.lng"))
-                        .ok_or(UserError::AttributeLimitReached)?;
-                    Some(field_ids)
-                } else {
-                    None
-                }
+                (is_sortable || is_filterable).then_some(gfid)
+            }
+            None => None,
+        };
+        // if `_geo` is faceted then we get the fid of the `_geo.lat` and `_geo.lng` fields.
+        let geo_fields_ids = match geo_field_id {
+            Some(_) => {
+                let field_ids = self
+                    .index
+                    .fields_ids_map(self.wtxn)?
+                    .insert("_geo.lat")
+                    .zip(self.index.fields_ids_map(self.wtxn)?.insert("_geo.lng"))
+                    .ok_or(UserError::AttributeLimitReached)?;
+                Some(field_ids)
             }
             None => None,
         };
 
         let stop_words = self.index.stop_words(self.wtxn)?;
+        let tokenizer_settings = TokenizerSettings::from_index(self.index, self.wtxn)?;
+        // get the fields that must be tokenized with a restricted set of languages.
+        let localized_attributes_rules =
+            self.index.localized_attributes_rules(self.wtxn)?.unwrap_or_default();
+        let localized_fields: HashMap<_, _> = fields_ids_map
+            .iter()
+            .filter_map(|(field_id, name)| {
+                let rule = localized_attributes_rules.iter().find(|rule| rule.match_str(name))?;
+                Some((field_id, locales_allow_list(&rule.locales)))
+            })
+            .collect();
         let exact_attributes = self.index.exact_attributes_ids(self.wtxn)?;
+        let proximity_precision = self.index.proximity_precision(self.wtxn)?.unwrap_or_default();
 
         let pool_params = GrenadParameters {
             chunk_compression_type: self.indexer_config.chunk_compression_type,
@@ -313,10 +340,15 @@ where
                     searchable_fields,
                     faceted_fields,
                     primary_key_id,
+                    geo_field_id,
                     geo_fields_ids,
+                    vectors_field_id,
                     stop_words,
+                    &tokenizer_settings,
+                    &localized_fields,
                     max_positions_per_attributes,
                     exact_attributes,
+                    proximity_precision,
                 )
             });
 
@@ -440,6 +472,11 @@ where
         FP: Fn(UpdateIndexingStep) + Sync,
         FA: Fn() -> bool + Sync,
     {
+        // The prefix databases are only used to search the last word of the query as a prefix.
+        if self.index.prefix_search(self.wtxn)?.unwrap_or_default() == PrefixSearch::Disabled {
+            return Ok(());
+        }
+
         // Merged databases are already been indexed, we start from this count;
         let mut databases_seen = MERGED_DATABASE_COUNT;
 
@@ -1025,6 +1062,162 @@ mod tests {
             &error.to_string(),
             r#"Could not parse longitude in the document with the id: `0`. Was expecting a finite number but instead got `"hello"`."#
         );
+
+        let error = index
+            .add_documents(documents!([
+              { "id": 0, "_geo": [{ "lat": 12, "lng": 42 }, { "lat": 12 }] }
+            ]))
+            .unwrap_err();
+        assert_eq!(
+            &error.to_string(),
+            r#"Could not find longitude in the document with the id: `0`. Was expecting a `_geo.lng` field."#
+        );
+
+        let error = index
+            .add_documents(documents!([
+              { "id": 0, "_geo": { "type": "Point", "coordinates": [12, 91] } }
+            ]))
+            .unwrap_err();
+        assert_eq!(
+            &error.to_string(),
+            r#"The `_geo` field in the document with the id: `0` is not a valid GeoJSON geometry: the latitude `91` must be contained between -90 and 90 degrees."#
+        );
+
+        let error = index
+            .add_documents(documents!([
+              { "id": 0, "_geo": { "type": "LineString", "coordinates": [[12, 42]] } }
+            ]))
+            .unwrap_err();
+        assert_eq!(
+            &error.to_string(),
+            r#"The `_geo` field in the document with the id: `0` is not a valid GeoJSON geometry: a line string must contain at least two positions."#
+        );
+    }
+
+    #[test]
+    fn vector_search() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.deletion_strategy = DeletionStrategy::AlwaysHard;
+
+        index
+            .add_documents(documents!([
+              { "id": 0, "kind": "up", "_vectors": [0.0, 1.0] },
+              { "id": 1, "kind": "right", "_vectors": [[1.0, 0.1], [-1.0, -1.0]] },
+              { "id": 2, "kind": "diagonal", "_vectors": [1.0, 1.0] },
+              { "id": 3, "kind": "none", "_vectors": null },
+              { "id": 4, "kind": "none" }
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.vector_id_docid.len(&rtxn).unwrap(), 4);
+
+        let result = index.search(&rtxn).vector([1.0, 0.0]).execute().unwrap();
+        assert_eq!(result.documents_ids, vec![1, 2, 0]);
+        assert_eq!(result.candidates, RoaringBitmap::from_iter([0, 1, 2]));
+
+        let result = index.search(&rtxn).vector([1.0, 0.0]).offset(1).limit(1).execute().unwrap();
+        assert_eq!(result.documents_ids, vec![2]);
+
+        // the candidates below the threshold are not counted, even when they are not on the page.
+        let result =
+            index.search(&rtxn).vector([1.0, 0.0]).ranking_score_threshold(0.8).execute().unwrap();
+        assert_eq!(result.documents_ids, vec![1, 2]);
+        assert_eq!(result.candidates, RoaringBitmap::from_iter([1, 2]));
+        let result = index
+            .search(&rtxn)
+            .vector([1.0, 0.0])
+            .ranking_score_threshold(0.8)
+            .limit(1)
+            .execute()
+            .unwrap();
+        assert_eq!(result.documents_ids, vec![1]);
+        assert_eq!(result.candidates, RoaringBitmap::from_iter([1, 2]));
+
+        // the candidates are all counted when there is no time left to compare their vectors.
+        let result = index
+            .search(&rtxn)
+            .vector([1.0, 0.0])
+            .ranking_score_threshold(0.8)
+            .time_budget(crate::TimeBudget::new(std::time::Duration::ZERO))
+            .execute()
+            .unwrap();
+        assert_eq!(result.candidates, RoaringBitmap::from_iter([0, 1, 2]));
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset!(S("kind")));
+                settings.set_sortable_fields(hashset!(S("kind")));
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = crate::Filter::from_str("kind != right").unwrap().unwrap();
+        let result = index.search(&rtxn).vector([1.0, 0.0]).filter(filter).execute().unwrap();
+        assert_eq!(result.documents_ids, vec![2, 0]);
+
+        // the query only selects the documents, they are still ranked by similarity.
+        let result = index.search(&rtxn).vector([1.0, 0.0]).query("up").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0]);
+        assert_eq!(result.candidates, RoaringBitmap::from_iter([0]));
+
+        let error = index.search(&rtxn).vector([1.0]).execute().unwrap_err();
+        assert_eq!(&error.to_string(), "Invalid vector dimensions: expected: `2`, found: `1`.");
+
+        let error = index
+            .search(&rtxn)
+            .vector([1.0, 0.0])
+            .sort_criteria(vec!["kind:asc".parse().unwrap()])
+            .execute()
+            .unwrap_err();
+        assert!(matches!(error, Error::UserError(UserError::SortWithVector)));
+        drop(rtxn);
+
+        // the vectors of the deleted documents are removed from the HNSW.
+        index.delete_document("2");
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.vector_id_docid.len(&rtxn).unwrap(), 3);
+        assert_eq!(index.vector_hnsw.len(&rtxn).unwrap(), 3);
+        assert_eq!(index.vector_documents_ids(&rtxn).unwrap(), RoaringBitmap::from_iter([0, 1]));
+        let result = index.search(&rtxn).vector([1.0, 0.0]).execute().unwrap();
+        assert_eq!(result.documents_ids, vec![1, 0]);
+        drop(rtxn);
+
+        index
+            .add_documents(documents!([{ "id": 5, "kind": "up", "_vectors": [0.1, 1.0] }]))
+            .unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.vector_hnsw.len(&rtxn).unwrap(), 4);
+        assert_eq!(index.vector_id_docid.len(&rtxn).unwrap(), 4);
+
+        let result = index.search(&rtxn).vector([0.0, 1.0]).execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0, 5, 1]);
+        let result = index.search(&rtxn).vector([0.0, 1.0]).distinct(S("kind")).execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0, 1]);
+    }
+
+    #[test]
+    fn vectors_error() {
+        let index = TempIndex::new();
+
+        let error = index
+            .add_documents(documents!([
+              { "id": 0, "_vectors": { "a": 1 } }
+            ]))
+            .unwrap_err();
+        assert_eq!(
+            &error.to_string(),
+            r#"The `_vectors` field in the document with the id: `0` is not an array. Was expecting an array of floats or an array of arrays of floats but instead got `{"a":1}`."#
+        );
+
+        let error = index
+            .add_documents(documents!([
+              { "id": 0, "_vectors": [1.0, 2.0] },
+              { "id": 1, "_vectors": [1.0, 2.0, 3.0] }
+            ]))
+            .unwrap_err();
+        assert_eq!(&error.to_string(), "Invalid vector dimensions: expected: `2`, found: `3`.");
     }
 
     #[test]
@@ -1817,10 +2010,11 @@ mod tests {
             "branch_id_number": 0
         }]};
 
-        let Err(Error::UserError(UserError::MultiplePrimaryKeyCandidatesFound {
-            candidates
-        })) =
-            index.add_documents(doc_multiple_ids) else { panic!("Expected Error::UserError(MultiplePrimaryKeyCandidatesFound)") };
+        let Err(Error::UserError(UserError::MultiplePrimaryKeyCandidatesFound { candidates })) =
+            index.add_documents(doc_multiple_ids)
+        else {
+            panic!("Expected Error::UserError(MultiplePrimaryKeyCandidatesFound)")
+        };
 
         assert_eq!(candidates, vec![S("id"), S("project_id"), S("public_uid"),]);
 
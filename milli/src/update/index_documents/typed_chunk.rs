@@ -7,18 +7,21 @@ use grenad::MergerBuilder;
 use heed::types::ByteSlice;
 use heed::{BytesDecode, RwTxn};
 use roaring::RoaringBitmap;
+use rstar::primitives::Rectangle;
 
 use super::helpers::{
     self, merge_ignore_values, roaring_bitmap_from_u32s_array, serialize_roaring_bitmap,
     valid_lmdb_key, CursorClonableMmap,
 };
 use super::{ClonableMmap, MergeFn};
+use crate::error::UserError;
 use crate::facet::FacetType;
 use crate::update::facet::FacetsUpdate;
 use crate::update::index_documents::helpers::as_cloneable_grenad;
+use crate::vector::hnsw;
 use crate::{
-    lat_lng_to_xyz, BoRoaringBitmapCodec, CboRoaringBitmapCodec, DocumentId, GeoPoint, Index,
-    Result,
+    lat_lng_to_xyz, BoRoaringBitmapCodec, CboRoaringBitmapCodec, DocumentId, GeoPoint, GeoShape,
+    Geometry, Index, InternalError, NDotProductPoint, Result, BEU32,
 };
 
 pub(crate) enum TypedChunk {
@@ -38,6 +41,8 @@ pub(crate) enum TypedChunk {
     FieldIdFacetNumberDocids(grenad::Reader<File>),
     FieldIdFacetExistsDocids(grenad::Reader<File>),
     GeoPoints(grenad::Reader<File>),
+    GeoShapes(grenad::Reader<File>),
+    VectorPoints(grenad::Reader<File>),
 }
 
 /// Write typed chunk in the corresponding LMDB database of the provided index.
@@ -196,7 +201,8 @@ pub(crate) fn write_typed_chunk_into_index(
             let mut cursor = geo_points.into_cursor()?;
             while let Some((key, value)) = cursor.move_on_next()? {
                 // convert the key back to a u32 (4 bytes)
-                let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
+                let (left, _index) = helpers::try_split_array_at(key).unwrap();
+                let docid = DocumentId::from_be_bytes(left);
 
                 // convert the latitude and longitude back to a f64 (8 bytes)
                 let (lat, tail) = helpers::try_split_array_at::<u8, 8>(value).unwrap();
@@ -210,6 +216,52 @@ pub(crate) fn write_typed_chunk_into_index(
             index.put_geo_rtree(wtxn, &rtree)?;
             index.put_geo_faceted_documents_ids(wtxn, &geo_faceted_docids)?;
         }
+        TypedChunk::GeoShapes(geo_shapes) => {
+            let mut rtree = index.geo_shapes_rtree(wtxn)?.unwrap_or_default();
+
+            let mut cursor = geo_shapes.into_cursor()?;
+            while let Some((key, value)) = cursor.move_on_next()? {
+                // convert the key back to a u32 (4 bytes)
+                let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
+
+                let geometry: Geometry =
+                    serde_json::from_slice(value).map_err(InternalError::SerdeJson)?;
+                let envelope = geometry.envelope();
+                let rectangle = Rectangle::from_corners(envelope.lower(), envelope.upper());
+
+                rtree.insert(GeoShape::new(rectangle, (docid, geometry)));
+            }
+            index.put_geo_shapes_rtree(wtxn, &rtree)?;
+        }
+        TypedChunk::VectorPoints(vector_points) => {
+            let mut vector_documents_ids = index.vector_documents_ids(wtxn)?;
+            let mut expected_dimensions = hnsw::dimensions(index, wtxn)?;
+            let mut cursor = vector_points.into_cursor()?;
+            while let Some((key, value)) = cursor.move_on_next()? {
+                // convert the key back to a u32 (4 bytes)
+                let (left, _index) = helpers::try_split_array_at(key).unwrap();
+                let docid = DocumentId::from_be_bytes(left);
+
+                // convert the vector back to a Vec<f32>
+                let vector: Vec<f32> = value
+                    .chunks_exact(4)
+                    .map(|bytes| f32::from_ne_bytes(bytes.try_into().unwrap()))
+                    .collect();
+
+                let found = vector.len();
+                let expected = *expected_dimensions.get_or_insert(found);
+                if expected != found {
+                    return Err(UserError::InvalidVectorDimensions { expected, found }.into());
+                }
+
+                // the vector is linked into the HNSW by only updating the nodes around it.
+                let pid = hnsw::insert(index, wtxn, NDotProductPoint::new(vector))?;
+                index.vector_id_docid.put(wtxn, &BEU32::new(pid), &BEU32::new(docid))?;
+                vector_documents_ids.insert(docid);
+            }
+            log::debug!("There are {} entries in the HNSW so far", index.vector_hnsw.len(wtxn)?);
+            index.put_vector_documents_ids(wtxn, &vector_documents_ids)?;
+        }
     }
 
     Ok((RoaringBitmap::new(), is_merged_database))
@@ -14,7 +14,10 @@ use crate::error::UserError;
 use crate::index::{DEFAULT_MIN_WORD_LEN_ONE_TYPO, DEFAULT_MIN_WORD_LEN_TWO_TYPOS};
 use crate::update::index_documents::IndexDocumentsMethod;
 use crate::update::{IndexDocuments, UpdateIndexingStep};
-use crate::{FieldsIdsMap, Index, Result};
+use crate::{
+    FieldsIdsMap, Index, LocalizedAttributesRule, OrderBy, PrefixSearch, ProximityPrecision,
+    Result, TokenizerSettings,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum Setting<T> {
@@ -112,6 +115,9 @@ pub struct Settings<'a, 't, 'u, 'i> {
     sortable_fields: Setting<HashSet<String>>,
     criteria: Setting<Vec<Criterion>>,
     stop_words: Setting<BTreeSet<String>>,
+    non_separator_tokens: Setting<BTreeSet<String>>,
+    separator_tokens: Setting<BTreeSet<String>>,
+    dictionary: Setting<BTreeSet<String>>,
     distinct_field: Setting<String>,
     synonyms: Setting<HashMap<String, Vec<String>>>,
     primary_key: Setting<String>,
@@ -122,7 +128,12 @@ pub struct Settings<'a, 't, 'u, 'i> {
     /// Attributes on which typo tolerance is disabled.
     exact_attributes: Setting<HashSet<String>>,
     max_values_per_facet: Setting<usize>,
+    sort_facet_values_by: Setting<HashMap<String, OrderBy>>,
     pagination_max_total_hits: Setting<usize>,
+    localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
+    proximity_precision: Setting<ProximityPrecision>,
+    search_cutoff: Setting<u64>,
+    prefix_search: Setting<PrefixSearch>,
 }
 
 impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
@@ -140,6 +151,9 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             sortable_fields: Setting::NotSet,
             criteria: Setting::NotSet,
             stop_words: Setting::NotSet,
+            non_separator_tokens: Setting::NotSet,
+            separator_tokens: Setting::NotSet,
+            dictionary: Setting::NotSet,
             distinct_field: Setting::NotSet,
             synonyms: Setting::NotSet,
             primary_key: Setting::NotSet,
@@ -149,7 +163,12 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             min_word_len_one_typo: Setting::NotSet,
             exact_attributes: Setting::NotSet,
             max_values_per_facet: Setting::NotSet,
+            sort_facet_values_by: Setting::NotSet,
             pagination_max_total_hits: Setting::NotSet,
+            localized_attributes_rules: Setting::NotSet,
+            proximity_precision: Setting::NotSet,
+            search_cutoff: Setting::NotSet,
+            prefix_search: Setting::NotSet,
             indexer_config,
         }
     }
@@ -203,6 +222,39 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
             if stop_words.is_empty() { Setting::Reset } else { Setting::Set(stop_words) }
     }
 
+    pub fn reset_non_separator_tokens(&mut self) {
+        self.non_separator_tokens = Setting::Reset;
+    }
+
+    pub fn set_non_separator_tokens(&mut self, non_separator_tokens: BTreeSet<String>) {
+        self.non_separator_tokens = if non_separator_tokens.is_empty() {
+            Setting::Reset
+        } else {
+            Setting::Set(non_separator_tokens)
+        }
+    }
+
+    pub fn reset_separator_tokens(&mut self) {
+        self.separator_tokens = Setting::Reset;
+    }
+
+    pub fn set_separator_tokens(&mut self, separator_tokens: BTreeSet<String>) {
+        self.separator_tokens = if separator_tokens.is_empty() {
+            Setting::Reset
+        } else {
+            Setting::Set(separator_tokens)
+        }
+    }
+
+    pub fn reset_dictionary(&mut self) {
+        self.dictionary = Setting::Reset;
+    }
+
+    pub fn set_dictionary(&mut self, dictionary: BTreeSet<String>) {
+        self.dictionary =
+            if dictionary.is_empty() { Setting::Reset } else { Setting::Set(dictionary) }
+    }
+
     pub fn reset_distinct_field(&mut self) {
         self.distinct_field = Setting::Reset;
     }
@@ -275,6 +327,47 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.max_values_per_facet = Setting::Reset;
     }
 
+    pub fn set_sort_facet_values_by(&mut self, value: HashMap<String, OrderBy>) {
+        self.sort_facet_values_by = Setting::Set(value);
+    }
+
+    pub fn reset_sort_facet_values_by(&mut self) {
+        self.sort_facet_values_by = Setting::Reset;
+    }
+
+    pub fn set_localized_attributes_rules(&mut self, value: Vec<LocalizedAttributesRule>) {
+        self.localized_attributes_rules =
+            if value.is_empty() { Setting::Reset } else { Setting::Set(value) }
+    }
+
+    pub fn reset_localized_attributes_rules(&mut self) {
+        self.localized_attributes_rules = Setting::Reset;
+    }
+
+    pub fn set_proximity_precision(&mut self, value: ProximityPrecision) {
+        self.proximity_precision = Setting::Set(value);
+    }
+
+    pub fn reset_proximity_precision(&mut self) {
+        self.proximity_precision = Setting::Reset;
+    }
+
+    pub fn set_search_cutoff(&mut self, value: u64) {
+        self.search_cutoff = Setting::Set(value);
+    }
+
+    pub fn reset_search_cutoff(&mut self) {
+        self.search_cutoff = Setting::Reset;
+    }
+
+    pub fn set_prefix_search(&mut self, value: PrefixSearch) {
+        self.prefix_search = Setting::Set(value);
+    }
+
+    pub fn reset_prefix_search(&mut self) {
+        self.prefix_search = Setting::Reset;
+    }
+
     pub fn set_pagination_max_total_hits(&mut self, value: usize) {
         self.pagination_max_total_hits = Setting::Set(value);
     }
@@ -433,12 +526,71 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         }
     }
 
+    fn update_non_separator_tokens(&mut self) -> Result<bool> {
+        match self.non_separator_tokens {
+            Setting::Set(ref non_separator_tokens) => {
+                let current = self.index.non_separator_tokens(self.wtxn)?;
+
+                // Does the new list differ from the previous one?
+                if current.map_or(true, |current| &current != non_separator_tokens) {
+                    self.index.put_non_separator_tokens(self.wtxn, non_separator_tokens)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_non_separator_tokens(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_separator_tokens(&mut self) -> Result<bool> {
+        match self.separator_tokens {
+            Setting::Set(ref separator_tokens) => {
+                let current = self.index.separator_tokens(self.wtxn)?;
+
+                // Does the new list differ from the previous one?
+                if current.map_or(true, |current| &current != separator_tokens) {
+                    self.index.put_separator_tokens(self.wtxn, separator_tokens)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_separator_tokens(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_dictionary(&mut self) -> Result<bool> {
+        match self.dictionary {
+            Setting::Set(ref dictionary) => {
+                let current = self.index.dictionary(self.wtxn)?;
+
+                // Does the new dictionary differ from the previous one?
+                if current.map_or(true, |current| &current != dictionary) {
+                    self.index.put_dictionary(self.wtxn, dictionary)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_dictionary(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
     fn update_synonyms(&mut self) -> Result<bool> {
         match self.synonyms {
             Setting::Set(ref synonyms) => {
-                fn normalize(tokenizer: &Tokenizer<&[u8]>, text: &str) -> Vec<String> {
-                    tokenizer
-                        .tokenize(text)
+                fn normalize(
+                    tokenizer: &Tokenizer<&[u8]>,
+                    tokenizer_settings: &TokenizerSettings,
+                    text: &str,
+                ) -> Vec<String> {
+                    tokenizer_settings
+                        .tokenize(tokenizer, text)
+                        .into_iter()
                         .filter_map(|token| {
                             if token.is_word() {
                                 Some(token.lemma().to_string())
@@ -455,13 +607,15 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
                     builder.stop_words(stop_words);
                 }
                 let tokenizer = builder.build();
+                let tokenizer_settings = TokenizerSettings::from_index(self.index, self.wtxn)?;
 
                 let mut new_synonyms = HashMap::new();
                 for (word, synonyms) in synonyms {
                     // Normalize both the word and associated synonyms.
-                    let normalized_word = normalize(&tokenizer, word);
-                    let normalized_synonyms =
-                        synonyms.iter().map(|synonym| normalize(&tokenizer, synonym));
+                    let normalized_word = normalize(&tokenizer, &tokenizer_settings, word);
+                    let normalized_synonyms = synonyms
+                        .iter()
+                        .map(|synonym| normalize(&tokenizer, &tokenizer_settings, synonym));
 
                     // Store the normalized synonyms under the normalized word,
                     // merging the possible duplicate words.
@@ -477,12 +631,10 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
 
                 let old_synonyms = self.index.synonyms(self.wtxn)?;
 
-                if new_synonyms != old_synonyms {
-                    self.index.put_synonyms(self.wtxn, &new_synonyms)?;
-                    Ok(true)
-                } else {
-                    Ok(false)
-                }
+                // The user-defined synonyms are always stored to be able to
+                // normalize them again when the tokenizer settings change.
+                self.index.put_synonyms(self.wtxn, &new_synonyms, synonyms)?;
+                Ok(new_synonyms != old_synonyms)
             }
             Setting::Reset => Ok(self.index.delete_synonyms(self.wtxn)?),
             Setting::NotSet => Ok(false),
@@ -633,8 +785,13 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
     fn update_exact_words(&mut self) -> Result<()> {
         match self.exact_words {
             Setting::Set(ref mut words) => {
-                fn normalize(tokenizer: &Tokenizer<&[u8]>, text: &str) -> String {
-                    tokenizer.tokenize(text).map(|token| token.lemma().to_string()).collect()
+                fn normalize(
+                    tokenizer: &Tokenizer<&[u8]>,
+                    tokenizer_settings: &TokenizerSettings,
+                    text: &str,
+                ) -> String {
+                    let tokens = tokenizer_settings.tokenize(tokenizer, text);
+                    tokens.iter().map(|token| token.lemma()).collect()
                 }
 
                 let mut builder = TokenizerBuilder::new();
@@ -643,18 +800,23 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
                     builder.stop_words(stop_words);
                 }
                 let tokenizer = builder.build();
+                let tokenizer_settings = TokenizerSettings::from_index(self.index, self.wtxn)?;
 
-                let mut words: Vec<_> =
-                    words.iter().map(|word| normalize(&tokenizer, word)).collect();
+                let mut normalized_words: Vec<_> = words
+                    .iter()
+                    .map(|word| normalize(&tokenizer, &tokenizer_settings, word))
+                    .collect();
 
                 // normalization could reorder words
-                words.sort_unstable();
+                normalized_words.sort_unstable();
 
-                let words = fst::Set::from_iter(words.iter())?;
-                self.index.put_exact_words(self.wtxn, &words)?;
+                let normalized_words = fst::Set::from_iter(normalized_words.iter())?;
+                self.index.put_exact_words(self.wtxn, &normalized_words)?;
+                self.index.put_user_defined_exact_words(self.wtxn, words)?;
             }
             Setting::Reset => {
                 self.index.put_exact_words(self.wtxn, &fst::Set::default())?;
+                self.index.delete_user_defined_exact_words(self.wtxn)?;
             }
             Setting::NotSet => (),
         }
@@ -676,6 +838,56 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
+    fn update_sort_facet_values_by(&mut self) -> Result<()> {
+        match self.sort_facet_values_by.as_ref() {
+            Setting::Set(value) => {
+                self.index.put_sort_facet_values_by(self.wtxn, value)?;
+            }
+            Setting::Reset => {
+                self.index.delete_sort_facet_values_by(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    fn update_localized_attributes_rules(&mut self) -> Result<bool> {
+        match self.localized_attributes_rules {
+            Setting::Set(ref rules) => {
+                let current = self.index.localized_attributes_rules(self.wtxn)?;
+
+                // Do the new rules differ from the previous ones?
+                if current.as_ref() != Some(rules) {
+                    self.index.put_localized_attributes_rules(self.wtxn, rules)?;
+                    Ok(true)
+                } else {
+                    Ok(false)
+                }
+            }
+            Setting::Reset => Ok(self.index.delete_localized_attributes_rules(self.wtxn)?),
+            Setting::NotSet => Ok(false),
+        }
+    }
+
+    fn update_proximity_precision(&mut self) -> Result<bool> {
+        let old = self.index.proximity_precision(self.wtxn)?.unwrap_or_default();
+        let new = match self.proximity_precision {
+            Setting::Set(new) => {
+                self.index.put_proximity_precision(self.wtxn, new)?;
+                new
+            }
+            Setting::Reset => {
+                self.index.delete_proximity_precision(self.wtxn)?;
+                ProximityPrecision::default()
+            }
+            Setting::NotSet => return Ok(false),
+        };
+
+        // The word pair proximity databases must be rebuilt or dropped when the precision changes.
+        Ok(old != new)
+    }
+
     fn update_pagination_max_total_hits(&mut self) -> Result<()> {
         match self.pagination_max_total_hits {
             Setting::Set(max) => {
@@ -690,6 +902,56 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         Ok(())
     }
 
+    fn update_prefix_search(&mut self) -> Result<bool> {
+        let old = self.index.prefix_search(self.wtxn)?.unwrap_or_default();
+        let new = match self.prefix_search {
+            Setting::Set(new) => {
+                self.index.put_prefix_search(self.wtxn, new)?;
+                new
+            }
+            Setting::Reset => {
+                self.index.delete_prefix_search(self.wtxn)?;
+                PrefixSearch::default()
+            }
+            Setting::NotSet => return Ok(false),
+        };
+
+        // The prefix databases must be built or dropped when the prefix search changes.
+        Ok(old != new)
+    }
+
+    fn update_search_cutoff(&mut self) -> Result<()> {
+        match self.search_cutoff {
+            Setting::Set(cutoff) => {
+                self.index.put_search_cutoff(self.wtxn, cutoff)?;
+            }
+            Setting::Reset => {
+                self.index.delete_search_cutoff(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
+    /// Sets the synonyms and the exact words that are not updated to the ones stored by
+    /// the user, so that they are normalized with the new tokenizer settings.
+    fn renormalize_synonyms_and_exact_words(&mut self) -> Result<()> {
+        if self.synonyms.is_not_set() {
+            if let Some(synonyms) = self.index.user_defined_synonyms(self.wtxn)? {
+                self.synonyms = Setting::Set(synonyms);
+            }
+        }
+
+        if self.exact_words.is_not_set() {
+            if let Some(words) = self.index.user_defined_exact_words(self.wtxn)? {
+                self.exact_words = Setting::Set(words);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<()>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
@@ -708,9 +970,10 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         self.update_primary_key()?;
         self.update_authorize_typos()?;
         self.update_min_typo_word_len()?;
-        self.update_exact_words()?;
         self.update_max_values_per_facet()?;
+        self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
+        self.update_search_cutoff()?;
 
         // If there is new faceted fields we indicate that we must reindex as we must
         // index new fields as facets. It means that the distinct attribute,
@@ -719,11 +982,35 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
         let faceted_updated = old_faceted_fields != new_faceted_fields;
 
         let stop_words_updated = self.update_stop_words()?;
+        let non_separator_tokens_updated = self.update_non_separator_tokens()?;
+        let separator_tokens_updated = self.update_separator_tokens()?;
+        let dictionary_updated = self.update_dictionary()?;
+
+        // The synonyms and the exact words are normalized with the tokenizer settings,
+        // they must be normalized again when one of these settings changed.
+        if stop_words_updated
+            || non_separator_tokens_updated
+            || separator_tokens_updated
+            || dictionary_updated
+        {
+            self.renormalize_synonyms_and_exact_words()?;
+        }
+
+        self.update_exact_words()?;
+        let localized_attributes_rules_updated = self.update_localized_attributes_rules()?;
+        let proximity_precision_updated = self.update_proximity_precision()?;
+        let prefix_search_updated = self.update_prefix_search()?;
         let synonyms_updated = self.update_synonyms()?;
         let searchable_updated = self.update_searchable()?;
         let exact_attributes_updated = self.update_exact_attributes()?;
 
         if stop_words_updated
+            || non_separator_tokens_updated
+            || separator_tokens_updated
+            || dictionary_updated
+            || localized_attributes_rules_updated
+            || proximity_precision_updated
+            || prefix_search_updated
             || faceted_updated
             || synonyms_updated
             || searchable_updated
@@ -738,15 +1025,18 @@ impl<'a, 't, 'u, 'i> Settings<'a, 't, 'u, 'i> {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use big_s::S;
     use heed::types::ByteSlice;
     use maplit::{btreeset, hashmap, hashset};
 
     use super::*;
+    use crate::documents::documents_batch_reader_from_objects;
     use crate::error::Error;
     use crate::index::tests::TempIndex;
     use crate::update::{ClearDocuments, DeleteDocuments};
-    use crate::{Criterion, Filter, SearchResult};
+    use crate::{Criterion, Filter, Locale, SearchResult, TimeBudget};
 
     #[test]
     fn set_and_reset_searchable_fields() {
@@ -1185,6 +1475,340 @@ mod tests {
         assert_eq!(result.documents_ids.len(), 1); // there is one benoit in our data
     }
 
+    #[test]
+    fn set_and_reset_separator_tokens() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "language": "I love C#" },
+                { "id": 1, "language": "I love C" },
+            ]))
+            .unwrap();
+
+        // by default the `#` is a separator, `C#` is indexed as `C`
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.separator_tokens(&rtxn).unwrap().is_none());
+        let result = index.search(&rtxn).query("C#").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0, 1]);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_non_separator_tokens(btreeset! { S("#") });
+                settings.set_separator_tokens(btreeset! { S("@@") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.non_separator_tokens(&rtxn).unwrap(), Some(btreeset! { S("#") }));
+        assert_eq!(index.separator_tokens(&rtxn).unwrap(), Some(btreeset! { S("@@") }));
+
+        // the documents have been reindexed, `C#` is now a word on its own
+        let result = index.search(&rtxn).query("C#").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0]);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_non_separator_tokens();
+                settings.reset_separator_tokens();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.non_separator_tokens(&rtxn).unwrap().is_none());
+        assert!(index.separator_tokens(&rtxn).unwrap().is_none());
+        let result = index.search(&rtxn).query("C#").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0, 1]);
+    }
+
+    #[test]
+    fn set_and_reset_dictionary() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "author": "J. R. R. Tolkien" },
+                { "id": 1, "author": "J. R. Hartley" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.dictionary(&rtxn).unwrap().is_none());
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_dictionary(btreeset! { S("J. R. R. Tolkien") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.dictionary(&rtxn).unwrap(), Some(btreeset! { S("J. R. R. Tolkien") }));
+
+        // the documents have been reindexed, the dictionary entry is now a single word
+        let result = index.search(&rtxn).query("J. R. R. Tolkien").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0]);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_dictionary();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.dictionary(&rtxn).unwrap().is_none());
+    }
+
+    #[test]
+    fn dictionary_update_normalizes_the_synonyms_again() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_synonyms(hashmap! {
+                    S("J. R. R. Tolkien") => vec![S("tolkien")],
+                });
+                settings.set_exact_words(btreeset! { S("J. R. R. Tolkien") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let synonyms = index.synonyms(&rtxn).unwrap();
+        let words: Vec<_> = synonyms.keys().map(Vec::len).collect();
+        assert_eq!(words, vec![4]);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_dictionary(btreeset! { S("J. R. R. Tolkien") });
+            })
+            .unwrap();
+
+        // the synonyms were normalized with the new dictionary, the entry is now a single word
+        let rtxn = index.read_txn().unwrap();
+        let synonyms = index.synonyms(&rtxn).unwrap();
+        let words: Vec<_> = synonyms.keys().map(Vec::len).collect();
+        assert_eq!(words, vec![1]);
+        assert_eq!(
+            index.user_defined_synonyms(&rtxn).unwrap(),
+            Some(hashmap! { S("J. R. R. Tolkien") => vec![S("tolkien")] })
+        );
+        assert_eq!(
+            index.user_defined_exact_words(&rtxn).unwrap(),
+            Some(btreeset! { S("J. R. R. Tolkien") })
+        );
+        assert_eq!(index.exact_words(&rtxn).unwrap().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn set_and_reset_localized_attributes_rules() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "title_ja": "東京の天気" },
+                { "id": 1, "title_ja": "大阪の天気" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.localized_attributes_rules(&rtxn).unwrap().is_none());
+        assert!(index.localized_attributes_locales(&rtxn).unwrap().is_empty());
+        drop(rtxn);
+
+        let rules = vec![LocalizedAttributesRule {
+            attribute_patterns: vec![S("*_ja")],
+            locales: vec![Locale::Jpn],
+        }];
+        index
+            .update_settings(|settings| {
+                settings.set_localized_attributes_rules(rules.clone());
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.localized_attributes_rules(&rtxn).unwrap(), Some(rules));
+        assert_eq!(index.localized_attributes_locales(&rtxn).unwrap(), vec![Locale::Jpn]);
+
+        // the query is tokenized with the locales of the rules by default
+        let result = index.search(&rtxn).query("東京").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![0]);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_localized_attributes_rules();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.localized_attributes_rules(&rtxn).unwrap().is_none());
+    }
+
+    #[test]
+    fn set_and_reset_proximity_precision() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "the quick brown fox", "overview": "jumps over the lazy dog" },
+                { "id": 1, "title": "the brown dog", "overview": "a quick fox" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.proximity_precision(&rtxn).unwrap().is_none());
+        assert!(!index.word_pair_proximity_docids.is_empty(&rtxn).unwrap());
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_proximity_precision(ProximityPrecision::ByAttribute);
+            })
+            .unwrap();
+
+        // the word pair proximity databases are dropped
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(
+            index.proximity_precision(&rtxn).unwrap(),
+            Some(ProximityPrecision::ByAttribute)
+        );
+        assert!(index.word_pair_proximity_docids.is_empty(&rtxn).unwrap());
+        assert!(index.word_prefix_pair_proximity_docids.is_empty(&rtxn).unwrap());
+        assert!(index.prefix_word_pair_proximity_docids.is_empty(&rtxn).unwrap());
+
+        // the documents with both words in the same attribute come first
+        let result = index.search(&rtxn).query("brown dog").execute().unwrap();
+        assert_eq!(result.documents_ids, vec![1, 0]);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_proximity_precision();
+            })
+            .unwrap();
+
+        // the word pair proximity databases are rebuilt
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.proximity_precision(&rtxn).unwrap().is_none());
+        assert!(!index.word_pair_proximity_docids.is_empty(&rtxn).unwrap());
+    }
+
+    #[test]
+    fn set_and_reset_search_cutoff() {
+        let index = TempIndex::new();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "the quick brown fox", "overview": "jumps over the lazy dog" },
+                { "id": 1, "title": "the brown dog", "overview": "a quick fox" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.search_cutoff(&rtxn).unwrap().is_none());
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_search_cutoff(150);
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.search_cutoff(&rtxn).unwrap(), Some(150));
+
+        // an exceeded budget still returns all the candidates but marks the search as degraded
+        let result = index
+            .search(&rtxn)
+            .query("quick fox")
+            .time_budget(TimeBudget::new(Duration::ZERO))
+            .execute()
+            .unwrap();
+        assert!(result.degraded);
+        assert_eq!(result.documents_ids.len(), 2);
+
+        let result = index.search(&rtxn).query("quick fox").execute().unwrap();
+        assert!(!result.degraded);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_search_cutoff();
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.search_cutoff(&rtxn).unwrap().is_none());
+    }
+
+    #[test]
+    fn set_and_reset_prefix_search() {
+        let index = TempIndex::new();
+
+        // enough words share the same prefix for it to be part of the prefix databases.
+        let documents = (0..200).map(|i: u8| {
+            let word = format!("qui{}{}", (b'a' + i / 26) as char, (b'a' + i % 26) as char);
+            serde_json::json!({ "id": i, "title": word }).as_object().unwrap().clone()
+        });
+        index.add_documents(documents_batch_reader_from_objects(documents)).unwrap();
+
+        let search = |rtxn: &heed::RoTxn, query: &str| {
+            let mut search = index.search(rtxn);
+            search.query(query).limit(1000);
+            search.execute().unwrap().documents_ids.len()
+        };
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.prefix_search(&rtxn).unwrap().is_none());
+        assert!(!index.words_prefixes_fst(&rtxn).unwrap().is_empty());
+        assert!(!index.word_prefix_docids.is_empty(&rtxn).unwrap());
+        assert_eq!(search(&rtxn, "qui"), 200);
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.set_prefix_search(PrefixSearch::Disabled);
+            })
+            .unwrap();
+
+        // the prefix databases are dropped
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.prefix_search(&rtxn).unwrap(), Some(PrefixSearch::Disabled));
+        assert!(index.words_prefixes_fst(&rtxn).unwrap().is_empty());
+        assert!(index.word_prefix_docids.is_empty(&rtxn).unwrap());
+        assert!(index.exact_word_prefix_docids.is_empty(&rtxn).unwrap());
+        assert!(index.word_prefix_pair_proximity_docids.is_empty(&rtxn).unwrap());
+        assert!(index.prefix_word_pair_proximity_docids.is_empty(&rtxn).unwrap());
+        assert!(index.word_prefix_position_docids.is_empty(&rtxn).unwrap());
+
+        // the last word of the query is a full word
+        assert_eq!(search(&rtxn, "qui"), 0);
+        assert_eq!(search(&rtxn, "quiaa"), 1);
+        drop(rtxn);
+
+        // the documents added afterward do not fill the prefix databases either
+        index.add_documents(documents!([{ "id": 200, "title": "quick" }])).unwrap();
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.word_prefix_docids.is_empty(&rtxn).unwrap());
+        drop(rtxn);
+
+        index
+            .update_settings(|settings| {
+                settings.reset_prefix_search();
+            })
+            .unwrap();
+
+        // the prefix databases are rebuilt
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.prefix_search(&rtxn).unwrap().is_none());
+        assert!(!index.word_prefix_docids.is_empty(&rtxn).unwrap());
+        assert_eq!(search(&rtxn, "qui"), 201);
+    }
+
     #[test]
     fn set_and_reset_synonyms() {
         let mut index = TempIndex::new();
@@ -1491,6 +2115,9 @@ mod tests {
                     sortable_fields,
                     criteria,
                     stop_words,
+                    non_separator_tokens,
+                    separator_tokens,
+                    dictionary,
                     distinct_field,
                     synonyms,
                     primary_key,
@@ -1500,7 +2127,12 @@ mod tests {
                     exact_words,
                     exact_attributes,
                     max_values_per_facet,
+                    sort_facet_values_by,
                     pagination_max_total_hits,
+                    localized_attributes_rules,
+                    proximity_precision,
+                    search_cutoff,
+                    prefix_search,
                 } = settings;
                 assert!(matches!(searchable_fields, Setting::NotSet));
                 assert!(matches!(displayed_fields, Setting::NotSet));
@@ -1508,6 +2140,9 @@ mod tests {
                 assert!(matches!(sortable_fields, Setting::NotSet));
                 assert!(matches!(criteria, Setting::NotSet));
                 assert!(matches!(stop_words, Setting::NotSet));
+                assert!(matches!(non_separator_tokens, Setting::NotSet));
+                assert!(matches!(separator_tokens, Setting::NotSet));
+                assert!(matches!(dictionary, Setting::NotSet));
                 assert!(matches!(distinct_field, Setting::NotSet));
                 assert!(matches!(synonyms, Setting::NotSet));
                 assert!(matches!(primary_key, Setting::NotSet));
@@ -1517,7 +2152,12 @@ mod tests {
                 assert!(matches!(exact_words, Setting::NotSet));
                 assert!(matches!(exact_attributes, Setting::NotSet));
                 assert!(matches!(max_values_per_facet, Setting::NotSet));
+                assert!(matches!(sort_facet_values_by, Setting::NotSet));
                 assert!(matches!(pagination_max_total_hits, Setting::NotSet));
+                assert!(matches!(localized_attributes_rules, Setting::NotSet));
+                assert!(matches!(proximity_precision, Setting::NotSet));
+                assert!(matches!(search_cutoff, Setting::NotSet));
+                assert!(matches!(prefix_search, Setting::NotSet));
             })
             .unwrap();
     }
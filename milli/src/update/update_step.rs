@@ -1,5 +1,7 @@
 use UpdateIndexingStep::*;
 
+use crate::update::index_documents::MERGED_DATABASE_COUNT;
+
 #[derive(Debug, Clone, Copy)]
 pub enum UpdateIndexingStep {
     /// Remap document addition fields the one present in the database, adding new fields in to the
@@ -32,4 +34,15 @@ impl UpdateIndexingStep {
     pub const fn number_of_steps(&self) -> usize {
         4
     }
+
+    /// Whether the step computes the prefix databases, which are merged
+    /// once all the other databases have been written.
+    pub fn is_computing_prefix_databases(&self) -> bool {
+        match self {
+            MergeDataIntoFinalDatabase { databases_seen, .. } => {
+                *databases_seen > MERGED_DATABASE_COUNT
+            }
+            _ => false,
+        }
+    }
 }
@@ -0,0 +1,418 @@
+//! An HNSW (Hierarchical Navigable Small World) graph stored in the `vector-hnsw` database.
+//!
+//! Every vector is a node of the graph stored under its own key along with its neighbors,
+//! so the vectors are inserted and removed one by one, by only reading and rewriting the
+//! nodes around them, instead of rebuilding and rewriting the whole graph.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::mem::take;
+
+use heed::RoTxn;
+use ordered_float::OrderedFloat;
+use roaring::RoaringBitmap;
+use serde::{Deserialize, Serialize};
+
+use super::NDotProductPoint;
+use crate::error::InternalError;
+use crate::index::db_name;
+use crate::{Index, Result, TimeBudget, BEU32};
+
+/// The maximum number of neighbors of a node on the upper layers of the graph.
+const MAX_NEIGHBORS: usize = 16;
+/// The maximum number of neighbors of a node on the bottom layer, where all the nodes are.
+const MAX_BOTTOM_NEIGHBORS: usize = 2 * MAX_NEIGHBORS;
+/// The number of closest nodes searched for when inserting a node, among which its neighbors are chosen.
+const EF_CONSTRUCTION: usize = 100;
+/// The minimum number of closest nodes searched for when searching the graph.
+const EF_SEARCH: usize = 64;
+/// The highest layer a node can belong to.
+const MAX_LAYER: usize = 16;
+
+/// The identifier of a vector in the graph.
+pub type PointId = u32;
+
+/// A vector of the graph along with its neighbors on each layer it belongs to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HnswNode {
+    pub point: NDotProductPoint,
+    /// The neighbors of the node, starting with the bottom layer.
+    pub layers: Vec<Vec<PointId>>,
+}
+
+/// Returns the number of dimensions of the vectors of the graph, `None` when it is empty.
+pub(crate) fn dimensions(index: &Index, rtxn: &RoTxn) -> Result<Option<usize>> {
+    match index.vector_hnsw_entry_point(rtxn)? {
+        Some(entry_point) => Ok(Some(get_node(index, rtxn, entry_point)?.point.len())),
+        None => Ok(None),
+    }
+}
+
+/// Inserts the point in the graph and returns its newly allocated identifier.
+pub(crate) fn insert(
+    index: &Index,
+    wtxn: &mut heed::RwTxn,
+    point: NDotProductPoint,
+) -> Result<PointId> {
+    let pid = match index.vector_hnsw.last(wtxn)? {
+        Some((last, _)) => last.get() + 1,
+        None => 0,
+    };
+    let level = random_level(pid);
+    let mut node = HnswNode { point, layers: vec![Vec::new(); level + 1] };
+
+    let entry_point = match index.vector_hnsw_entry_point(wtxn)? {
+        Some(entry_point) => entry_point,
+        None => {
+            index.vector_hnsw.put(wtxn, &BEU32::new(pid), &node)?;
+            index.put_vector_hnsw_entry_point(wtxn, pid)?;
+            return Ok(pid);
+        }
+    };
+
+    let entry_node = get_node(index, wtxn, entry_point)?;
+    let top_layer = entry_node.layers.len() - 1;
+    let mut closest = vec![(node.point.distance(&entry_node.point), entry_point)];
+    for layer in (level + 1..=top_layer).rev() {
+        closest = search_layer(index, wtxn, &node.point, closest, 1, layer, None)?;
+    }
+
+    // the node is written first for its vector to be found when its neighbors are pruned.
+    index.vector_hnsw.put(wtxn, &BEU32::new(pid), &node)?;
+    for layer in (0..=level.min(top_layer)).rev() {
+        closest = search_layer(index, wtxn, &node.point, closest, EF_CONSTRUCTION, layer, None)?;
+        let neighbors: Vec<_> =
+            closest.iter().take(max_neighbors(layer)).map(|&(_, neighbor)| neighbor).collect();
+        for &neighbor in &neighbors {
+            connect(index, wtxn, neighbor, pid, layer)?;
+        }
+        node.layers[layer] = neighbors;
+    }
+    index.vector_hnsw.put(wtxn, &BEU32::new(pid), &node)?;
+
+    if level > top_layer {
+        index.put_vector_hnsw_entry_point(wtxn, pid)?;
+    }
+
+    Ok(pid)
+}
+
+/// Removes the points from the graph.
+///
+/// The neighbors of a removed node are reconnected with its other neighbors,
+/// so that the graph remains navigable without being rebuilt.
+pub(crate) fn remove(index: &Index, wtxn: &mut heed::RwTxn, removed: &RoaringBitmap) -> Result<()> {
+    let mut entry_point = index.vector_hnsw_entry_point(wtxn)?;
+
+    for pid in removed {
+        let node = match index.vector_hnsw.get(wtxn, &BEU32::new(pid))? {
+            Some(node) => node,
+            None => continue,
+        };
+        index.vector_hnsw.delete(wtxn, &BEU32::new(pid))?;
+
+        for (layer, neighbors) in node.layers.iter().enumerate() {
+            for &neighbor in neighbors.iter().filter(|&&n| !removed.contains(n)) {
+                let mut neighbor_node = match index.vector_hnsw.get(wtxn, &BEU32::new(neighbor))? {
+                    Some(neighbor_node) => neighbor_node,
+                    None => continue,
+                };
+                let links = match neighbor_node.layers.get_mut(layer) {
+                    Some(links) => take(links),
+                    None => continue,
+                };
+                let candidates = links
+                    .into_iter()
+                    .chain(neighbors.iter().copied().filter(|&n| n != neighbor))
+                    .filter(|&n| n != pid && !removed.contains(n));
+                neighbor_node.layers[layer] = closest_neighbors(
+                    index,
+                    wtxn,
+                    &neighbor_node.point,
+                    candidates,
+                    max_neighbors(layer),
+                )?;
+                index.vector_hnsw.put(wtxn, &BEU32::new(neighbor), &neighbor_node)?;
+            }
+        }
+
+        if entry_point == Some(pid) {
+            entry_point = new_entry_point(index, wtxn, &node, removed)?;
+        }
+    }
+
+    match entry_point {
+        Some(entry_point) => index.put_vector_hnsw_entry_point(wtxn, entry_point)?,
+        None => {
+            index.delete_vector_hnsw_entry_point(wtxn)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the approximate closest points to the target along with their distance,
+/// ordered by increasing distance.
+///
+/// At least `ef` points are searched for, the search stops early when the time budget
+/// is exceeded and then returns the closest points found so far.
+pub(crate) fn search(
+    index: &Index,
+    rtxn: &RoTxn,
+    target: &NDotProductPoint,
+    ef: usize,
+    time_budget: &TimeBudget,
+) -> Result<Vec<(PointId, f32)>> {
+    let entry_point = match index.vector_hnsw_entry_point(rtxn)? {
+        Some(entry_point) => entry_point,
+        None => return Ok(Vec::new()),
+    };
+
+    let entry_node = get_node(index, rtxn, entry_point)?;
+    let mut closest = vec![(target.distance(&entry_node.point), entry_point)];
+    for layer in (1..entry_node.layers.len()).rev() {
+        closest = search_layer(index, rtxn, target, closest, 1, layer, Some(time_budget))?;
+    }
+    let ef = ef.max(EF_SEARCH);
+    let closest = search_layer(index, rtxn, target, closest, ef, 0, Some(time_budget))?;
+
+    Ok(closest.into_iter().map(|(distance, pid)| (pid, distance)).collect())
+}
+
+/// Returns the `ef` closest nodes to the target found on the layer by exploring the graph
+/// from the entry points, ordered by increasing distance.
+fn search_layer(
+    index: &Index,
+    rtxn: &RoTxn,
+    target: &NDotProductPoint,
+    entry_points: Vec<(f32, PointId)>,
+    ef: usize,
+    layer: usize,
+    time_budget: Option<&TimeBudget>,
+) -> Result<Vec<(f32, PointId)>> {
+    let mut visited: HashSet<_> = entry_points.iter().map(|&(_, pid)| pid).collect();
+    // the candidates to explore, the closest first.
+    let mut candidates = BinaryHeap::new();
+    // the closest nodes found, the furthest first.
+    let mut found = BinaryHeap::new();
+    for (distance, pid) in entry_points {
+        candidates.push(Reverse((OrderedFloat(distance), pid)));
+        found.push((OrderedFloat(distance), pid));
+    }
+    while found.len() > ef {
+        found.pop();
+    }
+
+    while let Some(Reverse((distance, pid))) = candidates.pop() {
+        let furthest = found.peek().map(|&(distance, _)| distance);
+        if found.len() >= ef && furthest.map_or(false, |furthest| distance > furthest) {
+            break;
+        }
+        if time_budget.map_or(false, TimeBudget::degrade_if_exceeded) {
+            break;
+        }
+
+        let node = match index.vector_hnsw.get(rtxn, &BEU32::new(pid))? {
+            Some(node) => node,
+            None => continue,
+        };
+        for &neighbor in node.layers.get(layer).into_iter().flatten() {
+            if !visited.insert(neighbor) {
+                continue;
+            }
+            // the links to the removed nodes are dropped the next time their node is pruned.
+            let neighbor_node = match index.vector_hnsw.get(rtxn, &BEU32::new(neighbor))? {
+                Some(neighbor_node) => neighbor_node,
+                None => continue,
+            };
+            let distance = OrderedFloat(target.distance(&neighbor_node.point));
+            let furthest = found.peek().map(|&(distance, _)| distance);
+            if found.len() < ef || furthest.map_or(true, |furthest| distance < furthest) {
+                candidates.push(Reverse((distance, neighbor)));
+                found.push((distance, neighbor));
+                if found.len() > ef {
+                    found.pop();
+                }
+            }
+        }
+    }
+
+    let mut found = found.into_vec();
+    found.sort_unstable();
+    Ok(found.into_iter().map(|(distance, pid)| (distance.0, pid)).collect())
+}
+
+/// Adds the `new` node to the neighbors of the node on the layer,
+/// only keeping its closest neighbors.
+fn connect(
+    index: &Index,
+    wtxn: &mut heed::RwTxn,
+    pid: PointId,
+    new: PointId,
+    layer: usize,
+) -> Result<()> {
+    let mut node = get_node(index, wtxn, pid)?;
+    let links = match node.layers.get_mut(layer) {
+        Some(links) if !links.contains(&new) => links,
+        _ => return Ok(()),
+    };
+
+    links.push(new);
+    if links.len() > max_neighbors(layer) {
+        let links = take(links);
+        node.layers[layer] =
+            closest_neighbors(index, wtxn, &node.point, links, max_neighbors(layer))?;
+    }
+
+    index.vector_hnsw.put(wtxn, &BEU32::new(pid), &node)?;
+    Ok(())
+}
+
+/// Returns the `max` candidates the closest to the point, ignoring the removed ones.
+fn closest_neighbors(
+    index: &Index,
+    rtxn: &RoTxn,
+    point: &NDotProductPoint,
+    candidates: impl IntoIterator<Item = PointId>,
+    max: usize,
+) -> Result<Vec<PointId>> {
+    let mut seen = HashSet::new();
+    let mut scored = Vec::new();
+    for candidate in candidates {
+        if seen.insert(candidate) {
+            if let Some(node) = index.vector_hnsw.get(rtxn, &BEU32::new(candidate))? {
+                scored.push((OrderedFloat(point.distance(&node.point)), candidate));
+            }
+        }
+    }
+
+    scored.sort_unstable();
+    Ok(scored.into_iter().take(max).map(|(_, pid)| pid).collect())
+}
+
+/// Returns the node that replaces the removed entry point: the remaining node
+/// with the most layers, looked for among its neighbors first.
+fn new_entry_point(
+    index: &Index,
+    rtxn: &RoTxn,
+    removed_entry_point: &HnswNode,
+    removed: &RoaringBitmap,
+) -> Result<Option<PointId>> {
+    // the neighbors on the highest layer are the nodes with the most layers after the entry point.
+    for neighbors in removed_entry_point.layers.iter().rev() {
+        let mut best: Option<(usize, PointId)> = None;
+        for &neighbor in neighbors.iter().filter(|&&n| !removed.contains(n)) {
+            if let Some(node) = index.vector_hnsw.get(rtxn, &BEU32::new(neighbor))? {
+                if best.map_or(true, |(layers, _)| node.layers.len() > layers) {
+                    best = Some((node.layers.len(), neighbor));
+                }
+            }
+        }
+        if let Some((_, pid)) = best {
+            return Ok(Some(pid));
+        }
+    }
+
+    // the entry point was isolated, this only happens with a handful of vectors.
+    let mut best: Option<(usize, PointId)> = None;
+    for result in index.vector_hnsw.iter(rtxn)? {
+        let (pid, node) = result?;
+        let pid = pid.get();
+        if !removed.contains(pid) && best.map_or(true, |(layers, _)| node.layers.len() > layers) {
+            best = Some((node.layers.len(), pid));
+        }
+    }
+    Ok(best.map(|(_, pid)| pid))
+}
+
+fn get_node(index: &Index, rtxn: &RoTxn, pid: PointId) -> Result<HnswNode> {
+    index.vector_hnsw.get(rtxn, &BEU32::new(pid))?.ok_or_else(|| {
+        InternalError::DatabaseMissingEntry { db_name: db_name::VECTOR_HNSW, key: None }.into()
+    })
+}
+
+fn max_neighbors(layer: usize) -> usize {
+    if layer == 0 {
+        MAX_BOTTOM_NEIGHBORS
+    } else {
+        MAX_NEIGHBORS
+    }
+}
+
+/// Draws the highest layer of a node from an exponentially decaying distribution,
+/// the number drawn is derived from the point id so that the graph is deterministic.
+fn random_level(pid: PointId) -> usize {
+    // the splitmix64 finalizer spreads consecutive ids over the whole u64 range.
+    let mut x = (pid as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^= x >> 31;
+
+    // a uniform number in ]0, 1].
+    let uniform = ((x >> 11) + 1) as f64 / (1u64 << 53) as f64;
+    let level_multiplier = 1.0 / (MAX_NEIGHBORS as f64).ln();
+    ((-uniform.ln() * level_multiplier) as usize).min(MAX_LAYER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::tests::TempIndex;
+
+    fn point(angle: f32) -> NDotProductPoint {
+        NDotProductPoint::new(vec![angle.cos(), angle.sin()])
+    }
+
+    #[test]
+    fn insert_search_and_remove() {
+        let index = TempIndex::new();
+        let mut wtxn = index.write_txn().unwrap();
+
+        let angles: Vec<f32> = (0..500).map(|i| i as f32 * std::f32::consts::TAU / 500.0).collect();
+        for &angle in &angles {
+            insert(&index, &mut wtxn, point(angle)).unwrap();
+        }
+        assert_eq!(index.vector_hnsw.len(&wtxn).unwrap(), 500);
+        assert_eq!(dimensions(&index, &wtxn).unwrap(), Some(2));
+
+        // the closest points to a target are its neighbors on the circle.
+        let found = search(&index, &wtxn, &point(angles[100]), 5, &TimeBudget::max()).unwrap();
+        let closest: Vec<_> = found.iter().take(5).map(|&(pid, _)| pid).collect();
+        assert_eq!(closest[0], 100);
+        let mut closest = closest;
+        closest.sort_unstable();
+        assert_eq!(closest, vec![98, 99, 100, 101, 102]);
+
+        // the removed points are never returned and the graph remains navigable.
+        let removed = RoaringBitmap::from_iter((0..500).filter(|pid| pid % 2 == 0));
+        remove(&index, &mut wtxn, &removed).unwrap();
+        assert_eq!(index.vector_hnsw.len(&wtxn).unwrap(), 250);
+
+        let found = search(&index, &wtxn, &point(angles[100]), 4, &TimeBudget::max()).unwrap();
+        let mut closest: Vec<_> = found.iter().take(4).map(|&(pid, _)| pid).collect();
+        closest.sort_unstable();
+        assert_eq!(closest, vec![97, 99, 101, 103]);
+
+        // the new points get new ids and can be found.
+        let pid = insert(&index, &mut wtxn, point(angles[100])).unwrap();
+        assert_eq!(pid, 500);
+        let found = search(&index, &wtxn, &point(angles[100]), 1, &TimeBudget::max()).unwrap();
+        assert_eq!(found[0].0, 500);
+
+        // removing all the points empties the graph.
+        let all = RoaringBitmap::from_iter(0..=500);
+        remove(&index, &mut wtxn, &all).unwrap();
+        assert!(index.vector_hnsw.is_empty(&wtxn).unwrap());
+        assert_eq!(index.vector_hnsw_entry_point(&wtxn).unwrap(), None);
+        assert!(search(&index, &wtxn, &point(0.0), 1, &TimeBudget::max()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn random_levels_decay() {
+        let levels: Vec<_> = (0..10_000).map(random_level).collect();
+        let bottom_only = levels.iter().filter(|&&level| level == 0).count();
+        // with 16 neighbors, 1 node out of 16 goes to the upper layers.
+        assert!((9_000..9_700).contains(&bottom_only), "{bottom_only}");
+        assert!(levels.iter().all(|&level| level <= MAX_LAYER));
+    }
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+
+pub use self::hnsw::HnswNode;
+
+pub(crate) mod hnsw;
+
+/// A vector normalized at construction, the distance between two points
+/// is then only based on the angle between them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NDotProductPoint(Vec<f32>);
+
+impl NDotProductPoint {
+    pub fn new(point: Vec<f32>) -> Self {
+        let mut point = point;
+        let norm = point.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            point.iter_mut().for_each(|x| *x /= norm);
+        }
+        NDotProductPoint(point)
+    }
+
+    pub fn into_inner(self) -> Vec<f32> {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The distance between the two points, from `0.0` when they have the same direction
+    /// to `2.0` when they have opposite directions.
+    pub fn distance(&self, other: &Self) -> f32 {
+        let dist = 1.0 - dot_product_similarity(&self.0, &other.0);
+        debug_assert!(!dist.is_nan());
+        dist
+    }
+}
+
+/// Returns the dot product of the two vectors,
+/// it is the cosine similarity when both vectors are normalized.
+pub fn dot_product_similarity(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+/// The value of the `_vectors` field of a document,
+/// either a single vector or an array of vectors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum VectorOrArrayOfVectors {
+    Vector(Vec<f32>),
+    Vectors(Vec<Vec<f32>>),
+}
+
+impl VectorOrArrayOfVectors {
+    pub fn into_array_of_vectors(self) -> Vec<Vec<f32>> {
+        match self {
+            // an empty array does not contain any vector.
+            VectorOrArrayOfVectors::Vector(vector) if vector.is_empty() => Vec::new(),
+            VectorOrArrayOfVectors::Vector(vector) => vec![vector],
+            VectorOrArrayOfVectors::Vectors(vectors) => vectors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_only_depends_on_the_angle() {
+        let a = NDotProductPoint::new(vec![1.0, 0.0]);
+        let b = NDotProductPoint::new(vec![10.0, 0.0]);
+        let c = NDotProductPoint::new(vec![0.0, 3.0]);
+        let d = NDotProductPoint::new(vec![-2.0, 0.0]);
+
+        assert_eq!(a.distance(&b), 0.0);
+        assert_eq!(a.distance(&c), 1.0);
+        assert_eq!(a.distance(&d), 2.0);
+    }
+
+    #[test]
+    fn deserialize_vectors() {
+        let vectors: VectorOrArrayOfVectors = serde_json::from_str("[1, 2.5]").unwrap();
+        assert_eq!(vectors.into_array_of_vectors(), vec![vec![1.0, 2.5]]);
+
+        let vectors: VectorOrArrayOfVectors = serde_json::from_str("[[1], [2.5]]").unwrap();
+        assert_eq!(vectors.into_array_of_vectors(), vec![vec![1.0], vec![2.5]]);
+
+        let vectors: VectorOrArrayOfVectors = serde_json::from_str("[]").unwrap();
+        assert!(vectors.into_array_of_vectors().is_empty());
+
+        assert!(serde_json::from_str::<VectorOrArrayOfVectors>(r#"{ "a": 1 }"#).is_err());
+        assert!(serde_json::from_str::<VectorOrArrayOfVectors>(r#"[1, "a"]"#).is_err());
+    }
+}
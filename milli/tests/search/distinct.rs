@@ -177,3 +177,56 @@ test_distinct!(
     vec![Words, Exactness],
     7
 );
+
+#[test]
+fn distinct_at_search_time_overrides_the_setting() {
+    let criteria = vec![Words, Typo, Proximity, Attribute, Exactness];
+    let index = search::setup_search_index_with_criteria(&criteria);
+
+    let mut wtxn = index.write_txn().unwrap();
+    let config = milli::update::IndexerConfig::default();
+    let mut builder = Settings::new(&mut wtxn, &index, &config);
+    builder.set_distinct_field(S("asc_desc_rank"));
+    builder.execute(|_| (), || false).unwrap();
+    wtxn.commit().unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+
+    let mut search = Search::new(&rtxn, &index);
+    search.query(search::TEST_QUERY);
+    search.limit(EXTERNAL_DOCUMENTS_IDS.len());
+    search.exhaustive_number_hits(true);
+    search.terms_matching_strategy(TermsMatchingStrategy::default());
+    search.distinct(S("tag"));
+
+    let SearchResult { documents_ids, candidates, .. } = search.execute().unwrap();
+
+    // the documents are deduplicated on the `tag` and not on the `asc_desc_rank`.
+    assert_eq!(candidates.len(), 3);
+
+    let mut distinct_values = HashSet::new();
+    let expected_external_ids: Vec<_> =
+        search::expected_order(&criteria, true, TermsMatchingStrategy::default(), &[])
+            .into_iter()
+            .filter_map(|d| distinct_values.insert(d.tag.to_owned()).then_some(d.id))
+            .collect();
+
+    let documents_ids = search::internal_to_external_ids(&index, &documents_ids);
+    assert_eq!(documents_ids, expected_external_ids);
+}
+
+#[test]
+fn distinct_at_search_time_must_be_filterable() {
+    let index = search::setup_search_index_with_criteria(&[Words]);
+    let rtxn = index.read_txn().unwrap();
+
+    let mut search = Search::new(&rtxn, &index);
+    search.query(search::TEST_QUERY);
+    search.distinct(S("title"));
+
+    let error = search.execute().unwrap_err();
+    assert!(matches!(
+        error,
+        milli::Error::UserError(milli::UserError::InvalidDistinctAttribute { .. })
+    ));
+}